@@ -1,15 +1,19 @@
-use clap::Parser;
+use clap::{error::ErrorKind, CommandFactory, Parser};
+use std::io::{BufRead, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 use towl::{
-    cli::{Cli, OutputFormat, TowlCommands},
+    cli::{Cli, ConfigAction, OutputFormat, TowlCommands},
     comment::todo::{TodoComment, TodoType},
-    config::{GitHubConfig, TowlConfig},
+    config::{
+        error::TowlConfigError, ConfigOverride, DiscoveryMode, GitHubConfig, LoadOptions,
+        ProjectSurvey, TowlConfig,
+    },
     error::TowlError,
-    github::{CreatedIssue, GitHubClient},
+    github::{CreatedIssue, GitHubClient, SourceLinks},
     llm::{types::Validity, AnalysisSummary},
-    output::Output,
+    output::{Output, ScanMetadata},
     processor::{Processor, ProcessorResult},
-    scanner::{ScanResult, Scanner},
+    scanner::{GitRef, ScanResult, Scanner},
 };
 use tracing::{debug, info, warn};
 
@@ -39,26 +43,68 @@ async fn main() {
 
 async fn run_cli(cli: Cli) -> Result<(), TowlError> {
     match cli.command {
-        TowlCommands::Init { path, force } => init_config(path, force).await,
+        TowlCommands::Init { path, force, yes } => init_config(path, force, yes, &cli.set).await,
         TowlCommands::Scan {
             config,
+            profile,
             path,
             non_interactive,
             format,
             output,
+            template,
+            junit_failures,
             todo_type,
+            include,
+            discovery,
+            follow_symlinks,
+            placeholders,
+            git_ref,
+            stdin,
+            stdin_filename,
             verbose,
             github,
             dry_run,
             ai,
         } => {
+            let overrides = ScanOverrides {
+                include,
+                discovery,
+                follow_symlinks,
+                placeholders,
+            };
+            let load = LoadOptions {
+                path: config,
+                profile,
+                overrides: cli.set,
+            };
+            if !junit_failures.is_empty() && format != Some(OutputFormat::Junit) {
+                Cli::command()
+                    .error(
+                        ErrorKind::ArgumentConflict,
+                        "--junit-failures only applies to --format junit",
+                    )
+                    .exit();
+            }
             if non_interactive {
+                let Some(format) = format.or_else(|| OutputFormat::infer(output.as_deref())) else {
+                    Cli::command()
+                        .error(
+                            ErrorKind::MissingRequiredArgument,
+                            "cannot infer the output format from the --output file name; pass --format",
+                        )
+                        .exit();
+                };
                 let opts = ScanOpts {
-                    config,
+                    load,
                     path,
                     format,
                     output,
+                    template,
+                    junit_failures,
                     todo_type,
+                    overrides,
+                    git_ref,
+                    stdin_filename: stdin.then_some(stdin_filename),
                     verbose,
                     github,
                     dry_run,
@@ -66,28 +112,175 @@ async fn run_cli(cli: Cli) -> Result<(), TowlError> {
                 };
                 scan_todos(opts).await
             } else {
-                run_interactive(config, path, ai).await
+                run_interactive(&load, path, overrides, ai).await
             }
         }
-        TowlCommands::Config { config } => show_config(config.as_ref()),
+        TowlCommands::Config {
+            config,
+            profile,
+            show_origin,
+            action,
+        } => match action {
+            None => show_config(
+                &LoadOptions {
+                    path: config,
+                    profile,
+                    overrides: cli.set,
+                },
+                show_origin,
+            ),
+            Some(ConfigAction::Validate { path }) => validate_config(path.or(config).as_ref()),
+            Some(ConfigAction::Schema) => print_schema(),
+        },
     }
 }
 
-async fn init_config(path: PathBuf, force: bool) -> Result<(), TowlError> {
-    TowlConfig::init(&path, force).await?;
+async fn init_config(
+    path: PathBuf,
+    force: bool,
+    yes: bool,
+    overrides: &[ConfigOverride],
+) -> Result<(), TowlError> {
+    if !force && path.exists() {
+        TowlConfig::init(&path, false).await?;
+        info!("Initialized config file at: {}", path.display());
+        return Ok(());
+    }
+
+    let mut survey = ProjectSurvey::detect(Path::new("."));
+    if !yes && std::io::stdin().is_terminal() {
+        let stdin = std::io::stdin();
+        if !review_survey(&mut survey, &path, &mut stdin.lock())? {
+            info!("Nothing written");
+            return Ok(());
+        }
+    } else {
+        for language in &survey.languages {
+            info!("Detected {} ({} files)", language.name, language.files);
+        }
+        for pattern in &survey.excludes {
+            info!("Excluding {pattern}");
+        }
+    }
+
+    let proposed = survey.to_config().with_overrides(overrides)?;
+    TowlConfig::init_from(&path, force, proposed).await?;
     info!("Initialized config file at: {}", path.display());
     Ok(())
 }
 
+/// Asks which detected languages and excludes to keep, then whether to
+/// write the config. Returns false if the user declined to write it.
+fn review_survey(
+    survey: &mut ProjectSurvey,
+    path: &Path,
+    input: &mut impl BufRead,
+) -> Result<bool, TowlConfigError> {
+    if survey.languages.is_empty() {
+        println!("No known languages found; the default settings will be used.");
+    } else {
+        println!("Detected languages:");
+        for language in &survey.languages {
+            println!("  {:<12} {:>6} files", language.name, language.files);
+        }
+    }
+
+    let mut languages = Vec::with_capacity(survey.languages.len());
+    for language in std::mem::take(&mut survey.languages) {
+        if confirm(&format!("Scan {} files?", language.name), input)? {
+            languages.push(language);
+        }
+    }
+    survey.languages = languages;
+
+    let mut excludes = Vec::with_capacity(survey.excludes.len());
+    for pattern in std::mem::take(&mut survey.excludes) {
+        if confirm(&format!("Exclude {pattern}?"), input)? {
+            excludes.push(pattern);
+        }
+    }
+    survey.excludes = excludes;
+
+    confirm(&format!("Write {}?", path.display()), input)
+}
+
+/// Prompts for a yes/no answer, defaulting to yes on an empty line or end of input.
+fn confirm(question: &str, input: &mut impl BufRead) -> Result<bool, TowlConfigError> {
+    loop {
+        print!("{question} [Y/n] ");
+        std::io::stdout()
+            .flush()
+            .map_err(TowlConfigError::PromptError)?;
+        let mut answer = String::new();
+        if input
+            .read_line(&mut answer)
+            .map_err(TowlConfigError::PromptError)?
+            == 0
+        {
+            println!();
+            return Ok(true);
+        }
+        match answer.trim().to_ascii_lowercase().as_str() {
+            "" | "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ => println!("Please answer y or n."),
+        }
+    }
+}
+
+/// Where the content to scan comes from.
+enum ScanSource {
+    WorkingTree,
+    GitRef(GitRef),
+    Stdin {
+        filename: PathBuf,
+    },
+    /// An archive scanned in place; its entries are not working tree files.
+    Archive,
+}
+
+/// Scan settings given on the command line that take precedence over the config file.
+struct ScanOverrides {
+    include: Vec<String>,
+    discovery: Option<DiscoveryMode>,
+    follow_symlinks: bool,
+    placeholders: bool,
+}
+
+/// Loads the config and scans `source`, passing each file's TODOs to
+/// `on_found` as they are found.
 async fn load_and_scan(
-    config_path: Option<&PathBuf>,
+    load: &LoadOptions,
     path: &Path,
+    overrides: ScanOverrides,
+    source: &ScanSource,
+    mut on_found: impl FnMut(&[TodoComment]),
 ) -> Result<(TowlConfig, ScanResult), TowlError> {
     info!("Scanning {}", path.display());
-    let config = TowlConfig::load(config_path)?;
+    let mut config = TowlConfig::load_with(load)?;
+    config.override_include_patterns(overrides.include)?;
+    if let Some(discovery) = overrides.discovery {
+        config.parsing.discovery = discovery;
+    }
+    if overrides.follow_symlinks {
+        config.parsing.follow_symlinks = true;
+    }
+    if overrides.placeholders {
+        config.parsing.detect_placeholders = true;
+    }
     info!("Scan config\n{}", config);
     let scanner = Scanner::new(config.parsing.clone())?; // clone: scanner takes ownership of ParsingConfig
-    let scan_result = scanner.scan(path.to_path_buf()).await?; // clone: scan takes owned PathBuf
+    let scan_result = match source {
+        ScanSource::WorkingTree | ScanSource::Archive => {
+            scanner.scan_with(path.to_path_buf(), on_found).await? // clone: scan takes owned PathBuf
+        }
+        ScanSource::GitRef(git_ref) => scanner.scan_ref_with(path, git_ref, on_found).await?,
+        ScanSource::Stdin { filename } => {
+            let result = scanner.scan_reader(filename, tokio::io::stdin()).await?;
+            on_found(&result.todos);
+            result
+        }
+    };
 
     if scan_result.all_files_failed() {
         eprintln!(
@@ -100,11 +293,16 @@ async fn load_and_scan(
 }
 
 struct ScanOpts {
-    config: Option<PathBuf>,
+    load: LoadOptions,
     path: PathBuf,
     format: OutputFormat,
     output: Option<PathBuf>,
+    template: Option<PathBuf>,
+    junit_failures: Vec<TodoType>,
     todo_type: Option<TodoType>,
+    overrides: ScanOverrides,
+    git_ref: Option<String>,
+    stdin_filename: Option<PathBuf>,
     verbose: bool,
     github: bool,
     dry_run: bool,
@@ -112,7 +310,34 @@ struct ScanOpts {
 }
 
 async fn scan_todos(opts: ScanOpts) -> Result<(), TowlError> {
-    let (config, scan_result) = load_and_scan(opts.config.as_ref(), &opts.path).await?;
+    let source = match (opts.git_ref.as_deref(), opts.stdin_filename) {
+        (Some(name), _) => ScanSource::GitRef(GitRef::resolve(&opts.path, name).await?),
+        (None, Some(filename)) => ScanSource::Stdin { filename },
+        (None, None) if Scanner::is_archive(&opts.path) => ScanSource::Archive,
+        (None, None) => ScanSource::WorkingTree,
+    };
+    let mut output = Output::new(opts.format, opts.output.clone())?; // clone: path is logged below
+    if !opts.junit_failures.is_empty() {
+        output = output.with_junit_failures(opts.junit_failures);
+    }
+    if let Some(template) = &opts.template {
+        output = output.with_template(template)?;
+    }
+    // Streaming formats write during the scan, unless AI analysis must see
+    // every TODO first
+    let mut stream = if opts.ai { None } else { output.stream()? };
+    let todo_type = opts.todo_type;
+    let (mut config, scan_result) =
+        load_and_scan(&opts.load, &opts.path, opts.overrides, &source, |found| {
+            if let Some(stream) = stream.as_mut() {
+                stream.write(
+                    found
+                        .iter()
+                        .filter(|todo| todo_type.map_or(true, |t| todo.todo_type == t)),
+                );
+            }
+        })
+        .await?;
 
     let files_scanned = scan_result.files_scanned;
     let files_skipped = scan_result.files_skipped;
@@ -121,6 +346,7 @@ async fn scan_todos(opts: ScanOpts) -> Result<(), TowlError> {
     let mut filtered_todos = filter_todos(scan_result.todos, opts.todo_type);
 
     if opts.ai {
+        config.llm.resolve_api_key().await?;
         let summary =
             towl::llm::analyse::analyse_todos(&mut filtered_todos, &config.llm, |_, _| {}).await?;
         let before = filtered_todos.len();
@@ -147,10 +373,44 @@ async fn scan_todos(opts: ScanOpts) -> Result<(), TowlError> {
         );
     }
 
-    save_output(opts.format, opts.output, &filtered_todos, opts.verbose).await?;
+    let source_links = if opts.format == OutputFormat::Html {
+        report_source_links(&opts.path, &config.github, &source).await
+    } else {
+        None
+    };
+    if let Some(stream) = stream {
+        let written = stream.finish()?;
+        if opts.verbose {
+            info!("Successfully streamed {written} todos to output");
+        }
+    } else {
+        let metadata = ScanMetadata {
+            path: opts.path.clone(), // clone: the path is used again for issue creation
+            git_ref: opts.git_ref,
+            files_scanned,
+            files_skipped,
+            files_errored,
+            duration_ms: duration.as_millis(),
+        };
+        save_output(
+            output
+                .with_source_links(source_links)
+                .with_scan_metadata(metadata),
+            &filtered_todos,
+            opts.verbose,
+        )
+        .await?;
+    }
 
     if opts.github {
-        create_github_issues(&opts.path, &config.github, filtered_todos, opts.dry_run).await?;
+        create_github_issues(
+            &opts.path,
+            config.github,
+            filtered_todos,
+            opts.dry_run,
+            &source,
+        )
+        .await?;
     }
 
     Ok(())
@@ -158,9 +418,10 @@ async fn scan_todos(opts: ScanOpts) -> Result<(), TowlError> {
 
 async fn create_github_issues(
     repo_root: &Path,
-    github_config: &GitHubConfig,
+    mut github_config: GitHubConfig,
     todos: Vec<TodoComment>,
     dry_run: bool,
+    source: &ScanSource,
 ) -> Result<(), TowlError> {
     if todos.is_empty() {
         debug!("No TODOs found, skipping GitHub issue creation");
@@ -172,14 +433,25 @@ async fn create_github_issues(
         return Ok(());
     }
 
-    let mut client = GitHubClient::new(github_config)?;
+    github_config.resolve_token().await?;
+    let mut client = GitHubClient::new(&github_config)?;
+    if let ScanSource::GitRef(git_ref) = source {
+        client.set_link_ref(git_ref.link_name());
+    }
     client.load_existing_issues().await?;
 
     let (replacements, skipped, failed) = submit_issues(&mut client, todos).await;
     let created = replacements.len();
-    let result = Processor::replace_todos(repo_root, &replacements).await;
+    // Line numbers from a git ref, stdin or an archive do not describe the
+    // working tree
+    let result = match source {
+        ScanSource::WorkingTree => {
+            Some(Processor::replace_todos(repo_root, &github_config.web_url(), &replacements).await)
+        }
+        ScanSource::GitRef(_) | ScanSource::Stdin { .. } | ScanSource::Archive => None,
+    };
 
-    report_github_results(created, skipped, failed, &result);
+    report_github_results(created, skipped, failed, result.as_ref());
 
     Ok(())
 }
@@ -227,8 +499,17 @@ async fn submit_issues(
     (replacements, skipped, failed)
 }
 
-fn report_github_results(created: usize, skipped: usize, failed: usize, result: &ProcessorResult) {
+fn report_github_results(
+    created: usize,
+    skipped: usize,
+    failed: usize,
+    result: Option<&ProcessorResult>,
+) {
     eprintln!("GitHub: {created} issues created, {skipped} skipped (duplicate), {failed} failed");
+    let Some(result) = result else {
+        eprintln!("Processor: skipped, TODOs are only rewritten when scanning the working tree");
+        return;
+    };
     eprintln!(
         "Processor: {} files modified, {} TODOs replaced, {} errors",
         result.files_modified,
@@ -255,11 +536,13 @@ fn report_dry_run(todos: &[TodoComment]) {
 }
 
 async fn run_interactive(
-    config_path: Option<PathBuf>,
+    load: &LoadOptions,
     path: PathBuf,
+    overrides: ScanOverrides,
     ai: bool,
 ) -> Result<(), TowlError> {
-    let (config, mut scan_result) = load_and_scan(config_path.as_ref(), &path).await?;
+    let (mut config, mut scan_result) =
+        load_and_scan(load, &path, overrides, &ScanSource::WorkingTree, |_| {}).await?;
 
     if scan_result.todos.is_empty() {
         eprintln!("No TODOs found.");
@@ -267,6 +550,7 @@ async fn run_interactive(
     }
 
     if ai {
+        config.llm.resolve_api_key().await?;
         let summary = towl::llm::analyse::analyse_todos(
             &mut scan_result.todos,
             &config.llm,
@@ -333,14 +617,30 @@ fn log_scan_verbose(
     }
 }
 
+/// Links for report locations: the scanned ref, else the checked-out branch
+/// or commit, else `main`. Offline, so the default branch is not looked up.
+async fn report_source_links(
+    repo_root: &Path,
+    github_config: &GitHubConfig,
+    source: &ScanSource,
+) -> Option<SourceLinks> {
+    let link_ref = match source {
+        ScanSource::GitRef(git_ref) => git_ref.link_name().to_string(),
+        ScanSource::WorkingTree | ScanSource::Stdin { .. } | ScanSource::Archive => {
+            GitRef::resolve(repo_root, "HEAD")
+                .await
+                .map_or_else(|_| "main".to_string(), |head| head.link_name().to_string())
+        }
+    };
+    SourceLinks::from_config(github_config, &link_ref)
+}
+
 async fn save_output(
-    format: OutputFormat,
-    output: Option<PathBuf>,
+    output: Output,
     filtered_todos: &[TodoComment],
     verbose: bool,
 ) -> Result<(), TowlError> {
-    let outputter = Output::new(format, output)?;
-    outputter.save(filtered_todos).await?;
+    output.save(filtered_todos).await?;
     if verbose {
         info!(
             "Successfully saved {} todos to output",
@@ -350,17 +650,51 @@ async fn save_output(
     Ok(())
 }
 
-fn show_config(config_path: Option<&PathBuf>) -> Result<(), TowlError> {
-    let config = TowlConfig::load(config_path)?;
+fn show_config(load: &LoadOptions, show_origin: bool) -> Result<(), TowlError> {
+    if show_origin {
+        let (config, origins) = TowlConfig::load_with_origins(load)?;
+        print!("{}", origins.render(&config));
+        return Ok(());
+    }
+    let config = TowlConfig::load_with(load)?;
+    let layers: String = TowlConfig::layers(load)
+        .iter()
+        .enumerate()
+        .map(|(i, layer)| format!("\n  {}. {layer}", i + 1))
+        .collect();
+    info!("Config layers (lowest precedence first):{layers}");
     info!("Scan config\n{}", config);
     Ok(())
 }
 
+fn validate_config(path: Option<&PathBuf>) -> Result<(), TowlError> {
+    let path = TowlConfig::resolve_path(path);
+    let problems = TowlConfig::check_file(&path)?;
+    for problem in &problems {
+        println!("{}:{problem}", path.display());
+    }
+    if problems.is_empty() {
+        println!("{}: ok", path.display());
+        return Ok(());
+    }
+    Err(TowlConfigError::InvalidConfigFile {
+        path,
+        count: problems.len(),
+    }
+    .into())
+}
+
+fn print_schema() -> Result<(), TowlError> {
+    println!("{:#}", TowlConfig::json_schema());
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use rstest::*;
     use std::path::PathBuf;
     use towl::comment::todo::{TodoComment, TodoType};
+    use towl::config::{DetectedLanguage, ProjectSurvey};
 
     fn create_mock_todo(todo_type: TodoType) -> TodoComment {
         TodoComment {
@@ -399,4 +733,44 @@ mod tests {
         let filtered_todos = super::filter_todos(todos, todo_type);
         assert_eq!(filtered_todos.len(), expected_count);
     }
+
+    #[rstest]
+    #[case("\n", true)]
+    #[case("y\n", true)]
+    #[case("No\n", false)]
+    #[case("maybe\nn\n", false)]
+    #[case("", true)]
+    fn test_confirm_answers(#[case] input: &str, #[case] expected: bool) {
+        let answer = super::confirm("Continue?", &mut std::io::Cursor::new(input)).unwrap();
+        assert_eq!(answer, expected);
+    }
+
+    #[test]
+    fn test_review_survey_drops_declined_entries() {
+        let mut survey = ProjectSurvey {
+            languages: vec![
+                DetectedLanguage {
+                    name: "typescript".to_string(),
+                    files: 3,
+                },
+                DetectedLanguage {
+                    name: "python".to_string(),
+                    files: 1,
+                },
+            ],
+            excludes: vec!["node_modules/*".to_string(), "build/*".to_string()],
+        };
+
+        let write = super::review_survey(
+            &mut survey,
+            std::path::Path::new(".towl.toml"),
+            &mut std::io::Cursor::new("y\nn\nn\ny\ny\n"),
+        )
+        .unwrap();
+
+        assert!(write);
+        assert_eq!(survey.languages.len(), 1);
+        assert_eq!(survey.languages[0].name, "typescript");
+        assert_eq!(survey.excludes, vec!["build/*"]);
+    }
 }
@@ -1,8 +1,10 @@
 //! Command-line interface definitions using [`clap`].
 
 use crate::comment::todo::TodoType;
+use crate::config::{ConfigOverride, DiscoveryMode};
+use crate::output::STDOUT_PATH;
 use clap::{Parser, Subcommand, ValueEnum};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Top-level CLI parser. Use [`Cli::command`] to access the chosen subcommand.
 #[derive(Debug, Parser)]
@@ -16,11 +18,15 @@ use std::path::PathBuf;
 pub struct Cli {
     #[command(subcommand)]
     pub command: TowlCommands,
+
+    /// Override a config value, e.g. `parsing.include_context_lines=5` (repeatable)
+    #[arg(long = "set", value_name = "KEY=VALUE", global = true)]
+    pub set: Vec<ConfigOverride>,
 }
 
 #[derive(Debug, Subcommand)]
 pub enum TowlCommands {
-    /// Initialize a new .towl.toml configuration file
+    /// Initialize a new .towl.toml configuration file for the languages found in the current directory
     Init {
         /// Path for the configuration file
         #[arg(long, short = 'p', default_value = ".towl.toml")]
@@ -29,6 +35,10 @@ pub enum TowlCommands {
         /// Overwrite existing configuration file
         #[arg(long, short = 'F')]
         force: bool,
+
+        /// Accept the detected languages and excludes without prompting
+        #[arg(long, short = 'y')]
+        yes: bool,
     },
 
     /// Scan for TODO comments in source code
@@ -37,7 +47,11 @@ pub enum TowlCommands {
         #[arg(long, short = 'c')]
         config: Option<PathBuf>,
 
-        /// Directory to scan for TODO comments
+        /// Config profile to apply over the config files (overrides `TOWL_PROFILE`)
+        #[arg(long, value_name = "NAME")]
+        profile: Option<String>,
+
+        /// Directory to scan for TODO comments, or a .tar, .tar.gz, .crate or .zip archive
         #[arg(default_value = ".")]
         path: PathBuf,
 
@@ -45,18 +59,68 @@ pub enum TowlCommands {
         #[arg(long, short = 'N')]
         non_interactive: bool,
 
-        /// Output format for non-interactive mode
-        #[arg(long, short = 'f', value_enum, default_value = "terminal")]
-        format: OutputFormat,
+        /// Output format for non-interactive mode [default: terminal, or inferred from the `--output` extension]
+        #[arg(long, short = 'f', value_enum)]
+        format: Option<OutputFormat>,
 
-        /// Write output to a file instead of stdout
+        /// Write output to a file; `-`, or leaving it out, writes to stdout
         #[arg(long, short = 'o')]
         output: Option<PathBuf>,
 
+        /// Jinja template rendered by `-f template`
+        #[arg(long, value_name = "PATH", required_if_eq("format", "template"))]
+        template: Option<PathBuf>,
+
+        /// TODO types reported as failed test cases with `--format junit`; others are skipped [default: bug,fixme]
+        #[arg(long, value_enum, value_name = "TYPE", value_delimiter = ',')]
+        junit_failures: Vec<TodoType>,
+
         /// Filter results by TODO type
         #[arg(long, short = 't', value_enum)]
         todo_type: Option<TodoType>,
 
+        /// Only scan files matching this glob (repeatable; replaces `include_patterns`)
+        #[arg(long = "include", value_name = "GLOB")]
+        include: Vec<String>,
+
+        /// How to find files: walk the tree, or ask git (falls back to walking outside git)
+        #[arg(long, value_enum, value_name = "MODE")]
+        discovery: Option<DiscoveryMode>,
+
+        /// Follow symbolic links, staying inside the scan root unless the config allows otherwise
+        #[arg(long, conflicts_with_all = ["git_ref", "stdin"])]
+        follow_symlinks: bool,
+
+        /// Also report code placeholders such as todo!(), unimplemented!() and NotImplementedError
+        #[arg(long)]
+        placeholders: bool,
+
+        /// Scan a branch, tag or commit straight from the repository instead of the working tree
+        #[arg(
+            long = "ref",
+            value_name = "REF",
+            requires = "non_interactive",
+            conflicts_with = "discovery"
+        )]
+        git_ref: Option<String>,
+
+        /// Read the content to scan from stdin instead of the file system
+        #[arg(
+            long,
+            requires = "non_interactive",
+            conflicts_with_all = ["git_ref", "discovery", "include"]
+        )]
+        stdin: bool,
+
+        /// Virtual file name for --stdin content; selects the comment syntax
+        #[arg(
+            long,
+            value_name = "PATH",
+            requires = "stdin",
+            default_value = "<stdin>"
+        )]
+        stdin_filename: PathBuf,
+
         /// Show detailed scan statistics
         #[arg(long, short = 'v')]
         verbose: bool,
@@ -79,17 +143,132 @@ pub enum TowlCommands {
         /// Path to a .towl.toml configuration file
         #[arg(long, short = 'c')]
         config: Option<PathBuf>,
+
+        /// Show the config with this profile applied (overrides `TOWL_PROFILE`)
+        #[arg(long, value_name = "NAME")]
+        profile: Option<String>,
+
+        /// Show which file, profile or environment variable set each value
+        #[arg(long)]
+        show_origin: bool,
+
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+    },
+}
+
+/// Config subcommands. Without one, `towl config` displays the configuration.
+#[derive(Debug, Subcommand)]
+pub enum ConfigAction {
+    /// Check a config file and report every problem with its line and column
+    Validate {
+        /// Config file to check (defaults to --config, `TOWL_CONFIG` or .towl.toml)
+        path: Option<PathBuf>,
     },
+
+    /// Print the JSON Schema for .towl.toml
+    Schema,
 }
 
 /// Output format for non-interactive scan results.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum OutputFormat {
     Json,
+    /// JSON Lines: one object per TODO, streamed as files are scanned.
+    Jsonl,
     Csv,
     Toml,
     Markdown,
+    /// Self-contained HTML report with a sortable, filterable table.
+    Html,
+    /// SARIF 2.1.0 log for code-scanning dashboards.
+    Sarif,
+    /// JUnit XML report: one test suite per file, one test case per TODO.
+    Junit,
+    /// Checkstyle XML report: one error per TODO.
+    Checkstyle,
+    /// GitHub Actions annotations on stdout, plus a job summary when
+    /// `GITHUB_STEP_SUMMARY` is set.
+    GithubActions,
+    /// User template given with `--template`, rendered to stdout or `-o`.
+    Template,
     /// Formatted table on stdout (also accepted as `table`).
     #[value(alias = "table")]
     Terminal,
 }
+
+impl OutputFormat {
+    /// The file extension reports in this format usually have, if any.
+    #[must_use]
+    pub const fn extension(self) -> Option<&'static str> {
+        match self {
+            Self::Json => Some("json"),
+            Self::Jsonl => Some("jsonl"),
+            Self::Csv => Some("csv"),
+            Self::Toml => Some("toml"),
+            Self::Markdown => Some("md"),
+            Self::Html => Some("html"),
+            Self::Sarif => Some("sarif"),
+            Self::Junit | Self::Checkstyle => Some("xml"),
+            Self::GithubActions | Self::Template | Self::Terminal => None,
+        }
+    }
+
+    /// The format a file extension names, ignoring case. `xml` is shared by
+    /// JUnit and Checkstyle, so it names neither.
+    #[must_use]
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "jsonl" => Some(Self::Jsonl),
+            "csv" => Some(Self::Csv),
+            "toml" => Some(Self::Toml),
+            "md" | "markdown" => Some(Self::Markdown),
+            "html" | "htm" => Some(Self::Html),
+            "sarif" => Some(Self::Sarif),
+            _ => None,
+        }
+    }
+
+    /// The format used when `--format` is omitted: `Terminal` for stdout,
+    /// else the one named by the output file's extension. `None` when the
+    /// file has no extension or one that names no single format, such as
+    /// `.xml`, so the caller can ask for `--format`.
+    #[must_use]
+    pub fn infer(output: Option<&Path>) -> Option<Self> {
+        match output.filter(|path| path.as_os_str() != STDOUT_PATH) {
+            None => Some(Self::Terminal),
+            Some(path) => Self::from_extension(&path.extension()?.to_string_lossy()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(None, Some(OutputFormat::Terminal))]
+    #[case(Some("-"), Some(OutputFormat::Terminal))]
+    #[case(Some("todos.json"), Some(OutputFormat::Json))]
+    #[case(Some("todos.JSONL"), Some(OutputFormat::Jsonl))]
+    #[case(Some("out/todos.sarif"), Some(OutputFormat::Sarif))]
+    #[case(Some("TODO.markdown"), Some(OutputFormat::Markdown))]
+    #[case(Some("report.htm"), Some(OutputFormat::Html))]
+    #[case(Some("report.xml"), None)]
+    #[case(Some("report.out"), None)]
+    #[case(Some("report"), None)]
+    fn test_infer_format(#[case] output: Option<&str>, #[case] expected: Option<OutputFormat>) {
+        assert_eq!(OutputFormat::infer(output.map(Path::new)), expected);
+    }
+
+    #[test]
+    fn test_extension_names_its_format() {
+        for format in OutputFormat::value_variants() {
+            if let Some(ext) = format.extension().filter(|&ext| ext != "xml") {
+                assert_eq!(OutputFormat::from_extension(ext), Some(*format));
+            }
+        }
+    }
+}
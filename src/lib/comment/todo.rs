@@ -7,6 +7,9 @@ use std::path::PathBuf;
 /// The category of a TODO comment, ordered by priority (Bug=1 highest, Note=5 lowest).
 ///
 /// Parsed from comment text via [`TryFrom<&str>`] (case-insensitive).
+/// [`TodoType::Placeholder`] is not a comment marker: it is reported for code
+/// constructs such as `todo!()` or `raise NotImplementedError`, and sorts
+/// after the comment markers (priority 6).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ValueEnum)]
 pub enum TodoType {
     Todo,
@@ -14,6 +17,7 @@ pub enum TodoType {
     Hack,
     Note,
     Bug,
+    Placeholder,
 }
 impl fmt::Display for TodoType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -23,6 +27,7 @@ impl fmt::Display for TodoType {
             Self::Hack => write!(f, "HACK"),
             Self::Note => write!(f, "NOTE"),
             Self::Bug => write!(f, "BUG"),
+            Self::Placeholder => write!(f, "PLACEHOLDER"),
         }
     }
 }
@@ -36,6 +41,7 @@ impl TodoType {
             Self::Hack => "hack",
             Self::Note => "note",
             Self::Bug => "bug",
+            Self::Placeholder => "placeholder",
         }
     }
 
@@ -52,8 +58,15 @@ impl TodoType {
             Self::Hack => 3,
             Self::Todo => 4,
             Self::Note => 5,
+            Self::Placeholder => 6,
         }
     }
+
+    /// Returns true for code placeholders, which have no comment to rewrite.
+    #[must_use]
+    pub const fn is_placeholder(&self) -> bool {
+        matches!(self, Self::Placeholder)
+    }
 }
 
 impl TryFrom<&str> for TodoType {
@@ -71,6 +84,8 @@ impl TryFrom<&str> for TodoType {
             Ok(Self::Note)
         } else if upper.contains("BUG") {
             Ok(Self::Bug)
+        } else if upper.contains("PLACEHOLDER") {
+            Ok(Self::Placeholder)
         } else {
             Err(TowlCommentError::UnknownTodoType {
                 comment: value.to_owned(), // clone: need owned String for error variant
@@ -283,6 +298,13 @@ mod tests {
     #[case("HACK: x", TodoType::Hack, "HACK", "hack", 3)]
     #[case("NOTE: x", TodoType::Note, "NOTE", "note", 5)]
     #[case("BUG: x", TodoType::Bug, "BUG", "bug", 1)]
+    #[case(
+        "PLACEHOLDER: x",
+        TodoType::Placeholder,
+        "PLACEHOLDER",
+        "placeholder",
+        6
+    )]
     fn test_todo_type_methods(
         #[case] input: &str,
         #[case] expected_variant: TodoType,
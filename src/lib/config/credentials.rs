@@ -0,0 +1,353 @@
+use std::process::Stdio;
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use super::error::TowlConfigError;
+use super::types::{GitHubConfig, LlmConfig, TowlConfig};
+
+/// How long a credential helper may run before it is killed.
+pub const CREDENTIAL_HELPER_TIMEOUT_SECS: u64 = 30;
+
+/// Longest stderr excerpt quoted in a helper failure.
+const MAX_STDERR_EXCERPT: usize = 200;
+
+/// How a `token_command` or `api_key_command` hands back its secret.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum CredentialProtocol {
+    /// The helper prints the secret on stdout, e.g. `pass show`, `op read` or
+    /// `gh auth token`. Surrounding whitespace is trimmed.
+    #[default]
+    Plain,
+    /// The helper speaks git's credential protocol, e.g. `git credential fill`:
+    /// it reads `protocol` and `host` on stdin and answers `password=<secret>`.
+    GitCredential,
+}
+
+impl CredentialProtocol {
+    pub(super) fn is_plain(&self) -> bool {
+        *self == Self::Plain
+    }
+}
+
+impl GitHubConfig {
+    /// Fills in `token` by running `token_command` when `TOWL_GITHUB_TOKEN`
+    /// did not provide one. The git credential protocol asks for `host`.
+    ///
+    /// # Errors
+    /// Returns `TowlConfigError::CredentialHelperFailed` if the helper cannot
+    /// run, times out, exits non-zero or prints no token.
+    pub async fn resolve_token(&mut self) -> Result<(), TowlConfigError> {
+        if let Some(command) = &self.token_command {
+            if self.token.expose_secret().is_empty() {
+                self.token = run_helper(
+                    "github.token_command",
+                    command,
+                    self.token_protocol,
+                    &self.host,
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl LlmConfig {
+    /// Fills in `api_key` by running `api_key_command` when
+    /// `TOWL_LLM_API_KEY` did not provide one. The git credential protocol
+    /// asks for the host of `base_url`, or of the provider's default API.
+    ///
+    /// # Errors
+    /// Returns `TowlConfigError::CredentialHelperFailed` if the helper cannot
+    /// run, times out, exits non-zero or prints no key.
+    pub async fn resolve_api_key(&mut self) -> Result<(), TowlConfigError> {
+        if let Some(command) = &self.api_key_command {
+            if self.api_key.expose_secret().is_empty() {
+                self.api_key = run_helper(
+                    "llm.api_key_command",
+                    command,
+                    self.api_key_protocol,
+                    &self.api_host(),
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn api_host(&self) -> String {
+        let from_base_url = self
+            .base_url
+            .as_deref()
+            .and_then(|url| url::Url::parse(url).ok())
+            .and_then(|url| url.host_str().map(str::to_string));
+        from_base_url.unwrap_or_else(|| match self.provider.as_str() {
+            "openai" | "codex" => "api.openai.com".to_string(),
+            _ => "api.anthropic.com".to_string(),
+        })
+    }
+}
+
+impl TowlConfig {
+    /// Checks that `github.token_command` and `llm.api_key_command` name a
+    /// program, as a bare name or an absolute path.
+    pub(crate) fn validate_credential_commands(config: &Self) -> Result<(), TowlConfigError> {
+        for (field, command) in [
+            ("github.token_command", &config.github.token_command),
+            ("llm.api_key_command", &config.llm.api_key_command),
+        ] {
+            let Some(command) = command else {
+                continue;
+            };
+            for arg in command {
+                Self::check_string_length(field, arg)?;
+            }
+            let invalid = |message: &str| TowlConfigError::InvalidCredentialCommand {
+                field: field.to_string(),
+                message: message.to_string(),
+            };
+            let Some(program) = command.first().filter(|p| !p.trim().is_empty()) else {
+                return Err(invalid("expected a program followed by its arguments"));
+            };
+            let path = std::path::Path::new(program);
+            if program.contains("..") || (path.components().count() > 1 && !path.is_absolute()) {
+                return Err(invalid(
+                    "the program must be a bare name or an absolute path",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs a credential helper and returns the secret it produced. Its stdout
+/// is never included in errors or logs.
+async fn run_helper(
+    setting: &str,
+    command: &[String],
+    protocol: CredentialProtocol,
+    host: &str,
+) -> Result<SecretString, TowlConfigError> {
+    let failed = |message: String| TowlConfigError::CredentialHelperFailed {
+        setting: setting.to_string(),
+        message,
+    };
+    let Some((program, args)) = command.split_first() else {
+        return Err(failed("no command given".to_string()));
+    };
+    tracing::debug!("Running credential helper {setting}: {program}");
+
+    let mut process = Command::new(program);
+    process
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // reap the child if the timeout below drops the wait future
+        .kill_on_drop(true);
+    match protocol {
+        CredentialProtocol::Plain => process.stdin(Stdio::null()),
+        // git would otherwise fall back to prompting on the terminal
+        CredentialProtocol::GitCredential => process
+            .stdin(Stdio::piped())
+            .env("GIT_TERMINAL_PROMPT", "0"),
+    };
+    let mut child = process
+        .spawn()
+        .map_err(|e| failed(format!("could not run {program}: {e}")))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        let request = format!("protocol=https\nhost={host}\n\n");
+        stdin
+            .write_all(request.as_bytes())
+            .await
+            .map_err(|e| failed(format!("could not write to {program}: {e}")))?;
+        drop(stdin);
+    }
+
+    let output = tokio::time::timeout(
+        Duration::from_secs(CREDENTIAL_HELPER_TIMEOUT_SECS),
+        child.wait_with_output(),
+    )
+    .await
+    .map_err(|_| {
+        failed(format!(
+            "{program} timed out after {CREDENTIAL_HELPER_TIMEOUT_SECS}s"
+        ))
+    })?
+    .map_err(|e| failed(format!("could not wait for {program}: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let excerpt: String = stderr.trim().chars().take(MAX_STDERR_EXCERPT).collect();
+        return Err(failed(if excerpt.is_empty() {
+            format!("{program} exited with {}", output.status)
+        } else {
+            format!("{program} exited with {}: {excerpt}", output.status)
+        }));
+    }
+
+    let stdout = SecretString::from(
+        String::from_utf8(output.stdout)
+            .map_err(|_| failed(format!("{program} printed non-UTF-8 output")))?,
+    );
+    let secret = match protocol {
+        CredentialProtocol::Plain => Some(stdout.expose_secret().trim()),
+        CredentialProtocol::GitCredential => stdout
+            .expose_secret()
+            .lines()
+            .find_map(|line| line.strip_prefix("password=")),
+    }
+    .filter(|secret| !secret.is_empty())
+    .ok_or_else(|| failed(format!("{program} returned no secret")))?;
+    TowlConfig::check_string_length(setting, secret)?;
+    Ok(SecretString::from(secret.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn sh(script: &str) -> Vec<String> {
+        vec!["sh".to_string(), "-c".to_string(), script.to_string()]
+    }
+
+    #[tokio::test]
+    async fn test_plain_helper_output_is_trimmed() {
+        let secret = run_helper(
+            "github.token_command",
+            &sh("printf '  ghp_abc123\\n'"),
+            CredentialProtocol::Plain,
+            "github.com",
+        )
+        .await
+        .unwrap();
+        assert_eq!(secret.expose_secret(), "ghp_abc123");
+    }
+
+    #[tokio::test]
+    async fn test_git_credential_helper_is_asked_for_host() {
+        let script = "read a; read b; [ \"$b\" = host=github.example.com ] && \
+                      printf 'username=me\\npassword=s3cret\\n'";
+        let secret = run_helper(
+            "github.token_command",
+            &sh(script),
+            CredentialProtocol::GitCredential,
+            "github.example.com",
+        )
+        .await
+        .unwrap();
+        assert_eq!(secret.expose_secret(), "s3cret");
+    }
+
+    #[rstest]
+    #[case::non_zero_exit("echo ghp_leaked; echo locked >&2; exit 3", "locked")]
+    #[case::empty_output("true", "returned no secret")]
+    #[tokio::test]
+    async fn test_helper_failures_do_not_leak_stdout(#[case] script: &str, #[case] expected: &str) {
+        let err = run_helper(
+            "llm.api_key_command",
+            &sh(script),
+            CredentialProtocol::Plain,
+            "api.anthropic.com",
+        )
+        .await
+        .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("llm.api_key_command"), "{message}");
+        assert!(message.contains(expected), "{message}");
+        assert!(!message.contains("ghp_leaked"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn test_git_credential_without_password_fails() {
+        let err = run_helper(
+            "github.token_command",
+            &sh("cat >/dev/null; echo username=me"),
+            CredentialProtocol::GitCredential,
+            "github.com",
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            TowlConfigError::CredentialHelperFailed { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_missing_program_fails() {
+        let err = run_helper(
+            "github.token_command",
+            &["towl-no-such-helper".to_string()],
+            CredentialProtocol::Plain,
+            "github.com",
+        )
+        .await
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("could not run towl-no-such-helper"));
+    }
+
+    #[tokio::test]
+    async fn test_environment_token_wins_over_command() {
+        let mut github = GitHubConfig {
+            token: SecretString::from("from-env"),
+            token_command: Some(sh("exit 1")),
+            ..GitHubConfig::default()
+        };
+        github.resolve_token().await.unwrap();
+        assert_eq!(github.token.expose_secret(), "from-env");
+    }
+
+    #[tokio::test]
+    async fn test_api_key_command_fills_empty_key() {
+        let mut llm = LlmConfig {
+            api_key_command: Some(sh("echo sk-test")),
+            ..LlmConfig::default()
+        };
+        llm.resolve_api_key().await.unwrap();
+        assert_eq!(llm.api_key.expose_secret(), "sk-test");
+    }
+
+    #[rstest]
+    #[case(None, "claude", "api.anthropic.com")]
+    #[case(None, "openai", "api.openai.com")]
+    #[case(Some("http://localhost:8080/v1"), "openai", "localhost")]
+    fn test_api_host(
+        #[case] base_url: Option<&str>,
+        #[case] provider: &str,
+        #[case] expected: &str,
+    ) {
+        let llm = LlmConfig {
+            base_url: base_url.map(str::to_string),
+            provider: provider.to_string(),
+            ..LlmConfig::default()
+        };
+        assert_eq!(llm.api_host(), expected);
+    }
+
+    #[rstest]
+    #[case(Some(vec!["gh", "auth", "token"]), true)]
+    #[case(Some(vec!["/usr/bin/pass", "show", "github"]), true)]
+    #[case(Some(vec![]), false)]
+    #[case(Some(vec![" "]), false)]
+    #[case(Some(vec!["bin/helper"]), false)]
+    #[case(Some(vec!["../helper"]), false)]
+    #[case(None, true)]
+    fn test_validate_credential_commands(#[case] command: Option<Vec<&str>>, #[case] ok: bool) {
+        let mut config = TowlConfig::default();
+        config.github.token_command = command.map(|c| c.into_iter().map(str::to_string).collect());
+        assert_eq!(
+            TowlConfig::validate_credential_commands(&config).is_ok(),
+            ok
+        );
+    }
+}
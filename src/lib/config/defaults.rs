@@ -1,9 +1,46 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+
+use super::types::{BlockComment, LanguageProfile};
 
 const RUST_COMMENT_PREFIX: &str = r"//";
 const SHELL_COMMENT_PREFIX: &str = r"^\s*#";
 const C_MULTILINE_START: &str = r"/\*";
 const MULTILINE_CONTINUATION: &str = r"^\s*\*";
+const HASKELL_COMMENT_PREFIX: &str = r"--";
+const OCAML_COMMENT_START: &str = r"\(\*";
+const RUST_PLACEHOLDER: &str = r#"\b(?:todo|unimplemented)!\s*\(\s*("(?:[^"\\]|\\.)*")?"#;
+const PYTHON_PLACEHOLDER: &str =
+    r#"\braise\s+NotImplementedError\b(?:\s*\(\s*("(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*')?)?"#;
+const JAVA_PLACEHOLDER: &str =
+    r#"\bthrow\s+new\s+UnsupportedOperationException\s*\(\s*("(?:[^"\\]|\\.)*")?"#;
+const RUST_FUNCTION: &str = r"^\s*(pub\s+)?fn\s+(\w+)";
+const DEF_FUNCTION: &str = r"^\s*def\s+(\w+)";
+const JS_FUNCTION: &str = r"^\s*(async\s+)?function\s+(\w+)";
+const JAVA_METHOD: &str = r"^\s*(?:public|private|protected)\s+(?:static\s+)?\w+\s+(\w+)\s*\(";
+const GO_FUNCTION: &str = r"^\s*func\s+(\w+)";
+const KOTLIN_FUNCTION: &str = r"^\s*(?:\w+\s+)*fun\s+(\w+)";
+const SWIFT_FUNCTION: &str = r"^\s*(?:\w+\s+)*func\s+(\w+)";
+const PHP_FUNCTION: &str = r"^\s*(?:\w+\s+)*function\s+(\w+)";
+const SHELL_FUNCTION: &str = r"^\s*(?:function\s+)?(\w+)\s*\(\)";
+
+/// Directories of vendored or generated code that `towl init` proposes to
+/// exclude when it finds them.
+pub(super) const GENERATED_DIRS: &[&str] = &[
+    "node_modules",
+    "bower_components",
+    "vendor",
+    "venv",
+    ".venv",
+    "__pycache__",
+    ".tox",
+    ".mypy_cache",
+    "dist",
+    "build",
+    "target",
+    ".gradle",
+    ".next",
+    "Pods",
+];
 
 pub(super) fn default_file_extensions() -> HashSet<String> {
     [
@@ -36,6 +73,194 @@ pub(super) fn default_comment_prefixes() -> Vec<String> {
     ]
 }
 
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(ToString::to_string).collect()
+}
+
+fn profile(
+    extensions: &[&str],
+    filenames: &[&str],
+    interpreters: &[&str],
+    comment_prefixes: &[&str],
+    line_comments: &[&str],
+) -> LanguageProfile {
+    LanguageProfile {
+        extensions: strings(extensions),
+        filenames: strings(filenames),
+        interpreters: strings(interpreters),
+        comment_prefixes: strings(comment_prefixes),
+        block_comments: Vec::new(),
+        line_comments: strings(line_comments),
+        placeholder_patterns: Vec::new(),
+    }
+}
+
+fn with_placeholders(mut profile: LanguageProfile, patterns: &[&str]) -> LanguageProfile {
+    profile.placeholder_patterns = strings(patterns);
+    profile
+}
+
+fn with_block_comments(
+    mut profile: LanguageProfile,
+    delimiters: &[(&str, &str)],
+    nested: bool,
+) -> LanguageProfile {
+    profile.block_comments = delimiters
+        .iter()
+        .map(|&(start, end)| BlockComment {
+            start: start.to_string(),
+            end: end.to_string(),
+            nested,
+        })
+        .collect();
+    profile
+}
+
+pub(super) fn default_languages() -> BTreeMap<String, LanguageProfile> {
+    let hash = &[SHELL_COMMENT_PREFIX];
+    let c_style = &[
+        RUST_COMMENT_PREFIX,
+        C_MULTILINE_START,
+        MULTILINE_CONTINUATION,
+    ];
+    let c_block = &[("/*", "*/")];
+    [
+        (
+            "shell",
+            profile(
+                &["sh", "bash", "zsh"],
+                &[".bashrc", ".bash_profile", ".zshrc", ".profile"],
+                &["sh", "bash", "zsh", "dash", "ksh"],
+                hash,
+                &["#"],
+            ),
+        ),
+        (
+            "python",
+            with_block_comments(
+                with_placeholders(
+                    profile(
+                        &["py", "pyi"],
+                        &[],
+                        &["python", "python2", "python3"],
+                        hash,
+                        &["#"],
+                    ),
+                    &[PYTHON_PLACEHOLDER],
+                ),
+                &[(r#"""""#, r#"""""#), ("'''", "'''")],
+                false,
+            ),
+        ),
+        (
+            "rust",
+            with_block_comments(
+                with_placeholders(
+                    profile(&["rs"], &[], &[], &[], &["//"]),
+                    &[RUST_PLACEHOLDER],
+                ),
+                c_block,
+                true,
+            ),
+        ),
+        (
+            "java",
+            with_block_comments(
+                with_placeholders(
+                    profile(&["java"], &[], &[], c_style, &["//"]),
+                    &[JAVA_PLACEHOLDER],
+                ),
+                c_block,
+                false,
+            ),
+        ),
+        (
+            "haskell",
+            with_block_comments(
+                profile(
+                    &["hs", "lhs"],
+                    &[],
+                    &["runhaskell"],
+                    &[HASKELL_COMMENT_PREFIX],
+                    &["--"],
+                ),
+                &[("{-", "-}")],
+                true,
+            ),
+        ),
+        (
+            "ocaml",
+            with_block_comments(
+                profile(&["ml", "mli"], &[], &["ocaml"], &[OCAML_COMMENT_START], &[]),
+                &[("(*", "*)")],
+                true,
+            ),
+        ),
+        (
+            "ruby",
+            profile(
+                &["rb"],
+                &["Gemfile", "Rakefile", "Vagrantfile"],
+                &["ruby"],
+                hash,
+                &["#"],
+            ),
+        ),
+        ("perl", profile(&["pl", "pm"], &[], &["perl"], hash, &["#"])),
+        (
+            "dockerfile",
+            profile(
+                &["dockerfile"],
+                &["Dockerfile", "Dockerfile.*", "Containerfile"],
+                &[],
+                hash,
+                &["#"],
+            ),
+        ),
+        (
+            "make",
+            profile(
+                &["mk"],
+                &["Makefile", "GNUmakefile", "makefile"],
+                &[],
+                hash,
+                &["#"],
+            ),
+        ),
+        (
+            "groovy",
+            with_block_comments(
+                profile(
+                    &["groovy", "gradle"],
+                    &["Jenkinsfile"],
+                    &[],
+                    c_style,
+                    &["//"],
+                ),
+                c_block,
+                false,
+            ),
+        ),
+        (
+            "javascript",
+            with_block_comments(
+                profile(
+                    &["js", "mjs", "cjs"],
+                    &[],
+                    &["node", "deno"],
+                    c_style,
+                    &["//"],
+                ),
+                c_block,
+                false,
+            ),
+        ),
+    ]
+    .into_iter()
+    .map(|(name, profile)| (name.to_string(), profile))
+    .collect()
+}
+
 pub(super) fn default_todo_patterns() -> Vec<String> {
     vec![
         r"(?i)\bTODO:\s*(.*)".to_string(),
@@ -47,19 +272,81 @@ pub(super) fn default_todo_patterns() -> Vec<String> {
 }
 
 pub(super) fn default_function_patterns() -> Vec<String> {
-    vec![
-        r"^\s*(pub\s+)?fn\s+(\w+)".to_string(),
-        r"^\s*def\s+(\w+)".to_string(),
-        r"^\s*(async\s+)?function\s+(\w+)".to_string(),
-        r"^\s*(?:public|private|protected)\s+(?:static\s+)?\w+\s+(\w+)\s*\(".to_string(),
-        r"^\s*func\s+(\w+)".to_string(),
-    ]
+    strings(&[
+        RUST_FUNCTION,
+        DEF_FUNCTION,
+        JS_FUNCTION,
+        JAVA_METHOD,
+        GO_FUNCTION,
+    ])
+}
+
+/// Function patterns for a language, by its name in [`known_languages`].
+pub(super) fn language_function_patterns(language: &str) -> &'static [&'static str] {
+    match language {
+        "rust" => &[RUST_FUNCTION],
+        "python" | "ruby" => &[DEF_FUNCTION],
+        "javascript" | "typescript" => &[JS_FUNCTION],
+        "java" | "csharp" | "groovy" => &[JAVA_METHOD],
+        "go" => &[GO_FUNCTION],
+        "kotlin" => &[KOTLIN_FUNCTION],
+        "swift" => &[SWIFT_FUNCTION],
+        "php" => &[PHP_FUNCTION],
+        "shell" => &[SHELL_FUNCTION],
+        _ => &[],
+    }
+}
+
+/// The default language profiles plus the languages `towl init` can detect
+/// but does not scan unless proposed.
+pub(super) fn known_languages() -> BTreeMap<String, LanguageProfile> {
+    let hash = &[SHELL_COMMENT_PREFIX];
+    let c_style = &[
+        RUST_COMMENT_PREFIX,
+        C_MULTILINE_START,
+        MULTILINE_CONTINUATION,
+    ];
+    let c_block = &[("/*", "*/")];
+    let c_family = |extensions: &[&str]| {
+        with_block_comments(
+            profile(extensions, &[], &[], c_style, &["//"]),
+            c_block,
+            false,
+        )
+    };
+    let mut languages = default_languages();
+    languages.extend(
+        [
+            ("typescript", c_family(&["ts", "tsx", "mts", "cts"])),
+            ("go", c_family(&["go"])),
+            ("c", c_family(&["c", "h"])),
+            ("cpp", c_family(&["cc", "cpp", "cxx", "hh", "hpp", "hxx"])),
+            ("csharp", c_family(&["cs"])),
+            ("kotlin", c_family(&["kt", "kts"])),
+            ("swift", c_family(&["swift"])),
+            ("scala", c_family(&["scala", "sc"])),
+            ("php", c_family(&["php"])),
+            ("toml", profile(&["toml"], &[], &[], hash, &["#"])),
+            ("yaml", profile(&["yaml", "yml"], &[], &[], hash, &["#"])),
+        ]
+        .into_iter()
+        .map(|(name, profile)| (name.to_string(), profile)),
+    );
+    languages
 }
 
 pub(super) const fn default_rate_limit_delay_ms() -> u64 {
     1000
 }
 
+pub(super) fn default_github_host() -> String {
+    "github.com".to_string()
+}
+
+pub(super) fn default_github_remote() -> String {
+    "origin".to_string()
+}
+
 pub(super) fn default_llm_provider() -> String {
     "claude".to_string()
 }
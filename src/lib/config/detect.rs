@@ -0,0 +1,339 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::{DirEntry, WalkBuilder};
+
+use super::defaults::{known_languages, language_function_patterns, GENERATED_DIRS};
+use super::types::{LanguageProfile, TowlConfig};
+use crate::parser::MAX_TOTAL_PATTERNS;
+
+/// Upper bound on walk entries visited, so `towl init` stays quick on huge trees.
+const MAX_SURVEY_ENTRIES: usize = 100_000;
+
+/// A language found by [`ProjectSurvey::detect`] and how many files use it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedLanguage {
+    pub name: String,
+    pub files: usize,
+}
+
+/// What `towl init` found in a project: languages, most files first, and
+/// exclude patterns for vendored or generated directories.
+///
+/// Drop entries from either list before calling [`ProjectSurvey::to_config`]
+/// to leave them out of the proposed config.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectSurvey {
+    pub languages: Vec<DetectedLanguage>,
+    pub excludes: Vec<String>,
+}
+
+impl ProjectSurvey {
+    /// Walks `root` and counts files per known language, by file name or
+    /// extension.
+    ///
+    /// Gitignore rules are not applied, so ignored directories such as
+    /// `node_modules` are still found; their contents and `.git` are never
+    /// descended into. A directory named in `GENERATED_DIRS` at the top level
+    /// is proposed as `name/*`, one found deeper as `**/name/*`.
+    pub fn detect(root: &Path) -> Self {
+        let catalogue = known_languages();
+        let matcher = LanguageMatcher::new(&catalogue);
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        let mut generated: BTreeMap<usize, bool> = BTreeMap::new();
+
+        let walker = WalkBuilder::new(root)
+            .standard_filters(false)
+            .filter_entry(|entry| !is_pruned(entry))
+            .build();
+        for entry in walker.filter_map(Result::ok).take(MAX_SURVEY_ENTRIES) {
+            let Some(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if let Some(index) = generated_dir(&entry) {
+                    *generated.entry(index).or_default() |= entry.depth() > 1;
+                }
+            } else if file_type.is_file() {
+                if let Some(name) = matcher.language_for(entry.path()) {
+                    *counts.entry(name).or_default() += 1;
+                }
+            }
+        }
+
+        let mut languages: Vec<DetectedLanguage> = counts
+            .into_iter()
+            .map(|(name, files)| DetectedLanguage {
+                name: name.to_string(),
+                files,
+            })
+            .collect();
+        languages.sort_by(|a, b| b.files.cmp(&a.files).then_with(|| a.name.cmp(&b.name)));
+
+        let excludes = generated
+            .into_iter()
+            .map(|(index, nested)| {
+                let name = GENERATED_DIRS[index];
+                if nested {
+                    format!("**/{name}/*")
+                } else {
+                    format!("{name}/*")
+                }
+            })
+            .collect();
+
+        Self {
+            languages,
+            excludes,
+        }
+    }
+
+    /// Builds the proposed config: the defaults, scanning only the detected
+    /// languages' extensions with their profiles and function patterns, plus
+    /// the detected excludes.
+    ///
+    /// Without any detected language the scan settings stay at their
+    /// defaults. Language profiles are added most files first while they fit
+    /// the parser's pattern budget; a language left out still has its
+    /// extensions scanned with `comment_prefixes`.
+    pub fn to_config(&self) -> TowlConfig {
+        let mut config = TowlConfig::default();
+        let parsing = &mut config.parsing;
+        for pattern in &self.excludes {
+            if !parsing.exclude_patterns.contains(pattern) {
+                parsing.exclude_patterns.push(pattern.clone()); // clone: survey stays reusable
+            }
+        }
+
+        let catalogue = known_languages();
+        let detected: Vec<(&str, &LanguageProfile)> = self
+            .languages
+            .iter()
+            .filter_map(|language| {
+                catalogue
+                    .get_key_value(&language.name)
+                    .map(|(name, profile)| (name.as_str(), profile))
+            })
+            .collect();
+        if detected.is_empty() {
+            return config;
+        }
+
+        let mut function_patterns: Vec<String> = Vec::new();
+        for pattern in detected
+            .iter()
+            .flat_map(|(name, _)| language_function_patterns(name))
+        {
+            if !function_patterns.iter().any(|p| p == pattern) {
+                function_patterns.push((*pattern).to_string());
+            }
+        }
+        if !function_patterns.is_empty() {
+            parsing.function_patterns = function_patterns;
+        }
+
+        let mut budget = MAX_TOTAL_PATTERNS.saturating_sub(
+            parsing.comment_prefixes.len()
+                + parsing.todo_patterns.len()
+                + parsing.function_patterns.len(),
+        );
+        parsing.file_extensions = HashSet::new();
+        parsing.languages = BTreeMap::new();
+        for (name, profile) in detected {
+            parsing
+                .file_extensions
+                .extend(profile.extensions.iter().cloned()); // clone: catalogue is borrowed
+            let cost = profile.comment_prefixes.len() + profile.placeholder_patterns.len();
+            if cost <= budget {
+                budget -= cost;
+                parsing.languages.insert(name.to_string(), profile.clone()); // clone: catalogue is borrowed
+            }
+        }
+
+        config
+    }
+}
+
+/// Skips `.git` and the contents of generated directories below the root.
+fn is_pruned(entry: &DirEntry) -> bool {
+    entry.depth() > 0 && entry.file_name() == ".git"
+        || entry.depth() > 1
+            && entry
+                .path()
+                .parent()
+                .and_then(Path::file_name)
+                .is_some_and(|parent| GENERATED_DIRS.iter().any(|dir| parent == *dir))
+}
+
+/// Index into `GENERATED_DIRS` of a directory entry below the root.
+fn generated_dir(entry: &DirEntry) -> Option<usize> {
+    if entry.depth() == 0 {
+        return None;
+    }
+    GENERATED_DIRS
+        .iter()
+        .position(|dir| entry.file_name() == *dir)
+}
+
+/// Maps file names and extensions to language names from the catalogue.
+struct LanguageMatcher<'a> {
+    filenames: GlobSet,
+    filename_languages: Vec<&'a str>,
+    extensions: HashMap<String, &'a str>,
+}
+
+impl<'a> LanguageMatcher<'a> {
+    fn new(catalogue: &'a BTreeMap<String, LanguageProfile>) -> Self {
+        let mut builder = GlobSetBuilder::new();
+        let mut filename_languages = Vec::new();
+        let mut extensions = HashMap::new();
+        for (name, profile) in catalogue {
+            for glob in profile.filenames.iter().filter_map(|f| Glob::new(f).ok()) {
+                builder.add(glob);
+                filename_languages.push(name.as_str());
+            }
+            for extension in &profile.extensions {
+                extensions
+                    .entry(extension.to_ascii_lowercase())
+                    .or_insert(name.as_str());
+            }
+        }
+        Self {
+            filenames: builder.build().unwrap_or_else(|_| GlobSet::empty()),
+            filename_languages,
+            extensions,
+        }
+    }
+
+    fn language_for(&self, path: &Path) -> Option<&'a str> {
+        let file_name = path.file_name()?;
+        if let Some(&index) = self.filenames.matches(file_name).first() {
+            return self.filename_languages.get(index).copied();
+        }
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        self.extensions.get(&extension).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+    use rstest::rstest;
+    use std::fs;
+
+    fn touch(root: &Path, relative: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "// TODO: x\n").unwrap();
+    }
+
+    #[test]
+    fn test_detect_counts_languages_and_skips_generated_contents() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        for file in [
+            "src/a.ts",
+            "src/b.tsx",
+            "src/c.ts",
+            "tools/run.py",
+            "Dockerfile",
+        ] {
+            touch(root, file);
+        }
+        touch(root, "node_modules/dep/index.js");
+        touch(root, "packages/web/node_modules/dep/index.js");
+        touch(root, ".venv/lib/site.py");
+        touch(root, ".git/hooks/pre-commit.sh");
+
+        let survey = ProjectSurvey::detect(root);
+
+        let counts: Vec<(&str, usize)> = survey
+            .languages
+            .iter()
+            .map(|l| (l.name.as_str(), l.files))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![("typescript", 3), ("dockerfile", 1), ("python", 1)]
+        );
+        assert_eq!(survey.excludes, vec!["**/node_modules/*", ".venv/*"]);
+    }
+
+    #[test]
+    fn test_to_config_scans_detected_languages() {
+        let survey = ProjectSurvey {
+            languages: vec![
+                DetectedLanguage {
+                    name: "typescript".to_string(),
+                    files: 10,
+                },
+                DetectedLanguage {
+                    name: "python".to_string(),
+                    files: 2,
+                },
+            ],
+            excludes: vec!["node_modules/*".to_string(), "target/*".to_string()],
+        };
+
+        let config = survey.to_config();
+        let parsing = &config.parsing;
+
+        let mut extensions: Vec<&str> =
+            parsing.file_extensions.iter().map(String::as_str).collect();
+        extensions.sort_unstable();
+        assert_eq!(extensions, vec!["cts", "mts", "py", "pyi", "ts", "tsx"]);
+        assert_eq!(
+            parsing.languages.keys().collect::<Vec<_>>(),
+            vec!["python", "typescript"]
+        );
+        assert_eq!(
+            parsing.function_patterns,
+            vec![
+                language_function_patterns("typescript")[0],
+                language_function_patterns("python")[0]
+            ]
+        );
+        assert_eq!(
+            parsing.exclude_patterns,
+            vec!["target/*", ".git/*", "node_modules/*"]
+        );
+        TowlConfig::validate(&config).unwrap();
+    }
+
+    #[rstest]
+    #[case::nothing_detected(Vec::new())]
+    #[case::unknown_language(vec!["cobol"])]
+    fn test_to_config_keeps_defaults_without_languages(#[case] names: Vec<&str>) {
+        let survey = ProjectSurvey {
+            languages: names
+                .into_iter()
+                .map(|name| DetectedLanguage {
+                    name: name.to_string(),
+                    files: 1,
+                })
+                .collect(),
+            excludes: Vec::new(),
+        };
+
+        assert_eq!(survey.to_config(), TowlConfig::default());
+    }
+
+    #[test]
+    fn test_to_config_stays_within_pattern_budget() {
+        let survey = ProjectSurvey {
+            languages: known_languages()
+                .into_keys()
+                .map(|name| DetectedLanguage { name, files: 1 })
+                .collect(),
+            excludes: Vec::new(),
+        };
+
+        let config = survey.to_config();
+
+        TowlConfig::validate(&config).unwrap();
+        Parser::new(&config.parsing).expect("proposed config must fit the parser budget");
+        assert!(config.parsing.file_extensions.contains("swift"));
+    }
+}
@@ -0,0 +1,574 @@
+use std::fmt;
+use std::path::Path;
+
+use config::{File, FileFormat};
+use serde_json::Value;
+use toml_edit::{ImDocument, Item};
+
+use super::error::TowlConfigError;
+use super::layers::{Layered, PROFILE_KEY};
+use super::schema::resolve;
+use super::types::TowlConfig;
+use crate::comment::todo::TodoType;
+use crate::parser::Parser;
+
+/// One problem found in a config file by [`TowlConfig::check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    /// 1-based line and column of the offending key or value, if it
+    /// appears in the file.
+    pub location: Option<(usize, usize)>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.location {
+            Some((line, column)) => write!(f, "{line}:{column}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// A step on the way to a key or array element in the document.
+#[derive(Debug, Clone, Copy)]
+enum Segment<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+impl TowlConfig {
+    /// Reads a config file and returns every problem [`TowlConfig::check`]
+    /// finds in it.
+    ///
+    /// # Errors
+    /// Returns `TowlConfigError::PathTraversalAttempt` for a path containing `..`.
+    /// Returns `TowlConfigError::ReadConfigError` if the file cannot be read.
+    pub fn check_file(path: &Path) -> Result<Vec<ConfigProblem>, TowlConfigError> {
+        Self::validate_path(path)?;
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| TowlConfigError::ReadConfigError(path.to_path_buf(), e))?; // clone: error owns PathBuf
+        Ok(Self::check(&source))
+    }
+
+    /// Checks the text of a `.towl.toml` file without loading it.
+    ///
+    /// Reports, in order: TOML syntax errors; keys the schema does not know
+    /// (they would be silently ignored); values of the wrong type; values
+    /// outside the configured limits; and regexes that do not compile or
+    /// TODO patterns that name no known type. Once the syntax is valid,
+    /// every problem is reported rather than just the first.
+    #[must_use]
+    pub fn check(source: &str) -> Vec<ConfigProblem> {
+        let doc = match ImDocument::parse(source) {
+            Ok(doc) => doc,
+            Err(e) => {
+                return vec![ConfigProblem {
+                    location: e.span().map(|span| line_column(source, span.start)),
+                    message: e.message().to_string(),
+                }];
+            }
+        };
+        let checker = Checker {
+            source,
+            doc: &doc,
+            problems: Vec::new(),
+            profile: None,
+        };
+        checker.run()
+    }
+}
+
+struct Checker<'a> {
+    source: &'a str,
+    doc: &'a ImDocument<&'a str>,
+    problems: Vec<ConfigProblem>,
+    /// The profile whose overlaid config is being checked, if any.
+    profile: Option<String>,
+}
+
+impl Checker<'_> {
+    fn run(mut self) -> Vec<ConfigProblem> {
+        let schema = TowlConfig::json_schema();
+        let mut path = Vec::new();
+        self.unknown_keys(self.doc.as_item(), &schema, &schema, &mut path);
+
+        let table = toml::from_str::<toml::Table>(self.source).ok();
+        let patterns_failed = table
+            .as_ref()
+            .is_some_and(|table| self.pattern_errors(table));
+        if self.type_errors() {
+            self.problems
+                .sort_by_key(|p| p.location.unwrap_or((usize::MAX, usize::MAX)));
+            return self.problems;
+        }
+        match TowlConfig::layered_over_defaults(File::from_str(self.source, FileFormat::Toml)) {
+            Ok(config) => {
+                self.limit_errors(&config);
+                if !patterns_failed {
+                    self.parser_errors(&config);
+                }
+            }
+            Err(e) => self.report(&[], e.to_string()),
+        }
+        self.profile_errors();
+        self.problems
+            .sort_by_key(|p| p.location.unwrap_or((usize::MAX, usize::MAX)));
+        self.problems
+    }
+
+    /// Runs the limit and regex checks on the config each profile produces,
+    /// reporting only problems the base config does not already have.
+    fn profile_errors(&mut self) {
+        let Ok(mut table) = toml::from_str::<toml::Table>(self.source) else {
+            return;
+        };
+        let Some(toml::Value::Table(profiles)) = table.remove(PROFILE_KEY) else {
+            return;
+        };
+        for name in profiles.keys() {
+            let mut layered = Layered {
+                table: table.clone(), // clone: each profile overlays the base table
+                ..Layered::default()
+            };
+            layered.table.insert(
+                PROFILE_KEY.to_string(),
+                toml::Value::Table(profiles.clone()),
+            ); // clone: each profile overlays the base table
+            self.profile = Some(name.clone()); // clone: problems are reported under this profile
+            let selected = layered.select_profile(Some(name));
+            let patterns_failed = selected.is_ok() && self.pattern_errors(&layered.table);
+            let overlaid = selected
+                .and_then(|()| toml::to_string(&layered.table).map_err(TowlConfigError::from))
+                .and_then(|text| {
+                    TowlConfig::layered_over_defaults(File::from_str(&text, FileFormat::Toml))
+                });
+            match overlaid {
+                Ok(config) => {
+                    self.limit_errors(&config);
+                    if !patterns_failed {
+                        self.parser_errors(&config);
+                    }
+                }
+                Err(e) => self.report(&[], e.to_string()),
+            }
+        }
+        self.profile = None;
+    }
+
+    /// Records a problem at `path`. While checking a profile, the location is
+    /// the profile's own key if it sets one (else the profile table), and a
+    /// problem the base config already has is skipped.
+    fn report(&mut self, path: &[Segment<'_>], message: String) {
+        let Some(name) = self.profile.clone() else {
+            let location = self.locate(path);
+            self.problems.push(ConfigProblem { location, message });
+            return;
+        };
+        if self.problems.iter().any(|p| p.message == message) {
+            return;
+        }
+        let prefix = [Segment::Key(PROFILE_KEY), Segment::Key(&name)];
+        let location = self
+            .locate(&[&prefix[..], path].concat())
+            .or_else(|| self.locate(&prefix));
+        self.problems.push(ConfigProblem {
+            location,
+            message: format!("profile `{name}`: {message}"),
+        });
+    }
+
+    /// Walks the document against the schema, reporting keys it does not describe.
+    fn unknown_keys<'s>(
+        &mut self,
+        item: &'s Item,
+        schema: &Value,
+        root: &Value,
+        path: &mut Vec<&'s str>,
+    ) {
+        let schema = resolve(schema, root);
+        if let Some(table) = item.as_table_like() {
+            let properties = schema.get("properties");
+            let additional = schema.get("additionalProperties").filter(|a| a.is_object());
+            for (key, child) in table.iter() {
+                path.push(key);
+                match properties.and_then(|p| p.get(key)).or(additional) {
+                    Some(child_schema) => self.unknown_keys(child, child_schema, root, path),
+                    None => {
+                        let segments: Vec<_> = path.iter().map(|k| Segment::Key(k)).collect();
+                        self.report(
+                            &segments,
+                            format!("unknown key `{}` is ignored", path.join(".")),
+                        );
+                    }
+                }
+                path.pop();
+            }
+        } else if let Some(items) = schema.get("items") {
+            for index in 0.. {
+                let Some(element) = item.get(index) else {
+                    break;
+                };
+                self.unknown_keys(element, items, root, path);
+            }
+        }
+    }
+
+    /// Loads each known field on its own over the defaults so every type
+    /// error is found, not just the first. Profile sections are probed as
+    /// the base sections they overlay. Returns true if any was found.
+    fn type_errors(&mut self) -> bool {
+        let before = self.problems.len();
+        let Ok(table) = toml::from_str::<toml::Table>(self.source) else {
+            return false;
+        };
+        for (section, value) in &table {
+            let profiles = (section == PROFILE_KEY).then(|| value.as_table()).flatten();
+            match profiles {
+                Some(profiles) => {
+                    for (name, profile) in profiles {
+                        let prefix = [Segment::Key(section), Segment::Key(name)];
+                        match profile.as_table() {
+                            Some(sections) => {
+                                for (inner, value) in sections {
+                                    self.section_type_errors(&prefix, inner, value);
+                                }
+                            }
+                            None => self.check_field(
+                                &prefix,
+                                single(section, single(name, profile.clone())), // clone: probe document owns the value
+                            ),
+                        }
+                    }
+                }
+                None => self.section_type_errors(&[], section, value),
+            }
+        }
+        self.problems.len() > before
+    }
+
+    /// Probes each field of one top-level `section`, reported below `prefix`.
+    fn section_type_errors(&mut self, prefix: &[Segment<'_>], section: &str, value: &toml::Value) {
+        let Some(fields) = value.as_table() else {
+            self.check_field(
+                &[prefix, &[Segment::Key(section)]].concat(),
+                single(section, value.clone()), // clone: probe document owns the value
+            );
+            return;
+        };
+        for (key, value) in fields {
+            let languages = (section == "parsing" && key == "languages")
+                .then(|| value.as_table())
+                .flatten();
+            match languages {
+                Some(languages) => {
+                    for (name, profile) in languages {
+                        let probe = single(name, profile.clone()); // clone: probe document owns the value
+                        self.check_field(
+                            &[
+                                prefix,
+                                &[Segment::Key(section), Segment::Key(key), Segment::Key(name)],
+                            ]
+                            .concat(),
+                            single(section, single(key, probe)),
+                        );
+                    }
+                }
+                None => self.check_field(
+                    &[prefix, &[Segment::Key(section), Segment::Key(key)]].concat(),
+                    single(section, single(key, value.clone())), // clone: probe document owns the value
+                ),
+            }
+        }
+    }
+
+    fn check_field(&mut self, path: &[Segment<'_>], probe: toml::Value) {
+        let Ok(text) = toml::to_string(&probe) else {
+            return;
+        };
+        if let Err(e) = TowlConfig::layered_over_defaults(File::from_str(&text, FileFormat::Toml)) {
+            let message = match e {
+                TowlConfigError::CouldNotCreateConfig(inner) => inner.to_string(),
+                other => other.to_string(),
+            };
+            self.report(path, message);
+        }
+    }
+
+    /// Runs each group of limit checks, so one bad value does not hide
+    /// problems in the others.
+    fn limit_errors(&mut self, config: &TowlConfig) {
+        let checks = [
+            TowlConfig::validate_pattern_counts(&config.parsing),
+            TowlConfig::validate_string_lengths(&config.parsing),
+            TowlConfig::validate_block_comments(&config.parsing),
+            TowlConfig::validate_context_lines(&config.parsing),
+            TowlConfig::validate_rate_limit_delay(&config.github),
+            TowlConfig::validate_github_endpoints(&config.github),
+            TowlConfig::validate_credential_commands(config),
+            TowlConfig::validate_llm(&config.llm),
+        ];
+        for error in checks.into_iter().filter_map(Result::err) {
+            let field = match &error {
+                TowlConfigError::ConfigValueTooLong { field, .. }
+                    if field.starts_with("github.") || field.starts_with("llm.") =>
+                {
+                    field.clone() // clone: the error is reported below
+                }
+                TowlConfigError::TooManyConfigPatterns { field, .. }
+                | TowlConfigError::ConfigValueTooLong { field, .. }
+                | TowlConfigError::EmptyCommentDelimiter { field } => {
+                    format!("parsing.{field}")
+                }
+                TowlConfigError::ContextLinesOutOfRange { .. } => {
+                    "parsing.include_context_lines".to_string()
+                }
+                TowlConfigError::RateLimitDelayTooHigh { .. } => {
+                    "github.rate_limit_delay_ms".to_string()
+                }
+                TowlConfigError::InvalidGitHubSetting { field, .. } => format!("github.{field}"),
+                TowlConfigError::InvalidCredentialCommand { field, .. } => {
+                    field.clone() // clone: the error is reported below
+                }
+                TowlConfigError::ConcurrentAnalysesOutOfRange { .. } => {
+                    "llm.max_concurrent_analyses".to_string()
+                }
+                _ => String::new(),
+            };
+            let path: Vec<_> = field.split('.').map(Segment::Key).collect();
+            self.report(&path, error.to_string());
+        }
+    }
+
+    /// Compiles every regex in the file's pattern lists, reporting each
+    /// failure at the pattern's position. Reads the raw table, so a value
+    /// of the wrong type elsewhere does not hide these. Returns true if any
+    /// pattern failed.
+    fn pattern_errors(&mut self, table: &toml::Table) -> bool {
+        let Some(parsing) = table.get("parsing").and_then(toml::Value::as_table) else {
+            return false;
+        };
+        let mut lists: Vec<(Vec<&str>, &toml::Value, bool)> = Vec::new();
+        for (key, is_todo) in [
+            ("comment_prefixes", false),
+            ("todo_patterns", true),
+            ("function_patterns", false),
+        ] {
+            if let Some(patterns) = parsing.get(key) {
+                lists.push((vec!["parsing", key], patterns, is_todo));
+            }
+        }
+        let languages = parsing.get("languages").and_then(toml::Value::as_table);
+        for (name, profile) in languages.into_iter().flatten() {
+            for key in ["comment_prefixes", "placeholder_patterns"] {
+                if let Some(patterns) = profile.get(key) {
+                    lists.push((
+                        vec!["parsing", "languages", name.as_str(), key],
+                        patterns,
+                        false,
+                    ));
+                }
+            }
+        }
+
+        let mut failed = false;
+        for (keys, patterns, is_todo) in lists {
+            let patterns = patterns.as_array().map_or(&[][..], Vec::as_slice);
+            for (index, pattern) in patterns.iter().enumerate() {
+                // a non-string entry is reported as a type error
+                let Some(pattern) = pattern.as_str() else {
+                    continue;
+                };
+                let mut path: Vec<_> = keys.iter().map(|k| Segment::Key(k)).collect();
+                path.push(Segment::Index(index));
+                let error = match Parser::build_regex(pattern) {
+                    Err(e) => Some(e.to_string()),
+                    Ok(_) if is_todo => TodoType::try_from(pattern).err().map(|e| e.to_string()),
+                    Ok(_) => None,
+                };
+                if let Some(message) = error {
+                    failed = true;
+                    self.report(&path, message);
+                }
+            }
+        }
+        failed
+    }
+
+    /// Builds the parser, to catch pattern budget and glob errors.
+    fn parser_errors(&mut self, config: &TowlConfig) {
+        if let Err(e) = Parser::new(&config.parsing) {
+            self.report(&[], e.to_string());
+        }
+    }
+
+    /// The 1-based line and column of a key (or array element) in the file.
+    fn locate(&self, path: &[Segment<'_>]) -> Option<(usize, usize)> {
+        let (last, parents) = path.split_last()?;
+        let mut item = self.doc.as_item();
+        for segment in parents {
+            item = match segment {
+                Segment::Key(key) => item.get(*key)?,
+                Segment::Index(index) => item.get(*index)?,
+            };
+        }
+        let span = match last {
+            Segment::Key(key) => item
+                .as_table_like()
+                .and_then(|table| table.key(key))
+                .and_then(toml_edit::Key::span)
+                .or_else(|| item.get(*key).and_then(Item::span)),
+            Segment::Index(index) => item.get(*index).and_then(Item::span),
+        }?;
+        Some(line_column(self.source, span.start))
+    }
+}
+
+/// A one-entry table, for probing a single field.
+fn single(key: &str, value: toml::Value) -> toml::Value {
+    let mut table = toml::Table::new();
+    table.insert(key.to_string(), value);
+    toml::Value::Table(table)
+}
+
+/// Converts a byte offset into a 1-based line and column (in characters).
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = source.get(..offset).unwrap_or(source);
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn problems(source: &str) -> Vec<String> {
+        TowlConfig::check(source)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_config_has_no_problems() {
+        let source = "[parsing]\nfile_extensions = [\"rs\"]\ninclude_context_lines = 5\n\n\
+                      [parsing.languages.terraform]\nextensions = [\"tf\"]\n\
+                      block_comments = [{ start = \"/*\", end = \"*/\" }]\n\n\
+                      [github]\nrate_limit_delay_ms = 500\n";
+        assert!(problems(source).is_empty(), "{:?}", problems(source));
+    }
+
+    #[test]
+    fn test_default_config_has_no_problems() {
+        let source = toml::to_string_pretty(&TowlConfig::default()).unwrap();
+        assert!(problems(&source).is_empty(), "{:?}", problems(&source));
+    }
+
+    #[rstest]
+    #[case::syntax("[parsing\n", "1:9:")]
+    #[case::unknown_section("[parsin]\n", "1:2: unknown key `parsin`")]
+    #[case::unknown_key("[parsing]\n\nexclude = []\n", "3:1: unknown key `parsing.exclude`")]
+    #[case::unknown_language_key(
+        "[parsing.languages.rust]\nprefixes = []\n",
+        "2:1: unknown key `parsing.languages.rust.prefixes`"
+    )]
+    #[case::env_only("[github]\ntoken = \"x\"\n", "2:1: unknown key `github.token`")]
+    #[case::wrong_type("[parsing]\nfollow_symlinks = \"maybe\"\n", "2:1:")]
+    #[case::out_of_range("[parsing]\ninclude_context_lines = 99\n", "2:1: Config context_lines")]
+    #[case::bad_regex(
+        "[parsing]\ntodo_patterns = [\"TODO:\", \"FIXME:(\"]\n",
+        "2:27: Pattern FIXME:( is not a valid regex"
+    )]
+    #[case::unknown_todo_type("[parsing]\ntodo_patterns = [\"LATER:(.*)\"]\n", "2:18:")]
+    #[case::language_regex(
+        "[parsing.languages.x]\nplaceholder_patterns = [\"(\"]\n",
+        "2:25: Pattern ( is not a valid regex"
+    )]
+    #[case::empty_delimiter(
+        "[parsing.languages.x]\nblock_comments = [{ start = \"\", end = \"*/\" }]\n",
+        "2:1: Config languages.x.block_comments"
+    )]
+    #[case::relative_helper(
+        "[github]\ntoken_command = [\"bin/token\"]\n",
+        "2:1: Config github.token_command is invalid"
+    )]
+    #[case::profile_unknown_key(
+        "[profile.ci.llm]\nmodle = \"x\"\n",
+        "2:1: unknown key `profile.ci.llm.modle`"
+    )]
+    #[case::profile_wrong_type("[profile.ci.parsing]\nfollow_symlinks = \"maybe\"\n", "2:1:")]
+    #[case::profile_out_of_range(
+        "[profile.ci.parsing]\ninclude_context_lines = 0\n",
+        "2:1: profile `ci`: Config context_lines"
+    )]
+    fn test_problem_reported_with_location(#[case] source: &str, #[case] expected: &str) {
+        let found = problems(source);
+        assert!(
+            found.iter().any(|p| p.starts_with(expected)),
+            "expected {expected:?} in {found:?}"
+        );
+    }
+
+    #[test]
+    fn test_every_problem_is_reported() {
+        let source = "[parsing]\nfollow_symlinks = \"maybe\"\ninclude_context_lines = \"many\"\n\
+                      typo = 1\n\n[llm]\nmax_tokens = -1\n";
+        let found = problems(source);
+        let lines: Vec<&str> = found.iter().map(|p| p.split(':').next().unwrap()).collect();
+        assert_eq!(lines, ["2", "3", "4", "7"], "{found:?}");
+    }
+
+    #[test]
+    fn test_pattern_problems_reported_despite_type_errors() {
+        let source = "[parsing]\ninclude_context_lines = \"five\"\n\
+                      todo_patterns = [\"(?i)TODO:(\", \"FOO\"]\n";
+        let found = problems(source);
+        assert_eq!(found.len(), 3, "{found:?}");
+        assert!(found[0].starts_with("2:1:"), "{found:?}");
+        assert!(
+            found[1].starts_with("3:18: Pattern (?i)TODO:( is not a valid regex"),
+            "{found:?}"
+        );
+        assert!(
+            found[2].starts_with("3:32: Cannot recognise FOO"),
+            "{found:?}"
+        );
+    }
+
+    #[test]
+    fn test_base_problem_not_repeated_for_profiles() {
+        let source = "[parsing]\ninclude_context_lines = 0\n\n\
+                      [profile.ci.llm]\nmodel = \"x\"\n\n[profile.release.llm]\nmodel = \"y\"\n";
+        let found = problems(source);
+        assert_eq!(found.len(), 1, "{found:?}");
+        assert!(
+            found[0].starts_with("2:1: Config context_lines"),
+            "{found:?}"
+        );
+    }
+
+    #[test]
+    fn test_limits_and_regexes_reported_together() {
+        let source = "[parsing]\ninclude_context_lines = 0\nfunction_patterns = [\"[\"]\n\n\
+                      [github]\nrate_limit_delay_ms = 999999\n";
+        let found = problems(source);
+        assert_eq!(found.len(), 3, "{found:?}");
+    }
+
+    #[rstest]
+    #[case("abc", 0, (1, 1))]
+    #[case("abc", 2, (1, 3))]
+    #[case("a\nbc", 3, (2, 2))]
+    #[case("é\nx", 3, (2, 1))]
+    #[case("éx", 2, (1, 2))]
+    fn test_line_column(
+        #[case] source: &str,
+        #[case] offset: usize,
+        #[case] expected: (usize, usize),
+    ) {
+        assert_eq!(line_column(source, offset), expected);
+    }
+}
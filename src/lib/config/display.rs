@@ -48,6 +48,32 @@ impl fmt::Display for TowlConfig {
             "│  ├─ Exclude Patterns: {}",
             self.parsing.exclude_patterns.join(", ")
         )?;
+        writeln!(f, "│  ├─ Discovery: {}", self.parsing.discovery)?;
+        let symlinks = match (
+            self.parsing.follow_symlinks,
+            self.parsing.allow_symlinks_outside_root,
+        ) {
+            (false, _) => "not followed",
+            (true, false) => "followed within scan root",
+            (true, true) => "followed anywhere",
+        };
+        writeln!(f, "│  ├─ Symlinks: {symlinks}")?;
+        writeln!(
+            f,
+            "│  ├─ Placeholders: {}",
+            if self.parsing.detect_placeholders {
+                "detected"
+            } else {
+                "not detected"
+            }
+        )?;
+        if !self.parsing.include_patterns.is_empty() {
+            writeln!(
+                f,
+                "│  ├─ Include Patterns: {}",
+                self.parsing.include_patterns.join(", ")
+            )?;
+        }
         writeln!(
             f,
             "│  ├─ Context Lines: {}",
@@ -55,6 +81,22 @@ impl fmt::Display for TowlConfig {
         )?;
         fmt_list_section(f, "Comment Prefixes", &self.parsing.comment_prefixes, false)?;
         fmt_list_section(f, "TODO Patterns", &self.parsing.todo_patterns, false)?;
+        let languages: Vec<String> = self
+            .parsing
+            .languages
+            .iter()
+            .map(|(name, profile)| {
+                let matchers: Vec<&str> = profile
+                    .extensions
+                    .iter()
+                    .chain(&profile.filenames)
+                    .chain(&profile.interpreters)
+                    .map(String::as_str)
+                    .collect();
+                format!("{name}: {}", matchers.join(", "))
+            })
+            .collect();
+        fmt_list_section(f, "Languages", &languages, false)?;
         fmt_list_section(
             f,
             "Function Patterns",
@@ -67,10 +109,12 @@ impl fmt::Display for TowlConfig {
         writeln!(
             f,
             "│  ├─ Token: {}",
-            if self.github.token.expose_secret().is_empty() {
-                "not set"
-            } else {
+            if !self.github.token.expose_secret().is_empty() {
                 "configured"
+            } else if self.github.token_command.is_some() {
+                "via token_command"
+            } else {
+                "not set"
             }
         )?;
         writeln!(
@@ -89,10 +133,12 @@ impl fmt::Display for TowlConfig {
         writeln!(
             f,
             "   ├─ API Key: {}",
-            if self.llm.api_key.expose_secret().is_empty() {
-                "not set"
-            } else {
+            if !self.llm.api_key.expose_secret().is_empty() {
                 "configured"
+            } else if self.llm.api_key_command.is_some() {
+                "via api_key_command"
+            } else {
+                "not set"
             }
         )?;
         writeln!(
@@ -27,6 +27,20 @@ pub enum TowlConfigError {
     ConfigAlreadyExists(PathBuf),
     #[error("Config file could not be written to path {0}: {1}")]
     WriteToFileError(PathBuf, std::io::Error),
+    #[error("Config file could not be read from path {0}: {1}")]
+    ReadConfigError(PathBuf, std::io::Error),
+    #[error("Could not read an answer from stdin: {0}")]
+    PromptError(std::io::Error),
+    #[error("Config file {path} extends itself: {chain}")]
+    ExtendsCycle { path: PathBuf, chain: String },
+    #[error("Config profile `{name}` is not defined (available: {available})")]
+    UnknownProfile { name: String, available: String },
+    #[error("Invalid config override `{setting}`: {reason}")]
+    InvalidOverride { setting: String, reason: String },
+    #[error("Unknown config key `{key}` in {from}")]
+    UnknownConfigKey { key: String, from: String },
+    #[error("Config file {path} has {count} problem(s)")]
+    InvalidConfigFile { path: PathBuf, count: usize },
     #[error("Could not parse toml for config {0}")]
     UnableToParseToml(#[from] toml::ser::Error),
     #[error("Could not create config {0}")]
@@ -55,6 +69,14 @@ pub enum TowlConfigError {
         min: usize,
         max: usize,
     },
+    #[error("Config {field} has an empty comment delimiter")]
+    EmptyCommentDelimiter { field: String },
+    #[error("Config github.{field} is invalid: {message}")]
+    InvalidGitHubSetting { field: String, message: String },
+    #[error("Config {field} is invalid: {message}")]
+    InvalidCredentialCommand { field: String, message: String },
+    #[error("Credential helper {setting} failed: {message}")]
+    CredentialHelperFailed { setting: String, message: String },
     #[error("Config rate_limit_delay_ms value {value} exceeds maximum ({max})")]
     RateLimitDelayTooHigh { value: u64, max: u64 },
     #[error("Config max_concurrent_analyses value {value} is out of range ({min}..={max})")]
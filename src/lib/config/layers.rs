@@ -0,0 +1,850 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+use config::ConfigError;
+use serde::Deserialize;
+
+use super::error::TowlConfigError;
+use super::types::{MergeConfig, TowlConfig};
+
+/// Where a config value came from, as shown by `towl config --show-origin`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    /// Built-in default.
+    Default,
+    /// Set by a config file (shown relative to the working directory when
+    /// it lies below it).
+    File(PathBuf),
+    /// Overridden by an environment variable.
+    Env(String),
+    /// Overridden with `--set`.
+    CommandLine,
+    /// A list built from several sources through `merge.append`, in order.
+    Appended(Vec<Origin>),
+    /// Set by the selected `[profile.<name>]` table, itself from `source`.
+    Profile { name: String, source: Box<Origin> },
+}
+
+impl Origin {
+    /// The origin of a list that `next` appended to.
+    fn then(self, next: Self) -> Self {
+        let mut parts = match self {
+            Self::Appended(parts) => parts,
+            other => vec![other],
+        };
+        match next {
+            Self::Appended(more) => parts.extend(more),
+            other => parts.push(other),
+        }
+        Self::Appended(parts)
+    }
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::File(path) => write!(f, "file:{}", path.display()),
+            Self::Env(var) => write!(f, "env:{var}"),
+            Self::CommandLine => write!(f, "cli:--set"),
+            Self::Profile { name, source } => write!(f, "profile:{name}({source})"),
+            Self::Appended(parts) => {
+                for (i, part) in parts.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "+")?;
+                    }
+                    write!(f, "{part}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+static DEFAULT_ORIGIN: Origin = Origin::Default;
+
+/// A source of config values, as listed by `towl config`; see
+/// [`TowlConfig::layers`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigLayer {
+    Defaults,
+    /// The user-level config, for personal preferences.
+    Global {
+        path: PathBuf,
+        found: bool,
+    },
+    /// The project's `.towl.toml` (or `--config`/`TOWL_CONFIG`) and the files it extends.
+    Project {
+        path: PathBuf,
+        found: bool,
+    },
+    /// The `[profile.<name>]` tables selected with `--profile` or `TOWL_PROFILE`.
+    Profile(String),
+    Environment,
+    /// `--set` overrides.
+    CommandLine,
+}
+
+impl fmt::Display for ConfigLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (label, path, found) = match self {
+            Self::Defaults => return write!(f, "built-in defaults"),
+            Self::Environment => return write!(f, "environment variables (TOWL_*)"),
+            Self::Profile(name) => return write!(f, "profile {name}"),
+            Self::CommandLine => return write!(f, "command-line overrides (--set)"),
+            Self::Global { path, found } => ("global config", path, found),
+            Self::Project { path, found } => ("project config", path, found),
+        };
+        write!(f, "{label} {}", path.display())?;
+        if !found {
+            write!(f, " (not found)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Which file or environment variable set each config value, keyed by
+/// dotted path (e.g. `parsing.exclude_patterns`). Returned by
+/// [`TowlConfig::load_with_origins`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigOrigins {
+    values: BTreeMap<String, Origin>,
+}
+
+impl ConfigOrigins {
+    /// Origin of the value at `key`; values nothing overrode are [`Origin::Default`].
+    #[must_use]
+    pub fn origin(&self, key: &str) -> &Origin {
+        self.values.get(key).unwrap_or(&DEFAULT_ORIGIN)
+    }
+
+    pub(crate) fn set(&mut self, key: &str, origin: Origin) {
+        self.values.insert(key.to_string(), origin);
+    }
+
+    /// Lists every serialised value of `config` as `origin<TAB>key = value`,
+    /// one per line in key order, like `git config --show-origin`.
+    #[must_use]
+    pub fn render(&self, config: &TowlConfig) -> String {
+        let Ok(toml::Value::Table(table)) = toml::Value::try_from(config) else {
+            return String::new();
+        };
+        let mut leaves = Vec::new();
+        flatten("", &table, &mut leaves);
+        leaves
+            .iter()
+            .map(|(key, value)| format!("{}\t{key} = {value}\n", self.origin(key)))
+            .collect()
+    }
+
+    /// Records `origin` for every leaf value under `key`.
+    pub(super) fn record(&mut self, key: &str, value: &toml::Value, origin: &Origin) {
+        match value {
+            toml::Value::Table(table) => {
+                for (child, value) in table {
+                    self.record(&join(key, child), value, origin);
+                }
+            }
+            _ => self.set(key, origin.clone()), // clone: each leaf records its own origin
+        }
+    }
+
+    /// Forgets `key` and everything under it.
+    pub(super) fn clear(&mut self, key: &str) {
+        let nested = format!("{key}.");
+        self.values
+            .retain(|k, _| k != key && !k.starts_with(nested.as_str()));
+    }
+}
+
+/// Top-level key holding the named profiles.
+pub(super) const PROFILE_KEY: &str = "profile";
+
+/// Sections of the config that profiles, `TOWL__*` variables and `--set`
+/// may change.
+pub(super) const SECTIONS: [&str; 3] = ["parsing", "github", "llm"];
+
+/// The global and project config files merged over everything they
+/// `extends`, with the meta keys (`extends`, `merge`) removed.
+#[derive(Debug, Default)]
+pub(super) struct Layered {
+    pub(super) table: toml::Table,
+    pub(super) origins: ConfigOrigins,
+}
+
+/// The keys each file in a chain may use to describe its inheritance.
+#[derive(Debug, Deserialize)]
+struct Meta {
+    #[serde(default)]
+    extends: Vec<PathBuf>,
+    #[serde(default)]
+    merge: MergeConfig,
+}
+
+impl Layered {
+    /// Reads the optional user-level `global` config, then `project` over
+    /// it. Each file is applied depth first after the files it `extends`,
+    /// so every file sees the result of everything before it. Missing
+    /// `global` or `project` files give no layer, as a missing `.towl.toml`
+    /// always has; a missing parent is an error.
+    ///
+    /// `extends` entries are resolved against the extending file's directory
+    /// and normalised before the usual path-traversal check, so
+    /// `../shared/base.toml` is allowed but the path that is read never
+    /// contains `..`. A file reached through several parents is applied once.
+    ///
+    /// # Errors
+    /// Returns `TowlConfigError::ExtendsCycle` if a file extends itself,
+    /// directly or not, and `TowlConfigError::ReadConfigError` or
+    /// `TowlConfigError::CouldNotCreateConfig` if a file in the chain cannot
+    /// be read or parsed.
+    pub(super) fn resolve(global: Option<&Path>, project: &Path) -> Result<Self, TowlConfigError> {
+        let cwd = std::env::current_dir()
+            .map_err(|e| TowlConfigError::ReadConfigError(project.to_path_buf(), e))?; // clone: error owns PathBuf
+        let defaults = match toml::Value::try_from(TowlConfig::default()) {
+            Ok(toml::Value::Table(table)) => table,
+            _ => toml::Table::new(),
+        };
+        let mut resolver = Resolver {
+            cwd,
+            defaults,
+            chain: Vec::new(),
+            applied: HashSet::new(),
+        };
+        let mut layered = Self::default();
+        for path in global.into_iter().chain(std::iter::once(project)) {
+            let path = normalize(&resolver.cwd.join(path));
+            resolver.apply_file(&mut layered, &path, true)?;
+        }
+        Ok(layered)
+    }
+
+    /// Removes the `profile` tables, first overlaying the `parsing`,
+    /// `github` and `llm` sections of profile `name`, if given.
+    ///
+    /// Profiles are merged across files like any other table, so the
+    /// project's `[profile.ci]` extends the user-level one. The selected
+    /// profile then applies over the merged result: tables key by key,
+    /// anything else, lists included, replaced.
+    ///
+    /// # Errors
+    /// Returns `TowlConfigError::UnknownProfile` if no file defines `name`.
+    pub(super) fn select_profile(&mut self, name: Option<&str>) -> Result<(), TowlConfigError> {
+        let mut profiles = match self.table.remove(PROFILE_KEY) {
+            Some(toml::Value::Table(profiles)) => profiles,
+            Some(other) => {
+                // Left in place so deserialising reports the type error
+                self.table.insert(PROFILE_KEY.to_string(), other);
+                toml::Table::new()
+            }
+            None => toml::Table::new(),
+        };
+        let selected = match name {
+            None => None,
+            Some(name) => match profiles.remove(name) {
+                Some(toml::Value::Table(profile)) => Some((name, profile)),
+                _ => {
+                    let available: Vec<&str> = profiles.keys().map(String::as_str).collect();
+                    return Err(TowlConfigError::UnknownProfile {
+                        name: name.to_string(),
+                        available: if available.is_empty() {
+                            "none".to_string()
+                        } else {
+                            available.join(", ")
+                        },
+                    });
+                }
+            },
+        };
+
+        if let Some((name, mut profile)) = selected {
+            let sections: toml::Table = SECTIONS
+                .iter()
+                .filter_map(|section| Some(((*section).to_string(), profile.remove(*section)?)))
+                .collect();
+            overlay(
+                &mut self.table,
+                &mut self.origins,
+                "",
+                &join(PROFILE_KEY, name),
+                sections,
+                name,
+            );
+        }
+        self.origins.clear(PROFILE_KEY);
+        Ok(())
+    }
+}
+
+/// Merges the profile table `top` (at `source`) over `base` (at `target`),
+/// recording each value as set by profile `name`.
+fn overlay(
+    base: &mut toml::Table,
+    origins: &mut ConfigOrigins,
+    target: &str,
+    source: &str,
+    top: toml::Table,
+    name: &str,
+) {
+    for (key, value) in top {
+        let to = join(target, &key);
+        let from = join(source, &key);
+        let merged = match (base.remove(&key), value) {
+            (existing @ (None | Some(toml::Value::Table(_))), toml::Value::Table(inner)) => {
+                let mut table = match existing {
+                    Some(toml::Value::Table(table)) => table,
+                    _ => toml::Table::new(),
+                };
+                overlay(&mut table, origins, &to, &from, inner, name);
+                toml::Value::Table(table)
+            }
+            (_, value) => {
+                let origin = Origin::Profile {
+                    name: name.to_string(),
+                    source: Box::new(origins.origin(&from).clone()), // clone: the profile key's origin is cleared afterwards
+                };
+                origins.clear(&to);
+                origins.record(&to, &value, &origin);
+                value
+            }
+        };
+        base.insert(key, merged);
+    }
+}
+
+struct Resolver {
+    cwd: PathBuf,
+    defaults: toml::Table,
+    /// Canonical paths of the files currently being resolved, outermost first.
+    chain: Vec<PathBuf>,
+    /// Canonical paths of the files already merged.
+    applied: HashSet<PathBuf>,
+}
+
+impl Resolver {
+    fn apply_file(
+        &mut self,
+        layered: &mut Layered,
+        path: &Path,
+        optional: bool,
+    ) -> Result<(), TowlConfigError> {
+        TowlConfig::validate_path(path)?;
+        let identity = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()); // clone: fallback identity for a missing file
+        if self.chain.contains(&identity) {
+            let chain = self
+                .chain
+                .iter()
+                .chain(std::iter::once(&identity))
+                .map(|p| self.display(p).display().to_string())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(TowlConfigError::ExtendsCycle {
+                path: self.display(path),
+                chain,
+            });
+        }
+        if self.applied.contains(&identity) {
+            return Ok(());
+        }
+
+        let source = match std::fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) if optional && e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(TowlConfigError::ReadConfigError(self.display(path), e)),
+        };
+        let mut table: toml::Table =
+            toml::from_str(&source).map_err(|e| self.parse_error(path, e))?;
+        let mut meta_table = toml::Table::new();
+        for key in ["extends", "merge"] {
+            if let Some(value) = table.remove(key) {
+                meta_table.insert(key.to_string(), value);
+            }
+        }
+        let meta = Meta::deserialize(toml::Value::Table(meta_table))
+            .map_err(|e| self.parse_error(path, e))?;
+
+        self.chain.push(identity);
+        let dir = path.parent().unwrap_or(Path::new(""));
+        for parent in &meta.extends {
+            self.apply_file(layered, &normalize(&dir.join(parent)), false)?;
+        }
+        if let Some(identity) = self.chain.pop() {
+            self.applied.insert(identity);
+        }
+
+        let origin = Origin::File(self.display(path));
+        self.merge(
+            &mut layered.table,
+            &mut layered.origins,
+            "",
+            table,
+            &origin,
+            &meta.merge.append,
+        );
+        Ok(())
+    }
+
+    /// Merges `top` over `base` (the table at `prefix`): tables key by key,
+    /// lists in `append` after the existing (or default) list, anything else
+    /// replaced.
+    fn merge(
+        &self,
+        base: &mut toml::Table,
+        origins: &mut ConfigOrigins,
+        prefix: &str,
+        top: toml::Table,
+        from: &Origin,
+        append: &[String],
+    ) {
+        for (key, value) in top {
+            let path = join(prefix, &key);
+            let merged = match (base.remove(&key), value) {
+                (existing @ (None | Some(toml::Value::Table(_))), toml::Value::Table(inner)) => {
+                    let mut table = match existing {
+                        Some(toml::Value::Table(table)) => table,
+                        _ => toml::Table::new(),
+                    };
+                    self.merge(&mut table, origins, &path, inner, from, append);
+                    toml::Value::Table(table)
+                }
+                (existing, toml::Value::Array(items)) if append.contains(&path) => {
+                    let (mut list, origin) = match existing {
+                        Some(toml::Value::Array(list)) => (list, origins.origin(&path).clone()), // clone: the appended origin extends it
+                        _ => (
+                            lookup(&self.defaults, &path)
+                                .and_then(toml::Value::as_array)
+                                .cloned() // clone: defaults are shared by every layer
+                                .unwrap_or_default(),
+                            Origin::Default,
+                        ),
+                    };
+                    list.extend(items);
+                    origins.set(&path, origin.then(from.clone())); // clone: every value this file sets shares its origin
+                    toml::Value::Array(list)
+                }
+                (_, value) => {
+                    origins.clear(&path);
+                    origins.record(&path, &value, from);
+                    value
+                }
+            };
+            base.insert(key, merged);
+        }
+    }
+
+    /// How `path` is shown in origins and errors: relative to the working
+    /// directory when below it.
+    fn display(&self, path: &Path) -> PathBuf {
+        path.strip_prefix(&self.cwd).unwrap_or(path).to_path_buf()
+    }
+
+    fn parse_error(
+        &self,
+        path: &Path,
+        cause: impl std::error::Error + Send + Sync + 'static,
+    ) -> TowlConfigError {
+        TowlConfigError::CouldNotCreateConfig(ConfigError::FileParse {
+            uri: Some(self.display(path).display().to_string()),
+            cause: Box::new(cause),
+        })
+    }
+}
+
+/// Removes `.` and `..` components without touching the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{prefix}.{key}")
+    }
+}
+
+fn lookup<'a>(table: &'a toml::Table, path: &str) -> Option<&'a toml::Value> {
+    let (parents, last) = path.rsplit_once('.').map_or(("", path), |(p, l)| (p, l));
+    let mut current = table;
+    for key in parents.split('.').filter(|k| !k.is_empty()) {
+        current = current.get(key)?.as_table()?;
+    }
+    current.get(last)
+}
+
+fn flatten(prefix: &str, table: &toml::Table, leaves: &mut Vec<(String, toml::Value)>) {
+    for (key, value) in table {
+        let path = join(prefix, key);
+        match value {
+            toml::Value::Table(inner) => flatten(&path, inner, leaves),
+            other => leaves.push((path, other.clone())), // clone: leaves outlive the serialised config
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rstest::rstest;
+    use tempfile::TempDir;
+
+    fn write(dir: &TempDir, name: &str, content: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).unwrap();
+        }
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn value<'a>(layered: &'a Layered, key: &str) -> &'a toml::Value {
+        lookup(&layered.table, key).unwrap_or_else(|| panic!("{key} not set"))
+    }
+
+    fn strings(value: &toml::Value) -> Vec<&str> {
+        value
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_scalars_override_and_lists_replace() {
+        let dir = TempDir::new().unwrap();
+        write(
+            &dir,
+            "shared/base.toml",
+            "[parsing]\ninclude_context_lines = 4\nexclude_patterns = [\"vendor/*\"]\n\n[llm]\nmodel = \"base\"\n",
+        );
+        let child = write(
+            &dir,
+            "repo/.towl.toml",
+            "extends = [\"../shared/base.toml\"]\n\n[parsing]\ninclude_context_lines = 7\nexclude_patterns = [\"gen/*\"]\n",
+        );
+
+        let layered = Layered::resolve(None, &child).unwrap();
+
+        assert_eq!(
+            value(&layered, "parsing.include_context_lines").as_integer(),
+            Some(7)
+        );
+        assert_eq!(
+            strings(value(&layered, "parsing.exclude_patterns")),
+            ["gen/*"]
+        );
+        assert_eq!(value(&layered, "llm.model").as_str(), Some("base"));
+        assert!(layered.table.get("extends").is_none());
+    }
+
+    #[test]
+    fn test_append_extends_inherited_list() {
+        let dir = TempDir::new().unwrap();
+        let base = write(
+            &dir,
+            "base.toml",
+            "[parsing]\nexclude_patterns = [\"vendor/*\"]\n",
+        );
+        let child = write(
+            &dir,
+            "child.toml",
+            "extends = [\"base.toml\"]\n\n[merge]\nappend = [\"parsing.exclude_patterns\"]\n\n[parsing]\nexclude_patterns = [\"gen/*\"]\n",
+        );
+
+        let layered = Layered::resolve(None, &child).unwrap();
+
+        assert_eq!(
+            strings(value(&layered, "parsing.exclude_patterns")),
+            ["vendor/*", "gen/*"]
+        );
+        assert_eq!(
+            layered.origins.origin("parsing.exclude_patterns"),
+            &Origin::Appended(vec![Origin::File(base), Origin::File(child)])
+        );
+    }
+
+    #[test]
+    fn test_append_without_inherited_list_extends_defaults() {
+        let dir = TempDir::new().unwrap();
+        let path = write(
+            &dir,
+            ".towl.toml",
+            "[merge]\nappend = [\"parsing.exclude_patterns\"]\n\n[parsing]\nexclude_patterns = [\"gen/*\"]\n",
+        );
+
+        let layered = Layered::resolve(None, &path).unwrap();
+
+        let mut expected = super::super::defaults::default_exclude_patterns();
+        expected.push("gen/*".to_string());
+        assert_eq!(
+            strings(value(&layered, "parsing.exclude_patterns")),
+            expected
+        );
+        assert_eq!(
+            layered
+                .origins
+                .origin("parsing.exclude_patterns")
+                .to_string(),
+            format!("default+file:{}", path.display())
+        );
+    }
+
+    #[test]
+    fn test_later_parents_override_earlier_ones() {
+        let dir = TempDir::new().unwrap();
+        let second = write(&dir, "b.toml", "[llm]\nmodel = \"b\"\n");
+        write(&dir, "a.toml", "[llm]\nmodel = \"a\"\nmax_retries = 5\n");
+        let child = write(&dir, "child.toml", "extends = [\"a.toml\", \"b.toml\"]\n");
+
+        let layered = Layered::resolve(None, &child).unwrap();
+
+        assert_eq!(value(&layered, "llm.model").as_str(), Some("b"));
+        assert_eq!(value(&layered, "llm.max_retries").as_integer(), Some(5));
+        assert_eq!(layered.origins.origin("llm.model"), &Origin::File(second));
+    }
+
+    #[test]
+    fn test_shared_ancestor_is_not_a_cycle() {
+        let dir = TempDir::new().unwrap();
+        write(&dir, "root.toml", "[llm]\nmodel = \"root\"\n");
+        write(&dir, "a.toml", "extends = [\"root.toml\"]\n");
+        write(&dir, "b.toml", "extends = [\"root.toml\"]\n");
+        let child = write(&dir, "child.toml", "extends = [\"a.toml\", \"b.toml\"]\n");
+
+        let layered = Layered::resolve(None, &child).unwrap();
+
+        assert_eq!(value(&layered, "llm.model").as_str(), Some("root"));
+    }
+
+    #[rstest]
+    #[case::self_reference(&[("a.toml", "extends = [\"a.toml\"]\n")])]
+    #[case::indirect(&[
+        ("a.toml", "extends = [\"b.toml\"]\n"),
+        ("b.toml", "extends = [\"./sub/../a.toml\"]\n"),
+    ])]
+    fn test_cycles_are_rejected(#[case] files: &[(&str, &str)]) {
+        let dir = TempDir::new().unwrap();
+        for (name, content) in files {
+            write(&dir, name, content);
+        }
+
+        let result = Layered::resolve(None, &dir.path().join("a.toml"));
+
+        assert!(
+            matches!(result, Err(TowlConfigError::ExtendsCycle { .. })),
+            "{:?}",
+            result.err()
+        );
+    }
+
+    #[test]
+    fn test_missing_parent_is_an_error() {
+        let dir = TempDir::new().unwrap();
+        let child = write(&dir, "child.toml", "extends = [\"missing.toml\"]\n");
+
+        let result = Layered::resolve(None, &child);
+
+        assert!(matches!(result, Err(TowlConfigError::ReadConfigError(..))));
+    }
+
+    #[test]
+    fn test_missing_config_file_is_empty() {
+        let dir = TempDir::new().unwrap();
+
+        let layered = Layered::resolve(None, &dir.path().join(".towl.toml")).unwrap();
+
+        assert!(layered.table.is_empty());
+    }
+
+    #[rstest]
+    #[case::not_a_list("extends = \"base.toml\"\n")]
+    #[case::bad_merge("[merge]\nappend = 3\n")]
+    #[case::bad_toml("extends = [\n")]
+    fn test_malformed_chain_is_a_parse_error(#[case] content: &str) {
+        let dir = TempDir::new().unwrap();
+        let path = write(&dir, "child.toml", content);
+
+        let result = Layered::resolve(None, &path);
+
+        assert!(matches!(
+            result,
+            Err(TowlConfigError::CouldNotCreateConfig(
+                ConfigError::FileParse { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_render_lists_origin_of_each_value() {
+        let mut origins = ConfigOrigins::default();
+        origins.set("llm.model", Origin::Env("TOWL_LLM_MODEL".to_string()));
+
+        let rendered = origins.render(&TowlConfig::default());
+
+        assert!(rendered.contains("env:TOWL_LLM_MODEL\tllm.model = "));
+        assert!(rendered.contains("default\tparsing.include_context_lines = 10\n"));
+        assert!(!rendered.contains("extends"));
+    }
+
+    #[test]
+    fn test_project_applies_over_global() {
+        let dir = TempDir::new().unwrap();
+        let global = write(
+            &dir,
+            "xdg/towl/config.toml",
+            "[llm]\nprovider = \"openai\"\nmodel = \"mine\"\n\n[parsing]\nexclude_patterns = [\"scratch/*\"]\n",
+        );
+        let project = write(
+            &dir,
+            "repo/.towl.toml",
+            "[merge]\nappend = [\"parsing.exclude_patterns\"]\n\n[llm]\nmodel = \"team\"\n\n[parsing]\nexclude_patterns = [\"gen/*\"]\n",
+        );
+
+        let layered = Layered::resolve(Some(&global), &project).unwrap();
+
+        assert_eq!(value(&layered, "llm.provider").as_str(), Some("openai"));
+        assert_eq!(value(&layered, "llm.model").as_str(), Some("team"));
+        assert_eq!(
+            strings(value(&layered, "parsing.exclude_patterns")),
+            ["scratch/*", "gen/*"]
+        );
+        assert_eq!(
+            layered.origins.origin("llm.provider"),
+            &Origin::File(global)
+        );
+        assert_eq!(layered.origins.origin("llm.model"), &Origin::File(project));
+    }
+
+    #[test]
+    fn test_missing_global_config_is_skipped() {
+        let dir = TempDir::new().unwrap();
+        let project = write(&dir, ".towl.toml", "[llm]\nmodel = \"team\"\n");
+
+        let layered = Layered::resolve(Some(&dir.path().join("none.toml")), &project).unwrap();
+
+        assert_eq!(value(&layered, "llm.model").as_str(), Some("team"));
+    }
+
+    #[test]
+    fn test_selected_profile_overlays_merged_config() {
+        let dir = TempDir::new().unwrap();
+        let global = write(
+            &dir,
+            "xdg/towl/config.toml",
+            "[profile.ci.llm]
+model = \"cheap\"\nmax_retries = 1\n",
+        );
+        let project = write(
+            &dir,
+            ".towl.toml",
+            "[llm]\nmodel = \"team\"\n\n[parsing]\nexclude_patterns = [\"gen/*\"]\n\n\
+             [profile.ci.llm]\nmax_retries = 0\n\n\
+             [profile.ci.parsing]\nexclude_patterns = [\"vendor/*\"]\n\n\
+             [profile.release.llm]\nmodel = \"careful\"\n",
+        );
+
+        let mut layered = Layered::resolve(Some(&global), &project).unwrap();
+        layered.select_profile(Some("ci")).unwrap();
+
+        assert_eq!(value(&layered, "llm.model").as_str(), Some("cheap"));
+        assert_eq!(value(&layered, "llm.max_retries").as_integer(), Some(0));
+        assert_eq!(
+            strings(value(&layered, "parsing.exclude_patterns")),
+            ["vendor/*"]
+        );
+        assert!(layered.table.get(PROFILE_KEY).is_none());
+        assert_eq!(
+            layered.origins.origin("llm.model"),
+            &Origin::Profile {
+                name: "ci".to_string(),
+                source: Box::new(Origin::File(global)),
+            }
+        );
+        assert_eq!(
+            layered.origins.origin("llm.max_retries").to_string(),
+            format!("profile:ci(file:{})", project.display())
+        );
+    }
+
+    #[test]
+    fn test_profiles_are_dropped_when_none_is_selected() {
+        let dir = TempDir::new().unwrap();
+        let project = write(
+            &dir,
+            ".towl.toml",
+            "[llm]\nmodel = \"team\"\n\n[profile.ci.llm]\nmodel = \"cheap\"\n",
+        );
+
+        let mut layered = Layered::resolve(None, &project).unwrap();
+        layered.select_profile(None).unwrap();
+
+        assert_eq!(value(&layered, "llm.model").as_str(), Some("team"));
+        assert!(layered.table.get(PROFILE_KEY).is_none());
+        assert!(!layered
+            .origins
+            .render(&TowlConfig::default())
+            .contains("profile:"));
+    }
+
+    #[rstest]
+    #[case::no_profiles("[llm]\nmodel = \"team\"\n", "none")]
+    #[case::other_profiles(
+        "[profile.release.llm]\nmodel = \"a\"\n\n[profile.ci.llm]\nmodel = \"b\"\n",
+        "ci, release"
+    )]
+    fn test_unknown_profile_is_an_error(#[case] content: &str, #[case] expected: &str) {
+        let dir = TempDir::new().unwrap();
+        let project = write(&dir, ".towl.toml", content);
+
+        let mut layered = Layered::resolve(None, &project).unwrap();
+        let result = layered.select_profile(Some("nightly"));
+
+        match result {
+            Err(TowlConfigError::UnknownProfile { name, available }) => {
+                assert_eq!(name, "nightly");
+                assert_eq!(available, expected);
+            }
+            other => panic!("expected UnknownProfile, got {other:?}"),
+        }
+    }
+
+    #[rstest]
+    #[case(ConfigLayer::Defaults, "built-in defaults")]
+    #[case(ConfigLayer::Global { path: PathBuf::from("/x/towl/config.toml"), found: false }, "global config /x/towl/config.toml (not found)")]
+    #[case(ConfigLayer::Project { path: PathBuf::from(".towl.toml"), found: true }, "project config .towl.toml")]
+    #[case(ConfigLayer::Profile("ci".to_string()), "profile ci")]
+    fn test_layer_display(#[case] layer: ConfigLayer, #[case] expected: &str) {
+        assert_eq!(layer.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("/a/./b/../c.toml", "/a/c.toml")]
+    #[case("/a/b/../../c.toml", "/c.toml")]
+    #[case("/../c.toml", "/c.toml")]
+    fn test_normalize(#[case] path: &str, #[case] expected: &str) {
+        assert_eq!(normalize(Path::new(path)), Path::new(expected));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_normalized_paths_pass_traversal_check(
+            parts in prop::collection::vec(prop_oneof!["[a-z]{1,8}", Just("..".to_string()), Just(".".to_string())], 0..10),
+        ) {
+            let path = PathBuf::from("/").join(parts.join("/"));
+            prop_assert!(TowlConfig::validate_path(&normalize(&path)).is_ok());
+        }
+    }
+}
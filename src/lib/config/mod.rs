@@ -1,21 +1,41 @@
 //! Configuration loading, validation, and initialisation.
 //!
-//! Configuration is read from a `.towl.toml` file (see [`DEFAULT_CONFIG_PATH`]) and
-//! can be overridden by environment variables (`TOWL_CONFIG`, `TOWL_GITHUB_TOKEN`,
+//! Configuration is read from a `.towl.toml` file (see [`DEFAULT_CONFIG_PATH`]),
+//! merged over any files it `extends` (see [`MergeConfig`]) and over the
+//! user-level config (see [`GLOBAL_CONFIG_PATH`]), optionally overlaid with a
+//! named [`ProfileConfig`], and can be overridden by environment variables (`TOWL_CONFIG`, `TOWL_PROFILE`, `TOWL_GITHUB_TOKEN`,
 //! `TOWL_GITHUB_OWNER`, `TOWL_GITHUB_REPO`, `TOWL_LLM_API_KEY`, `TOWL_LLM_PROVIDER`,
-//! `TOWL_LLM_MODEL`, `TOWL_LLM_BASE_URL`).
+//! `TOWL_LLM_MODEL`, `TOWL_LLM_BASE_URL`, and `TOWL__SECTION__KEY` for any key) and
+//! by [`ConfigOverride`]s given with `--set`. Secrets missing from the
+//! environment can come from a credential helper command (see
+//! [`CredentialProtocol`]).
 
+mod credentials;
 pub(crate) mod defaults;
+mod detect;
+mod diagnostics;
 mod display;
 pub mod error;
 pub mod git;
+mod layers;
 mod newtypes;
+mod overrides;
+mod schema;
 mod types;
 mod validation;
 
+pub use credentials::{CredentialProtocol, CREDENTIAL_HELPER_TIMEOUT_SECS};
+pub use detect::{DetectedLanguage, ProjectSurvey};
+pub use diagnostics::ConfigProblem;
+pub use layers::{ConfigLayer, ConfigOrigins, Origin};
 pub(crate) use newtypes::MAX_CONFIG_STRING_LENGTH;
 pub use newtypes::{Owner, Repo};
-pub use types::{GitHubConfig, LlmConfig, ParsingConfig, TowlConfig, DEFAULT_CONFIG_PATH};
+pub use overrides::ConfigOverride;
+pub use schema::SCHEMA_FILE_NAME;
+pub use types::{
+    BlockComment, DiscoveryMode, GitHubConfig, LanguageProfile, LlmConfig, LoadOptions,
+    MergeConfig, ParsingConfig, ProfileConfig, TowlConfig, DEFAULT_CONFIG_PATH, GLOBAL_CONFIG_PATH,
+};
 
 #[cfg(test)]
 pub use types::test_parsing_config;
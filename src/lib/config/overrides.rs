@@ -0,0 +1,361 @@
+use std::fmt;
+use std::str::FromStr;
+
+use config::{Environment, File, FileFormat, FileSourceString, Source};
+
+use super::error::TowlConfigError;
+use super::layers::{ConfigOrigins, Origin, SECTIONS};
+use super::schema;
+use super::types::TowlConfig;
+
+/// Prefix and separator of the generic environment variables, e.g.
+/// `TOWL__PARSING__INCLUDE_CONTEXT_LINES` for `parsing.include_context_lines`.
+const ENV_PREFIX: &str = "TOWL";
+const ENV_SEPARATOR: &str = "__";
+
+/// Environment variables that predate the generic `TOWL__*` convention,
+/// with the keys they set.
+const NAMED_ENV_VARS: [(&str, &str); 3] = [
+    ("TOWL_LLM_PROVIDER", "llm.provider"),
+    ("TOWL_LLM_MODEL", "llm.model"),
+    ("TOWL_LLM_BASE_URL", "llm.base_url"),
+];
+
+/// One `--set key=value` argument.
+///
+/// The key is a dotted path into `parsing`, `github` or `llm`, e.g.
+/// `parsing.include_context_lines`. The value is read as a TOML value
+/// (`5`, `true`, `["a/*", "b/*"]`, `{ extensions = ["tf"] }`), or taken as
+/// a plain string if it is not one, so `llm.model=gpt-4o` needs no quotes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigOverride {
+    pub key: String,
+    pub value: toml::Value,
+}
+
+impl FromStr for ConfigOverride {
+    type Err = TowlConfigError;
+
+    fn from_str(setting: &str) -> Result<Self, Self::Err> {
+        let invalid = |shown: &str, reason: &str| TowlConfigError::InvalidOverride {
+            setting: shown.to_string(),
+            reason: reason.to_string(),
+        };
+        let (key, raw) = setting
+            .split_once('=')
+            .ok_or_else(|| invalid(setting, "expected KEY=VALUE"))?;
+        let key = key.trim();
+        let well_formed = key.split('.').all(|segment| {
+            !segment.is_empty()
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        });
+        if !well_formed {
+            // the value may be a secret, so only the key is shown
+            return Err(invalid(
+                key,
+                "the key must be a dotted path such as llm.model",
+            ));
+        }
+        let value = toml::from_str::<toml::Table>(&format!("value = {raw}"))
+            .ok()
+            .and_then(|mut table| table.remove("value"))
+            .unwrap_or_else(|| toml::Value::String(raw.to_string()));
+        Ok(Self {
+            key: key.to_string(),
+            value,
+        })
+    }
+}
+
+impl fmt::Display for ConfigOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.key, self.value)
+    }
+}
+
+/// The `TOWL__SECTION__KEY` variables as a config source. Values are parsed
+/// as booleans or numbers where possible, and top-level lists are split on
+/// commas. `vars` replaces the process environment, for tests.
+pub(super) fn environment(vars: Option<config::Map<String, String>>) -> Environment {
+    let schema = TowlConfig::json_schema();
+    schema::list_keys(&schema, &SECTIONS)
+        .iter()
+        .fold(
+            Environment::with_prefix(ENV_PREFIX)
+                .prefix_separator(ENV_SEPARATOR)
+                .separator(ENV_SEPARATOR)
+                .try_parsing(true)
+                .list_separator(","),
+            |env, key| env.with_list_parse_key(key),
+        )
+        .source(vars)
+}
+
+/// The config sources applied over the config files, lowest precedence
+/// first: `TOWL__*` variables, the older named variables, then `overrides`.
+/// Records the origin of every key they set.
+///
+/// # Errors
+/// Returns `TowlConfigError::UnknownConfigKey` if a variable or override
+/// names a key the config does not have.
+pub(super) fn sources(
+    environment: Environment,
+    overrides: &[ConfigOverride],
+    origins: &mut ConfigOrigins,
+) -> Result<Vec<Box<dyn Source + Send + Sync>>, TowlConfigError> {
+    let schema = TowlConfig::json_schema();
+    for key in environment
+        .collect()
+        .map_err(TowlConfigError::CouldNotCreateConfig)?
+        .keys()
+    {
+        let var = env_var_name(key);
+        check_key(&schema, key, &var)?;
+        origins.set(key, Origin::Env(var));
+    }
+
+    let mut named = Vec::new();
+    for (var, key) in NAMED_ENV_VARS {
+        if let Ok(value) = std::env::var(var) {
+            named.push((key, toml::Value::String(value)));
+            origins.set(key, Origin::Env(var.to_string()));
+        }
+    }
+
+    Ok(vec![
+        Box::new(environment),
+        Box::new(table_source(named)?),
+        Box::new(override_source(&schema, overrides, origins)?),
+    ])
+}
+
+/// The `--set` overrides as a config source.
+fn override_source(
+    schema: &serde_json::Value,
+    overrides: &[ConfigOverride],
+    origins: &mut ConfigOrigins,
+) -> Result<File<FileSourceString, FileFormat>, TowlConfigError> {
+    for setting in overrides {
+        // the value may be a secret, so only the key is named
+        check_key(schema, &setting.key, "--set")?;
+        origins.clear(&setting.key);
+        origins.record(&setting.key, &setting.value, &Origin::CommandLine);
+    }
+    table_source(
+        overrides.iter().map(|o| (o.key.as_str(), o.value.clone())), // clone: the source owns its values
+    )
+}
+
+impl TowlConfig {
+    /// Applies `overrides` to this config, as `towl init --set` does before
+    /// writing it, and validates the result.
+    ///
+    /// # Errors
+    /// Returns `TowlConfigError` if an override names an unknown key or
+    /// gives a value of the wrong type, or the result fails validation.
+    pub fn with_overrides(self, overrides: &[ConfigOverride]) -> Result<Self, TowlConfigError> {
+        if overrides.is_empty() {
+            return Ok(self);
+        }
+        let base = toml::to_string(&self).map_err(TowlConfigError::UnableToParseToml)?;
+        let sources: Vec<Box<dyn Source + Send + Sync>> = vec![
+            Box::new(File::from_str(&base, FileFormat::Toml)),
+            Box::new(override_source(
+                &Self::json_schema(),
+                overrides,
+                &mut ConfigOrigins::default(),
+            )?),
+        ];
+        let config = Self::layered_over_defaults(sources)?;
+        Self::validate(&config)?;
+        Ok(config)
+    }
+}
+
+/// Rejects keys outside `parsing`, `github` and `llm` or unknown to the
+/// schema, naming `from` (the variable or argument) in the error.
+fn check_key(schema: &serde_json::Value, key: &str, from: &str) -> Result<(), TowlConfigError> {
+    let section = key.split('.').next().unwrap_or_default();
+    if SECTIONS.contains(&section) && schema::describes(schema, key) {
+        return Ok(());
+    }
+    Err(TowlConfigError::UnknownConfigKey {
+        key: key.to_string(),
+        from: from.to_string(),
+    })
+}
+
+/// The environment variable that sets `key` under the `TOWL__*` convention.
+fn env_var_name(key: &str) -> String {
+    format!(
+        "{ENV_PREFIX}{ENV_SEPARATOR}{}",
+        key.replace('.', ENV_SEPARATOR).to_ascii_uppercase()
+    )
+}
+
+/// A TOML document setting each dotted key to its value.
+fn table_source<'a>(
+    entries: impl IntoIterator<Item = (&'a str, toml::Value)>,
+) -> Result<File<FileSourceString, FileFormat>, TowlConfigError> {
+    let mut root = toml::Table::new();
+    for (key, value) in entries {
+        let (parents, last) = key.rsplit_once('.').unwrap_or(("", key));
+        let mut table = &mut root;
+        for segment in parents.split('.').filter(|s| !s.is_empty()) {
+            let entry = table
+                .entry(segment)
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            if !entry.is_table() {
+                *entry = toml::Value::Table(toml::Table::new());
+            }
+            let toml::Value::Table(inner) = entry else {
+                unreachable!("replaced by a table above");
+            };
+            table = inner;
+        }
+        table.insert(last.to_string(), value);
+    }
+    let text = toml::to_string(&root).map_err(TowlConfigError::UnableToParseToml)?;
+    Ok(File::from_str(&text, FileFormat::Toml))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn env(vars: &[(&str, &str)]) -> Environment {
+        environment(Some(
+            vars.iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+        ))
+    }
+
+    fn load(
+        vars: &[(&str, &str)],
+        overrides: &[&str],
+    ) -> Result<(TowlConfig, ConfigOrigins), TowlConfigError> {
+        let overrides: Vec<ConfigOverride> = overrides.iter().map(|s| s.parse().unwrap()).collect();
+        let mut origins = ConfigOrigins::default();
+        let sources = sources(env(vars), &overrides, &mut origins)?;
+        let config = TowlConfig::layered_over_defaults(sources)?;
+        TowlConfig::validate(&config)?;
+        Ok((config, origins))
+    }
+
+    #[rstest]
+    #[case("parsing.include_context_lines=5", toml::Value::Integer(5))]
+    #[case("parsing.follow_symlinks=true", toml::Value::Boolean(true))]
+    #[case("llm.model=gpt-4o", toml::Value::String("gpt-4o".to_string()))]
+    #[case("llm.model=\"4\"", toml::Value::String("4".to_string()))]
+    #[case("llm.base_url=", toml::Value::String(String::new()))]
+    #[case(
+        "parsing.exclude_patterns=[\"a/*\"]",
+        toml::Value::Array(vec![toml::Value::String("a/*".to_string())])
+    )]
+    fn test_parse_override_value(#[case] setting: &str, #[case] expected: toml::Value) {
+        let parsed: ConfigOverride = setting.parse().unwrap();
+        assert_eq!(parsed.value, expected);
+    }
+
+    #[rstest]
+    #[case::no_equals("parsing.include_context_lines")]
+    #[case::empty_key("=5")]
+    #[case::empty_segment("parsing..x=5")]
+    #[case::bad_character("llm.mo del=x")]
+    fn test_malformed_override_is_rejected(#[case] setting: &str) {
+        assert!(matches!(
+            setting.parse::<ConfigOverride>(),
+            Err(TowlConfigError::InvalidOverride { .. })
+        ));
+    }
+
+    #[test]
+    fn test_environment_and_overrides_apply_in_order() {
+        let (config, origins) = load(
+            &[
+                ("TOWL__PARSING__INCLUDE_CONTEXT_LINES", "4"),
+                ("TOWL__LLM__MAX_CONCURRENT_ANALYSES", "10"),
+                ("TOWL__PARSING__EXCLUDE_PATTERNS", "vendor/*,gen/*"),
+                ("TOWL_GITHUB_TOKEN", "ignored"),
+            ],
+            &["parsing.include_context_lines=5"],
+        )
+        .unwrap();
+
+        assert_eq!(config.parsing.include_context_lines, 5);
+        assert_eq!(config.llm.max_concurrent_analyses, 10);
+        assert_eq!(config.parsing.exclude_patterns, ["vendor/*", "gen/*"]);
+        assert_eq!(
+            origins.origin("parsing.include_context_lines"),
+            &Origin::CommandLine
+        );
+        assert_eq!(
+            origins.origin("llm.max_concurrent_analyses").to_string(),
+            "env:TOWL__LLM__MAX_CONCURRENT_ANALYSES"
+        );
+    }
+
+    #[rstest]
+    #[case::env(&[("TOWL__LLM__MODLE", "x")], &[], "TOWL__LLM__MODLE")]
+    #[case::env_only_field(&[("TOWL__GITHUB__TOKEN", "x")], &[], "TOWL__GITHUB__TOKEN")]
+    #[case::set(&[], &["parsing.context=3"], "--set")]
+    #[case::meta_key(&[], &["extends=[]"], "--set")]
+    fn test_unknown_key_is_rejected(
+        #[case] vars: &[(&str, &str)],
+        #[case] overrides: &[&str],
+        #[case] expected_from: &str,
+    ) {
+        match load(vars, overrides) {
+            Err(TowlConfigError::UnknownConfigKey { from, .. }) => assert_eq!(from, expected_from),
+            other => panic!("expected UnknownConfigKey, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_rejected_override_does_not_echo_value() {
+        let unknown = load(&[], &["github.token=ghp_secret"]).unwrap_err();
+        assert!(matches!(
+            &unknown,
+            TowlConfigError::UnknownConfigKey { key, .. } if key == "github.token"
+        ));
+        let malformed = "github token=ghp_secret"
+            .parse::<ConfigOverride>()
+            .unwrap_err();
+        for error in [unknown, malformed] {
+            assert!(!error.to_string().contains("ghp_secret"), "{error}");
+        }
+    }
+
+    #[rstest]
+    #[case::wrong_type(&["parsing.include_context_lines=many"])]
+    #[case::out_of_range(&["llm.max_concurrent_analyses=99"])]
+    fn test_overrides_are_validated(#[case] overrides: &[&str]) {
+        assert!(load(&[], overrides).is_err());
+    }
+
+    #[test]
+    fn test_nested_override_sets_language_profile() {
+        let (config, _) = load(
+            &[],
+            &["parsing.languages.terraform={ extensions = [\"tf\"] }"],
+        )
+        .unwrap();
+
+        assert_eq!(config.parsing.languages["terraform"].extensions, ["tf"]);
+        assert!(config.parsing.languages.contains_key("rust"));
+    }
+
+    #[test]
+    fn test_with_overrides_changes_only_named_keys() {
+        let overrides = vec!["llm.max_retries=1".parse().unwrap()];
+
+        let config = TowlConfig::default().with_overrides(&overrides).unwrap();
+
+        assert_eq!(config.llm.max_retries, 1);
+        assert_eq!(config.parsing, TowlConfig::default().parsing);
+    }
+}
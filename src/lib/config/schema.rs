@@ -0,0 +1,161 @@
+use schemars::schema_for;
+use serde_json::Value;
+
+use super::types::TowlConfig;
+
+/// Repository-relative path of the checked-in schema, referenced from
+/// `.towl.toml` files and editor settings.
+pub const SCHEMA_FILE_NAME: &str = "towl.schema.json";
+
+impl TowlConfig {
+    /// The JSON Schema (draft-07) describing `.towl.toml`, generated from the
+    /// config types. Fields read only from the environment (tokens, owner and
+    /// repo) are not part of it.
+    #[must_use]
+    pub fn json_schema() -> Value {
+        let mut schema = serde_json::to_value(schema_for!(Self)).unwrap_or_default();
+        if let Some(root) = schema.as_object_mut() {
+            root.insert(
+                "title".to_string(),
+                Value::String("towl configuration".to_string()),
+            );
+        }
+        schema
+    }
+}
+
+/// Follows `$ref`, single-entry `allOf` and optional (`anyOf` with `null`)
+/// wrappers to the schema they name.
+pub(super) fn resolve<'a>(schema: &'a Value, root: &'a Value) -> &'a Value {
+    if let Some(name) = schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|r| r.strip_prefix("#/definitions/"))
+    {
+        if let Some(target) = root.get("definitions").and_then(|d| d.get(name)) {
+            return resolve(target, root);
+        }
+    }
+    if let Some(all) = schema.get("allOf").and_then(Value::as_array) {
+        if all.len() == 1 {
+            return resolve(&all[0], root);
+        }
+    }
+    let non_null: Vec<&Value> = schema
+        .get("anyOf")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|branch| branch.get("type").and_then(Value::as_str) != Some("null"))
+        .collect();
+    match non_null.as_slice() {
+        [only] => resolve(only, root),
+        _ => schema,
+    }
+}
+
+/// Whether `schema` describes the dotted `key`, following map entries such
+/// as `parsing.languages.<name>`.
+pub(super) fn describes(schema: &Value, key: &str) -> bool {
+    let mut node = schema;
+    for segment in key.split('.') {
+        let parent = resolve(node, schema);
+        let child = parent
+            .get("properties")
+            .and_then(|p| p.get(segment))
+            .or_else(|| parent.get("additionalProperties").filter(|a| a.is_object()));
+        match child {
+            Some(child) => node = child,
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Dotted keys of the list fields directly inside each of `sections`.
+pub(super) fn list_keys(schema: &Value, sections: &[&str]) -> Vec<String> {
+    let mut keys = Vec::new();
+    for section in sections {
+        let Some(properties) = schema["properties"]
+            .get(section)
+            .and_then(|s| resolve(s, schema).get("properties"))
+            .and_then(Value::as_object)
+        else {
+            continue;
+        };
+        for (name, property) in properties {
+            let kind = &resolve(property, schema)["type"];
+            let is_list = kind == "array"
+                || kind
+                    .as_array()
+                    .is_some_and(|kinds| kinds.iter().any(|k| k == "array"));
+            if is_list {
+                keys.push(format!("{section}.{name}"));
+            }
+        }
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn test_checked_in_schema_is_current() {
+        let checked_in: Value =
+            serde_json::from_str(include_str!("../../../towl.schema.json")).unwrap();
+        assert_eq!(
+            checked_in,
+            TowlConfig::json_schema(),
+            "towl.schema.json is stale; regenerate with `towl config schema > towl.schema.json`"
+        );
+    }
+
+    #[rstest]
+    #[case(&["parsing", "include_context_lines"], "integer")]
+    #[case(&["parsing", "follow_symlinks"], "boolean")]
+    #[case(&["github", "rate_limit_delay_ms"], "integer")]
+    #[case(&["llm", "model"], "string")]
+    #[case(&["profile", "ci", "llm", "max_retries"], "integer")]
+    fn test_schema_describes_fields(#[case] path: &[&str], #[case] expected_type: &str) {
+        let schema = TowlConfig::json_schema();
+        let mut node = &schema;
+        for key in path {
+            let parent = resolve(node, &schema);
+            node = parent["properties"]
+                .get(key)
+                .unwrap_or(&parent["additionalProperties"]);
+        }
+        assert_eq!(resolve(node, &schema)["type"], expected_type);
+    }
+
+    #[rstest]
+    #[case("parsing.include_context_lines", true)]
+    #[case("parsing.languages.go.extensions", true)]
+    #[case("llm.args", true)]
+    #[case("github.token", false)]
+    #[case("parsing.include_context_lines.x", false)]
+    fn test_describes(#[case] key: &str, #[case] expected: bool) {
+        assert_eq!(describes(&TowlConfig::json_schema(), key), expected);
+    }
+
+    #[test]
+    fn test_list_keys() {
+        let keys = list_keys(&TowlConfig::json_schema(), &["parsing", "llm"]);
+        assert!(keys.contains(&"parsing.exclude_patterns".to_string()));
+        assert!(keys.contains(&"llm.args".to_string()));
+        assert!(!keys.contains(&"parsing.languages".to_string()));
+        assert!(!keys.contains(&"llm.model".to_string()));
+    }
+
+    #[test]
+    fn test_schema_omits_environment_only_fields() {
+        let schema = TowlConfig::json_schema();
+        let github = resolve(&schema["properties"]["github"], &schema);
+        for key in ["token", "owner", "repo"] {
+            assert!(github["properties"].get(key).is_none(), "{key}");
+        }
+    }
+}
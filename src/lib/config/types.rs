@@ -1,33 +1,55 @@
+use super::credentials::CredentialProtocol;
 use super::defaults::{
     default_comment_prefixes, default_exclude_patterns, default_file_extensions,
-    default_function_patterns, default_include_context_lines, default_llm_max_retries,
+    default_function_patterns, default_github_host, default_github_remote,
+    default_include_context_lines, default_languages, default_llm_max_retries,
     default_llm_max_tokens, default_llm_model, default_llm_provider, default_max_analyse_count,
     default_max_concurrent_analyses, default_rate_limit_delay_ms, default_todo_patterns,
 };
 use super::error::TowlConfigError;
 use super::git::GitRepoInfo;
+use super::layers::{ConfigLayer, ConfigOrigins, Layered};
 use super::newtypes::{Owner, Repo};
-use config::{Config as ConfigBuilder, File};
+use super::overrides::{self, ConfigOverride};
+use config::{Config as ConfigBuilder, File, FileFormat};
+use schemars::JsonSchema;
 use secrecy::SecretString;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fmt;
 use std::path::{Path, PathBuf};
 
 pub const DEFAULT_CONFIG_PATH: &str = ".towl.toml";
 
+/// Location of the user-level config below the XDG config directory.
+pub const GLOBAL_CONFIG_PATH: &str = "towl/config.toml";
+
 /// Root configuration combining parsing rules and GitHub settings.
 ///
 /// Load from a `.towl.toml` file with [`TowlConfig::load`], or create a new
 /// config file with [`TowlConfig::init`].
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
 pub struct TowlConfig {
+    /// Config files this one inherits from, relative to this file. Later
+    /// entries override earlier ones and this file overrides them all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extends: Vec<PathBuf>,
+    /// How this file's lists combine with inherited ones.
+    #[serde(default, skip_serializing_if = "MergeConfig::is_empty")]
+    pub merge: MergeConfig,
+    /// Which files are scanned and how TODOs are recognised.
     #[serde(default)]
     pub parsing: ParsingConfig,
+    /// GitHub issue creation.
     #[serde(default)]
     pub github: GitHubConfig,
+    /// AI analysis of TODOs.
     #[serde(default)]
     pub llm: LlmConfig,
+    /// Named overlays selected with `--profile` or `TOWL_PROFILE`; see
+    /// [`ProfileConfig`]. Removed once loaded.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profile: BTreeMap<String, ProfileConfig>,
 }
 
 impl TowlConfig {
@@ -38,9 +60,21 @@ impl TowlConfig {
     /// Returns `TowlConfigError` if the git repo cannot be found, the merged
     /// config fails validation, or the file cannot be written.
     pub async fn init(path: &Path, force: bool) -> Result<(), TowlConfigError> {
-        Self::validate_path(path)?;
+        Self::init_from(path, force, Self::default()).await
+    }
 
-        GitRepoInfo::from_path(".").await?;
+    /// Like [`TowlConfig::init`], writing `proposed` (e.g. from
+    /// [`ProjectSurvey::to_config`](super::ProjectSurvey::to_config)) instead
+    /// of the defaults. An existing file is still kept unless `force` is set.
+    ///
+    /// # Errors
+    /// Returns `TowlConfigError` as for [`TowlConfig::init`].
+    pub async fn init_from(
+        path: &Path,
+        force: bool,
+        proposed: Self,
+    ) -> Result<(), TowlConfigError> {
+        Self::validate_path(path)?;
 
         let config = if !force && path.exists() {
             let existing = tokio::fs::read_to_string(path)
@@ -50,10 +84,11 @@ impl TowlConfig {
                 TowlConfigError::CouldNotCreateConfig(config::ConfigError::Foreign(Box::new(e)))
             })?
         } else {
-            Self::default()
+            proposed
         };
 
         Self::validate(&config)?;
+        GitRepoInfo::from_path(".", &config.github).await?;
 
         let toml_string =
             toml::to_string_pretty(&config).map_err(TowlConfigError::UnableToParseToml)?;
@@ -72,26 +107,59 @@ impl TowlConfig {
     /// # Errors
     /// Returns `TowlConfigError` if the config file is malformed or cannot be parsed.
     pub fn load(path: Option<&PathBuf>) -> Result<Self, TowlConfigError> {
-        let env_path = std::env::var("TOWL_CONFIG").ok().map(PathBuf::from);
-        let default_path = PathBuf::from(DEFAULT_CONFIG_PATH);
-        let config_path = path.or(env_path.as_ref()).unwrap_or(&default_path);
-        Self::validate_path(config_path)?;
-
-        let mut builder = ConfigBuilder::builder().add_source(
-            config::Config::try_from(&Self::default())
-                .map_err(TowlConfigError::CouldNotCreateConfig)?,
-        );
-
-        builder = builder.add_source(File::from(config_path.as_path()).required(false));
+        Self::load_with(&LoadOptions {
+            path: path.cloned(), // clone: options own the path
+            ..LoadOptions::default()
+        })
+    }
 
-        let built: config::Config = builder.build().map_err(|e| {
-            tracing::error!("Config build error: {:?}", e);
-            TowlConfigError::CouldNotCreateConfig(e)
-        })?;
+    /// Like [`TowlConfig::load`], also applying a profile and `--set`
+    /// overrides; see [`LoadOptions`].
+    ///
+    /// # Errors
+    /// Returns `TowlConfigError::UnknownProfile` if no config file defines
+    /// the profile, `TowlConfigError::UnknownConfigKey` if an override names
+    /// a key the config does not have, and otherwise as for
+    /// [`TowlConfig::load`].
+    pub fn load_with(options: &LoadOptions) -> Result<Self, TowlConfigError> {
+        Self::load_with_origins(options).map(|(config, _)| config)
+    }
 
-        let mut config: Self = built.try_deserialize().map_err(|e| {
-            tracing::error!("Config deserialization error: {:?}", e);
-            TowlConfigError::CouldNotCreateConfig(e)
+    /// Like [`TowlConfig::load_with`], also returning which file, profile,
+    /// environment variable or override set each value.
+    ///
+    /// Layers apply in the order of [`TowlConfig::layers`]: defaults, the
+    /// user-level config from [`TowlConfig::global_path`], the project config,
+    /// the selected profile, environment variables, then `--set` overrides.
+    /// Each file is merged over the files it `extends`, resolved relative to
+    /// it, depth first; see [`MergeConfig`] for how values combine.
+    ///
+    /// # Errors
+    /// Returns `TowlConfigError` if a file in the chain is malformed, missing
+    /// (other than the config file itself) or extends itself, if the
+    /// selected profile is not defined, or if an environment variable or
+    /// override names an unknown key or gives a value of the wrong type.
+    pub fn load_with_origins(
+        options: &LoadOptions,
+    ) -> Result<(Self, ConfigOrigins), TowlConfigError> {
+        let config_path = Self::resolve_path(options.path.as_ref());
+        Self::validate_path(&config_path)?;
+
+        let global = Self::global_path();
+        let mut layered = Layered::resolve(global.as_deref(), &config_path)?;
+        layered.select_profile(Self::resolve_profile(options.profile.as_deref()).as_deref())?;
+        let Layered { table, mut origins } = layered;
+        let merged = toml::to_string(&table).map_err(TowlConfigError::UnableToParseToml)?;
+        let mut sources: Vec<Box<dyn config::Source + Send + Sync>> =
+            vec![Box::new(File::from_str(&merged, FileFormat::Toml))];
+        sources.extend(overrides::sources(
+            overrides::environment(None),
+            &options.overrides,
+            &mut origins,
+        )?);
+        let mut config = Self::layered_over_defaults(sources).map_err(|e| {
+            tracing::error!("Config load error: {:?}", e);
+            e
         })?;
 
         if let Ok(token) = std::env::var("TOWL_GITHUB_TOKEN") {
@@ -101,8 +169,10 @@ impl TowlConfig {
 
         let owner_env = std::env::var("TOWL_GITHUB_OWNER").ok();
         let repo_env = std::env::var("TOWL_GITHUB_REPO").ok();
+        // The remote name is passed to git, so check it first
+        Self::validate_github_endpoints(&config.github)?;
         let git_info = if owner_env.is_none() || repo_env.is_none() {
-            GitRepoInfo::from_path_sync(".").ok()
+            GitRepoInfo::from_path_sync(".", &config.github).ok()
         } else {
             None
         };
@@ -121,37 +191,209 @@ impl TowlConfig {
             Self::check_string_length("TOWL_LLM_API_KEY", &key)?;
             config.llm.api_key = SecretString::from(key);
         }
-        if let Ok(provider) = std::env::var("TOWL_LLM_PROVIDER") {
-            config.llm.provider = provider;
+        Self::validate(&config)?;
+
+        Ok((config, origins))
+    }
+
+    /// The config file to use: `path` if given, else `TOWL_CONFIG`, else
+    /// [`DEFAULT_CONFIG_PATH`].
+    #[must_use]
+    pub fn resolve_path(path: Option<&PathBuf>) -> PathBuf {
+        path.cloned() // clone: caller keeps its path
+            .or_else(|| std::env::var("TOWL_CONFIG").ok().map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH))
+    }
+
+    /// The profile to apply: `profile` if given, else a non-empty
+    /// `TOWL_PROFILE`, else none.
+    #[must_use]
+    pub fn resolve_profile(profile: Option<&str>) -> Option<String> {
+        profile
+            .map(str::to_string)
+            .or_else(|| std::env::var("TOWL_PROFILE").ok())
+            .filter(|name| !name.is_empty())
+    }
+
+    /// The user-level config file: `$XDG_CONFIG_HOME/towl/config.toml`, or
+    /// `~/.config/towl/config.toml` when `XDG_CONFIG_HOME` is unset or not
+    /// absolute. `None` if neither variable is usable.
+    #[must_use]
+    pub fn global_path() -> Option<PathBuf> {
+        let non_empty = |var: &str| std::env::var_os(var).filter(|v| !v.is_empty());
+        let base = non_empty("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute())
+            .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(base.join(GLOBAL_CONFIG_PATH))
+    }
+
+    /// The layers [`TowlConfig::load_with`] reads for `options`, lowest
+    /// precedence first. Files are marked if they do not exist.
+    #[must_use]
+    pub fn layers(options: &LoadOptions) -> Vec<ConfigLayer> {
+        let project = Self::resolve_path(options.path.as_ref());
+        let mut layers = vec![ConfigLayer::Defaults];
+        if let Some(global) = Self::global_path() {
+            layers.push(ConfigLayer::Global {
+                found: global.is_file(),
+                path: global,
+            });
         }
-        if let Ok(model) = std::env::var("TOWL_LLM_MODEL") {
-            config.llm.model = model;
+        layers.push(ConfigLayer::Project {
+            found: project.is_file(),
+            path: project,
+        });
+        if let Some(name) = Self::resolve_profile(options.profile.as_deref()) {
+            layers.push(ConfigLayer::Profile(name));
         }
-        if let Ok(url) = std::env::var("TOWL_LLM_BASE_URL") {
-            config.llm.base_url = Some(url);
+        layers.push(ConfigLayer::Environment);
+        if !options.overrides.is_empty() {
+            layers.push(ConfigLayer::CommandLine);
         }
+        layers
+    }
 
-        Self::validate(&config)?;
+    /// Deserialises `source` merged over the built-in defaults, field by
+    /// field, without environment overrides or validation.
+    pub(crate) fn layered_over_defaults<S>(source: S) -> Result<Self, TowlConfigError>
+    where
+        S: config::Source + Send + Sync + 'static,
+    {
+        ConfigBuilder::builder()
+            .add_source(
+                config::Config::try_from(&Self::default())
+                    .map_err(TowlConfigError::CouldNotCreateConfig)?,
+            )
+            .add_source(source)
+            .build()
+            .and_then(config::Config::try_deserialize)
+            .map_err(TowlConfigError::CouldNotCreateConfig)
+    }
 
-        Ok(config)
+    /// Replaces `parsing.include_patterns` (e.g. from `--include`) and
+    /// re-validates the result. An empty list leaves the loaded value intact.
+    ///
+    /// # Errors
+    /// Returns `TowlConfigError` if the patterns exceed the configured limits.
+    pub fn override_include_patterns(
+        &mut self,
+        patterns: Vec<String>,
+    ) -> Result<(), TowlConfigError> {
+        if patterns.is_empty() {
+            return Ok(());
+        }
+        self.parsing.include_patterns = patterns;
+        Self::validate(self)
+    }
+}
+
+/// What [`TowlConfig::load_with`] reads besides the config files.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadOptions {
+    /// Project config file; see [`TowlConfig::resolve_path`].
+    pub path: Option<PathBuf>,
+    /// Profile to apply; see [`TowlConfig::resolve_profile`].
+    pub profile: Option<String>,
+    /// `--set` overrides, applied over everything else.
+    pub overrides: Vec<ConfigOverride>,
+}
+
+/// Merge rules for a file that `extends` others.
+///
+/// Tables are merged key by key and scalars override inherited values.
+/// Lists replace the inherited list unless their dotted key (e.g.
+/// `parsing.exclude_patterns`) is in `append`, in which case this file's
+/// entries are added after the inherited ones (or after the built-in
+/// defaults, if no inherited file sets the list).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct MergeConfig {
+    /// Dotted keys of lists that extend the inherited list instead of replacing it.
+    #[serde(default)]
+    pub append: Vec<String>,
+}
+
+impl MergeConfig {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.append.is_empty()
     }
 }
 
+/// A named overlay of the base config, written as `[profile.<name>.parsing]`,
+/// `[profile.<name>.github]` and `[profile.<name>.llm]` tables.
+///
+/// Only the keys a profile sets override the base config; lists replace the
+/// base list. The full section types describe what a profile may contain,
+/// for the schema and `towl config validate`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct ProfileConfig {
+    /// Overrides for `parsing`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parsing: Option<ParsingConfig>,
+    /// Overrides for `github`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github: Option<GitHubConfig>,
+    /// Overrides for `llm`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llm: Option<LlmConfig>,
+}
+
+/// Serializes a set in sorted order, so written configs and the generated
+/// schema do not change from run to run.
+fn serialize_sorted<S: serde::Serializer>(
+    set: &HashSet<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let sorted: std::collections::BTreeSet<&String> = set.iter().collect();
+    serializer.collect_seq(sorted)
+}
+
 /// Controls which files to scan, what patterns to match, and how much context to capture.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 pub struct ParsingConfig {
-    #[serde(default = "default_file_extensions")]
+    /// File extensions to scan, without the leading dot.
+    #[serde(
+        default = "default_file_extensions",
+        serialize_with = "serialize_sorted"
+    )]
     pub file_extensions: HashSet<String>,
+    /// Globs of paths to skip, relative to the scan root.
     #[serde(default = "default_exclude_patterns")]
     pub exclude_patterns: Vec<String>,
+    /// When non-empty, only files matching at least one of these globs are
+    /// scanned. Never overrides gitignore rules or `exclude_patterns`.
+    #[serde(default)]
+    pub include_patterns: Vec<String>,
+    /// Number of surrounding lines captured with each TODO (1-50).
     #[serde(default = "default_include_context_lines")]
     pub include_context_lines: usize,
+    /// Regexes that mark a line as a comment, for files without a language profile.
     #[serde(default = "default_comment_prefixes")]
     pub comment_prefixes: Vec<String>,
+    /// Regexes that find TODO markers; the first capture group is the description.
     #[serde(default = "default_todo_patterns")]
     pub todo_patterns: Vec<String>,
+    /// Regexes that find the enclosing function of a TODO.
     #[serde(default = "default_function_patterns")]
     pub function_patterns: Vec<String>,
+    /// Per-language file matching and comment syntax, keyed by language name.
+    #[serde(default = "default_languages")]
+    pub languages: BTreeMap<String, LanguageProfile>,
+    /// How candidate files are found; see [`DiscoveryMode`].
+    #[serde(default)]
+    pub discovery: DiscoveryMode,
+    /// Follow symbolic links to files and directories. Link cycles are
+    /// skipped and a file reachable through several links is scanned once.
+    #[serde(default)]
+    pub follow_symlinks: bool,
+    /// Let followed links lead outside the scan root. Off by default.
+    #[serde(default)]
+    pub allow_symlinks_outside_root: bool,
+    /// Also report code placeholders such as `todo!()`, using each language's
+    /// `placeholder_patterns`.
+    #[serde(default)]
+    pub detect_placeholders: bool,
 }
 
 impl Default for ParsingConfig {
@@ -159,19 +401,114 @@ impl Default for ParsingConfig {
         Self {
             file_extensions: default_file_extensions(),
             exclude_patterns: default_exclude_patterns(),
+            include_patterns: Vec::new(),
             include_context_lines: default_include_context_lines(),
             comment_prefixes: default_comment_prefixes(),
             todo_patterns: default_todo_patterns(),
             function_patterns: default_function_patterns(),
+            languages: default_languages(),
+            discovery: DiscoveryMode::default(),
+            follow_symlinks: false,
+            allow_symlinks_outside_root: false,
+            detect_placeholders: false,
         }
     }
 }
 
+/// Where the scanner gets its candidate file list from.
+///
+/// The git modes list files with `git ls-files` instead of walking the tree,
+/// so the file set is exactly what git tracks. They fall back to
+/// [`DiscoveryMode::Walk`] when the scan path is not inside a git work tree.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, clap::ValueEnum,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum DiscoveryMode {
+    /// Walk the directory tree, honouring `.gitignore` rules.
+    #[default]
+    Walk,
+    /// Files tracked by git.
+    Git,
+    /// Files tracked by git plus untracked files that are not ignored.
+    GitUntracked,
+}
+
+impl fmt::Display for DiscoveryMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Walk => write!(f, "walk"),
+            Self::Git => write!(f, "git"),
+            Self::GitUntracked => write!(f, "git-untracked"),
+        }
+    }
+}
+
+/// How to recognise files of one language and which comment syntax they use.
+///
+/// `extensions` only selects the comment syntax; whether an extension is
+/// scanned at all is still decided by [`ParsingConfig::file_extensions`].
+/// Files matching `filenames` (globs on the file name, e.g. `Dockerfile.*`)
+/// and extensionless files whose shebang names one of `interpreters` are
+/// scanned regardless of extension. An empty `comment_prefixes` falls back
+/// to [`ParsingConfig::comment_prefixes`].
+///
+/// `block_comments` lists multi-line comment delimiters; every line that a
+/// block comment touches counts as a comment line, so a TODO inside a
+/// `/* ... */` body or a Python docstring is found without a line prefix.
+/// `line_comments` are the literal tokens that start a comment running to
+/// the end of the line, such as `//` or `#`; a delimiter after one, as in
+/// `// see target/*`, does not open a block comment.
+///
+/// `placeholder_patterns` match unfinished code outside comments (e.g.
+/// `todo!()`) when [`ParsingConfig::detect_placeholders`] is set. The first
+/// capture group, if it matched a string literal, becomes the description.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct LanguageProfile {
+    /// Extensions that use this language's comment syntax.
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    /// Globs on the file name; matching files are always scanned.
+    #[serde(default)]
+    pub filenames: Vec<String>,
+    /// Shebang interpreters; extensionless files naming one are always scanned.
+    #[serde(default)]
+    pub interpreters: Vec<String>,
+    /// Comment regexes for this language; empty falls back to `parsing.comment_prefixes`.
+    #[serde(default)]
+    pub comment_prefixes: Vec<String>,
+    /// Multi-line comment delimiters.
+    #[serde(default)]
+    pub block_comments: Vec<BlockComment>,
+    /// Literal tokens that start a comment running to the end of the line.
+    #[serde(default)]
+    pub line_comments: Vec<String>,
+    /// Regexes for unfinished code, used when `detect_placeholders` is set.
+    #[serde(default)]
+    pub placeholder_patterns: Vec<String>,
+}
+
+/// Literal start and end delimiters of a multi-line comment, e.g. `/*` and
+/// `*/`. With `nested`, inner `start` delimiters open a further level that
+/// needs its own `end` (Rust, Haskell, OCaml). Delimiters may be equal, as
+/// for Python's `"""` docstrings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct BlockComment {
+    /// Literal text that opens the comment.
+    pub start: String,
+    /// Literal text that closes the comment.
+    pub end: String,
+    /// Whether inner `start` delimiters open a further level.
+    #[serde(default)]
+    pub nested: bool,
+}
+
 /// GitHub integration settings for issue creation.
 ///
-/// The token is loaded from the `TOWL_GITHUB_TOKEN` environment variable (never
-/// serialised to disk). Owner and repo can be auto-detected from the git remote.
-#[derive(Clone, Serialize, Deserialize)]
+/// The token is loaded from the `TOWL_GITHUB_TOKEN` environment variable or,
+/// failing that, from `token_command` (never serialised to disk). Owner and repo can be auto-detected from the git remote
+/// named by `remote`, which must point at `host`.
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GitHubConfig {
     #[serde(skip)]
     pub token: SecretString,
@@ -179,8 +516,48 @@ pub struct GitHubConfig {
     pub owner: Owner,
     #[serde(skip)]
     pub repo: Repo,
+    /// Delay between issue creation requests, in milliseconds (max 60000).
     #[serde(default = "default_rate_limit_delay_ms")]
     pub rate_limit_delay_ms: u64,
+    /// Web host of the GitHub instance, optionally with a port, e.g.
+    /// `github.example.com` for GitHub Enterprise Server. Links point here and
+    /// the git remote must be on this host.
+    #[serde(default = "default_github_host")]
+    pub host: String,
+    /// REST API base URL. Defaults to `https://api.github.com` for
+    /// `github.com` and `https://<host>/api/v3` otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+    /// Git remote that owner and repo are detected from.
+    #[serde(default = "default_github_remote")]
+    pub remote: String,
+    /// Command that produces the token when `TOWL_GITHUB_TOKEN` is unset,
+    /// e.g. `["gh", "auth", "token"]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_command: Option<Vec<String>>,
+    /// How `token_command` returns the token.
+    #[serde(default, skip_serializing_if = "CredentialProtocol::is_plain")]
+    pub token_protocol: CredentialProtocol,
+}
+
+impl GitHubConfig {
+    /// Base URL of the web interface, e.g. `https://github.com`.
+    #[must_use]
+    pub fn web_url(&self) -> String {
+        format!("https://{}", self.host)
+    }
+
+    /// Base URL of the REST API: `api_url` if set, else derived from `host`.
+    #[must_use]
+    pub fn api_base_url(&self) -> String {
+        match &self.api_url {
+            Some(url) => url.clone(), // clone: caller owns the URL
+            None if self.host.eq_ignore_ascii_case("github.com") => {
+                "https://api.github.com".to_string()
+            }
+            None => format!("https://{}/api/v3", self.host),
+        }
+    }
 }
 
 impl Default for GitHubConfig {
@@ -190,6 +567,11 @@ impl Default for GitHubConfig {
             owner: Owner::default(),
             repo: Repo::default(),
             rate_limit_delay_ms: default_rate_limit_delay_ms(),
+            host: default_github_host(),
+            api_url: None,
+            remote: default_github_remote(),
+            token_command: None,
+            token_protocol: CredentialProtocol::default(),
         }
     }
 }
@@ -201,6 +583,11 @@ impl fmt::Debug for GitHubConfig {
             .field("owner", &self.owner)
             .field("repo", &self.repo)
             .field("rate_limit_delay_ms", &self.rate_limit_delay_ms)
+            .field("host", &self.host)
+            .field("api_url", &self.api_url)
+            .field("remote", &self.remote)
+            .field("token_command", &self.token_command)
+            .field("token_protocol", &self.token_protocol)
             .finish()
     }
 }
@@ -210,6 +597,11 @@ impl PartialEq for GitHubConfig {
         self.owner == other.owner
             && self.repo == other.repo
             && self.rate_limit_delay_ms == other.rate_limit_delay_ms
+            && self.host == other.host
+            && self.api_url == other.api_url
+            && self.remote == other.remote
+            && self.token_command == other.token_command
+            && self.token_protocol == other.token_protocol
     }
 }
 
@@ -217,28 +609,45 @@ impl Eq for GitHubConfig {}
 
 /// LLM configuration for AI-powered TODO validation.
 ///
-/// API key is loaded from `TOWL_LLM_API_KEY` (never serialised to disk).
+/// API key is loaded from `TOWL_LLM_API_KEY` or, failing that, from
+/// `api_key_command` (never serialised to disk).
 /// Provider and model can be overridden via environment variables.
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LlmConfig {
+    /// LLM provider: `claude`, `openai`, `claude-code` or `codex`.
     #[serde(default = "default_llm_provider")]
     pub provider: String,
+    /// Model identifier passed to the provider.
     #[serde(default = "default_llm_model")]
     pub model: String,
+    /// Override for the provider's API base URL.
     #[serde(default)]
     pub base_url: Option<String>,
     #[serde(skip)]
     pub api_key: SecretString,
+    /// Command that produces the API key when `TOWL_LLM_API_KEY` is unset,
+    /// e.g. `["op", "read", "op://dev/anthropic/key"]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_command: Option<Vec<String>>,
+    /// How `api_key_command` returns the key.
+    #[serde(default, skip_serializing_if = "CredentialProtocol::is_plain")]
+    pub api_key_protocol: CredentialProtocol,
+    /// Number of TODOs analysed in parallel (1-20).
     #[serde(default = "default_max_concurrent_analyses")]
     pub max_concurrent_analyses: usize,
+    /// Maximum number of TODOs analysed per run.
     #[serde(default = "default_max_analyse_count")]
     pub max_analyse_count: usize,
+    /// Maximum tokens in each response.
     #[serde(default = "default_llm_max_tokens")]
     pub max_tokens: u32,
+    /// Retries for failed requests.
     #[serde(default = "default_llm_max_retries")]
     pub max_retries: usize,
+    /// CLI binary for the `claude-code` and `codex` providers.
     #[serde(default)]
     pub command: Option<String>,
+    /// Arguments for `command`.
     #[serde(default)]
     pub args: Option<Vec<String>>,
 }
@@ -250,6 +659,8 @@ impl Default for LlmConfig {
             model: default_llm_model(),
             base_url: None,
             api_key: SecretString::default(),
+            api_key_command: None,
+            api_key_protocol: CredentialProtocol::default(),
             max_concurrent_analyses: default_max_concurrent_analyses(),
             max_analyse_count: default_max_analyse_count(),
             max_tokens: default_llm_max_tokens(),
@@ -267,6 +678,8 @@ impl fmt::Debug for LlmConfig {
             .field("model", &self.model)
             .field("base_url", &self.base_url)
             .field("api_key", &"[REDACTED]")
+            .field("api_key_command", &self.api_key_command)
+            .field("api_key_protocol", &self.api_key_protocol)
             .field("max_concurrent_analyses", &self.max_concurrent_analyses)
             .field("max_analyse_count", &self.max_analyse_count)
             .field("max_tokens", &self.max_tokens)
@@ -282,6 +695,8 @@ impl PartialEq for LlmConfig {
         self.provider == other.provider
             && self.model == other.model
             && self.base_url == other.base_url
+            && self.api_key_command == other.api_key_command
+            && self.api_key_protocol == other.api_key_protocol
             && self.max_concurrent_analyses == other.max_concurrent_analyses
             && self.max_analyse_count == other.max_analyse_count
             && self.max_tokens == other.max_tokens
@@ -314,6 +729,7 @@ pub fn test_parsing_config() -> ParsingConfig {
             .into_iter()
             .collect(),
         exclude_patterns: vec!["target/*".to_string(), "*.log".to_string()],
+        include_patterns: Vec::new(),
         include_context_lines: 3,
         comment_prefixes: vec![
             r"//".to_string(),
@@ -332,6 +748,11 @@ pub fn test_parsing_config() -> ParsingConfig {
             r"^\s*(pub\s+)?fn\s+(\w+)".to_string(),
             r"^\s*def\s+(\w+)".to_string(),
         ],
+        languages: BTreeMap::new(),
+        discovery: DiscoveryMode::Walk,
+        follow_symlinks: false,
+        allow_symlinks_outside_root: false,
+        detect_placeholders: false,
     }
 }
 
@@ -359,6 +780,7 @@ mod tests {
             loaded.parsing.include_context_lines,
             defaults.parsing.include_context_lines
         );
+        assert_eq!(loaded.parsing.languages, defaults.parsing.languages);
         assert_ne!(
             loaded.github.owner.to_string(),
             "no owner",
@@ -381,6 +803,31 @@ mod tests {
         assert!(!display.contains("secret-token-123"));
     }
 
+    #[test]
+    fn test_display_credential_commands_without_running_them() {
+        let mut config = TowlConfig::default();
+        config.github.token_command = Some(vec!["gh".to_string(), "auth".to_string()]);
+        config.llm.api_key_command = Some(vec!["op".to_string(), "read".to_string()]);
+        let display = config.to_string();
+
+        assert!(display.contains("Token: via token_command"));
+        assert!(display.contains("API Key: via api_key_command"));
+    }
+
+    #[test]
+    fn test_credential_protocol_round_trips() {
+        let source = "[github]\ntoken_command = [\"git\", \"credential\", \"fill\"]\n\
+                      token_protocol = \"git-credential\"\n";
+        let config: TowlConfig = toml::from_str(source).unwrap();
+        assert_eq!(
+            config.github.token_protocol,
+            CredentialProtocol::GitCredential
+        );
+        let rendered = toml::to_string(&config).unwrap();
+        assert!(rendered.contains("token_protocol = \"git-credential\""));
+        assert!(!rendered.contains("api_key_protocol"));
+    }
+
     proptest! {
         #[test]
         fn prop_validate_path_rejects_traversal(
@@ -439,6 +886,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_language_profile_merges_over_defaults() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("languages.toml");
+        std::fs::write(
+            &config_path,
+            "[parsing.languages.python]\ncomment_prefixes = ['^\\s*;']\n\n\
+             [parsing.languages.terraform]\nextensions = [\"tf\"]\nfilenames = [\"Terrafile\"]\n",
+        )
+        .unwrap();
+
+        let loaded = TowlConfig::load(Some(&config_path)).unwrap();
+        let python = &loaded.parsing.languages["python"];
+        assert_eq!(python.comment_prefixes, vec![r"^\s*;".to_string()]);
+        assert_eq!(
+            python.interpreters,
+            ParsingConfig::default().languages["python"].interpreters
+        );
+        assert_eq!(
+            loaded.parsing.languages["terraform"].filenames,
+            vec!["Terrafile".to_string()]
+        );
+        assert!(loaded.parsing.languages.contains_key("dockerfile"));
+    }
+
+    #[rstest]
+    #[case("walk", DiscoveryMode::Walk)]
+    #[case("git", DiscoveryMode::Git)]
+    #[case("git-untracked", DiscoveryMode::GitUntracked)]
+    fn test_discovery_mode_from_file(#[case] value: &str, #[case] expected: DiscoveryMode) {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("discovery.toml");
+        std::fs::write(
+            &config_path,
+            format!("[parsing]\ndiscovery = \"{value}\"\n"),
+        )
+        .unwrap();
+
+        let loaded = TowlConfig::load(Some(&config_path)).unwrap();
+        assert_eq!(loaded.parsing.discovery, expected);
+        assert_eq!(loaded.parsing.discovery.to_string(), value);
+    }
+
     #[tokio::test]
     async fn test_atomic_write_overwrites_existing() {
         let temp_dir = tempfile::TempDir::new().unwrap();
@@ -577,4 +1067,112 @@ mod tests {
             if field == "file_extensions"
         ));
     }
+
+    #[rstest]
+    #[case::public("github.com", None, "https://api.github.com")]
+    #[case::enterprise("ghe.example.com", None, "https://ghe.example.com/api/v3")]
+    #[case::enterprise_port("ghe.example.com:8443", None, "https://ghe.example.com:8443/api/v3")]
+    #[case::explicit(
+        "ghe.example.com",
+        Some("https://api.ghe.example.com"),
+        "https://api.ghe.example.com"
+    )]
+    fn test_github_api_base_url(
+        #[case] host: &str,
+        #[case] api_url: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        let github = GitHubConfig {
+            host: host.to_string(),
+            api_url: api_url.map(String::from),
+            ..GitHubConfig::default()
+        };
+        assert_eq!(github.api_base_url(), expected);
+        assert_eq!(github.web_url(), format!("https://{host}"));
+    }
+
+    #[rstest]
+    #[case::default("github.com", None, "origin", None)]
+    #[case::enterprise(
+        "ghe.example.com:8443",
+        Some("http://ghe.internal/api/v3"),
+        "upstream",
+        None
+    )]
+    #[case::scheme_in_host("https://ghe.example.com", None, "origin", Some("host"))]
+    #[case::path_in_host("ghe.example.com/github", None, "origin", Some("host"))]
+    #[case::empty_host("", None, "origin", Some("host"))]
+    #[case::api_not_url(
+        "ghe.example.com",
+        Some("ghe.example.com/api"),
+        "origin",
+        Some("api_url")
+    )]
+    #[case::api_scheme(
+        "ghe.example.com",
+        Some("ftp://ghe.example.com"),
+        "origin",
+        Some("api_url")
+    )]
+    #[case::option_remote("github.com", None, "--upload-pack=x", Some("remote"))]
+    #[case::spaced_remote("github.com", None, "my remote", Some("remote"))]
+    fn test_validate_github_endpoints(
+        #[case] host: &str,
+        #[case] api_url: Option<&str>,
+        #[case] remote: &str,
+        #[case] bad_field: Option<&str>,
+    ) {
+        let github = GitHubConfig {
+            host: host.to_string(),
+            api_url: api_url.map(String::from),
+            remote: remote.to_string(),
+            ..GitHubConfig::default()
+        };
+        let result = TowlConfig::validate_github_endpoints(&github);
+        match bad_field {
+            None => assert!(result.is_ok(), "{result:?}"),
+            Some(expected) => assert!(
+                matches!(&result, Err(TowlConfigError::InvalidGitHubSetting { field, .. }) if field == expected),
+                "{result:?}"
+            ),
+        }
+    }
+
+    #[rstest]
+    #[case("", "*/", "//", Some("block_comments"))]
+    #[case("/*", "", "//", Some("block_comments"))]
+    #[case("/*", "*/", "", Some("line_comments"))]
+    #[case("/*", "*/", "//", None)]
+    fn test_validate_block_comments_rejects_empty_delimiters(
+        #[case] start: &str,
+        #[case] end: &str,
+        #[case] line_comment: &str,
+        #[case] expected_field: Option<&str>,
+    ) {
+        let mut parsing = ParsingConfig::default();
+        parsing.languages.insert(
+            "custom".to_string(),
+            LanguageProfile {
+                block_comments: vec![BlockComment {
+                    start: start.to_string(),
+                    end: end.to_string(),
+                    nested: false,
+                }],
+                line_comments: vec![line_comment.to_string()],
+                ..Default::default()
+            },
+        );
+        let result = TowlConfig::validate_block_comments(&parsing);
+        match expected_field {
+            None => assert!(result.is_ok(), "{result:?}"),
+            Some(expected) => assert!(
+                matches!(
+                    &result,
+                    Err(TowlConfigError::EmptyCommentDelimiter { field })
+                    if *field == format!("languages.custom.{expected}")
+                ),
+                "{result:?}"
+            ),
+        }
+    }
 }
@@ -2,7 +2,7 @@ use std::path::Path;
 
 use super::error::TowlConfigError;
 use super::newtypes::MAX_CONFIG_STRING_LENGTH;
-use super::types::{GitHubConfig, LlmConfig, ParsingConfig, TowlConfig};
+use super::types::{GitHubConfig, LanguageProfile, LlmConfig, ParsingConfig, TowlConfig};
 use crate::{MAX_CONTEXT_LINES, MIN_CONTEXT_LINES};
 
 const MAX_CONFIG_PATTERNS: usize = 100;
@@ -14,8 +14,11 @@ impl TowlConfig {
     pub(crate) fn validate(config: &Self) -> Result<(), TowlConfigError> {
         Self::validate_pattern_counts(&config.parsing)?;
         Self::validate_string_lengths(&config.parsing)?;
+        Self::validate_block_comments(&config.parsing)?;
         Self::validate_context_lines(&config.parsing)?;
         Self::validate_rate_limit_delay(&config.github)?;
+        Self::validate_github_endpoints(&config.github)?;
+        Self::validate_credential_commands(config)?;
         Self::validate_llm(&config.llm)
     }
 
@@ -56,6 +59,7 @@ impl TowlConfig {
         }
         let vec_fields: &[(&str, &[String])] = &[
             ("exclude_patterns", &parsing.exclude_patterns),
+            ("include_patterns", &parsing.include_patterns),
             ("comment_prefixes", &parsing.comment_prefixes),
             ("todo_patterns", &parsing.todo_patterns),
             ("function_patterns", &parsing.function_patterns),
@@ -65,9 +69,69 @@ impl TowlConfig {
                 Self::check_string_length(field, value)?;
             }
         }
+        for (name, profile) in &parsing.languages {
+            Self::check_string_length("languages", name)?;
+            for (field, values) in Self::language_fields(name, profile) {
+                for value in values {
+                    Self::check_string_length(&field, value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn validate_block_comments(parsing: &ParsingConfig) -> Result<(), TowlConfigError> {
+        for (name, profile) in &parsing.languages {
+            let field = format!("languages.{name}.block_comments");
+            if profile.block_comments.len() > MAX_CONFIG_PATTERNS {
+                return Err(TowlConfigError::TooManyConfigPatterns {
+                    field,
+                    count: profile.block_comments.len(),
+                    max_allowed: MAX_CONFIG_PATTERNS,
+                });
+            }
+            for block in &profile.block_comments {
+                if block.start.is_empty() || block.end.is_empty() {
+                    return Err(TowlConfigError::EmptyCommentDelimiter { field });
+                }
+                Self::check_string_length(&field, &block.start)?;
+                Self::check_string_length(&field, &block.end)?;
+            }
+            if profile.line_comments.iter().any(String::is_empty) {
+                return Err(TowlConfigError::EmptyCommentDelimiter {
+                    field: format!("languages.{name}.line_comments"),
+                });
+            }
+        }
         Ok(())
     }
 
+    fn language_fields<'a>(
+        name: &str,
+        profile: &'a LanguageProfile,
+    ) -> [(String, &'a [String]); 6] {
+        [
+            (format!("languages.{name}.extensions"), &profile.extensions),
+            (format!("languages.{name}.filenames"), &profile.filenames),
+            (
+                format!("languages.{name}.interpreters"),
+                &profile.interpreters,
+            ),
+            (
+                format!("languages.{name}.comment_prefixes"),
+                &profile.comment_prefixes,
+            ),
+            (
+                format!("languages.{name}.line_comments"),
+                &profile.line_comments,
+            ),
+            (
+                format!("languages.{name}.placeholder_patterns"),
+                &profile.placeholder_patterns,
+            ),
+        ]
+    }
+
     pub(crate) const fn validate_context_lines(
         parsing: &ParsingConfig,
     ) -> Result<(), TowlConfigError> {
@@ -95,18 +159,79 @@ impl TowlConfig {
         Ok(())
     }
 
+    /// Checks that `host`, `api_url` and `remote` are usable: a bare host
+    /// with an optional port, an http(s) URL, and a remote name git will not
+    /// read as an option.
+    pub(crate) fn validate_github_endpoints(github: &GitHubConfig) -> Result<(), TowlConfigError> {
+        let invalid = |field: &str, message: &str| TowlConfigError::InvalidGitHubSetting {
+            field: field.to_string(),
+            message: message.to_string(),
+        };
+        for (field, value) in [
+            ("host", Some(&github.host)),
+            ("api_url", github.api_url.as_ref()),
+            ("remote", Some(&github.remote)),
+        ] {
+            if let Some(value) = value {
+                Self::check_string_length(&format!("github.{field}"), value)?;
+            }
+        }
+
+        let host_url = url::Url::parse(&github.web_url()).ok().filter(|url| {
+            url.host_str().is_some()
+                && url.path() == "/"
+                && url.username().is_empty()
+                && url.query().is_none()
+                && !github.host.ends_with('/')
+        });
+        if github.host.is_empty() || github.host.contains("://") || host_url.is_none() {
+            return Err(invalid(
+                "host",
+                "expected a host name with an optional port",
+            ));
+        }
+
+        if let Some(api_url) = &github.api_url {
+            let valid = url::Url::parse(api_url)
+                .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host());
+            if !valid {
+                return Err(invalid("api_url", "expected an http or https URL"));
+            }
+        }
+
+        if github.remote.is_empty()
+            || github.remote.starts_with('-')
+            || github.remote.chars().any(char::is_whitespace)
+        {
+            return Err(invalid(
+                "remote",
+                "expected a git remote name without whitespace or a leading '-'",
+            ));
+        }
+        Ok(())
+    }
+
     pub(crate) fn validate_pattern_counts(parsing: &ParsingConfig) -> Result<(), TowlConfigError> {
         let checks: &[(&str, usize)] = &[
             ("file_extensions", parsing.file_extensions.len()),
             ("exclude_patterns", parsing.exclude_patterns.len()),
+            ("include_patterns", parsing.include_patterns.len()),
             ("comment_prefixes", parsing.comment_prefixes.len()),
             ("todo_patterns", parsing.todo_patterns.len()),
             ("function_patterns", parsing.function_patterns.len()),
+            ("languages", parsing.languages.len()),
         ];
-        for &(field, count) in checks {
+        let language_checks = parsing.languages.iter().flat_map(|(name, profile)| {
+            Self::language_fields(name, profile).map(|(field, values)| (field, values.len()))
+        });
+        let checks = checks
+            .iter()
+            .map(|&(field, count)| (field.to_string(), count))
+            .chain(language_checks);
+        for (field, count) in checks {
             if count > MAX_CONFIG_PATTERNS {
                 return Err(TowlConfigError::TooManyConfigPatterns {
-                    field: field.to_string(),
+                    field,
                     count,
                     max_allowed: MAX_CONFIG_PATTERNS,
                 });
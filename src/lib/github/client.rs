@@ -23,16 +23,19 @@ const MAX_PAGES: u32 = 100;
 /// Includes rate-limit handling with configurable delays and automatic retries.
 pub struct GitHubClient {
     client: Octocrab,
+    web_url: Url,
     owner: String,
     repo: String,
     default_branch: String,
+    link_ref: Option<String>,
     existing_issue_titles: HashSet<String>,
     existing_todo_ids: HashSet<String>,
     rate_limit_delay_ms: u64,
 }
 
 impl GitHubClient {
-    /// Creates a new GitHub client from config.
+    /// Creates a new GitHub client from config, talking to
+    /// [`GitHubConfig::api_base_url`] and linking to [`GitHubConfig::web_url`].
     ///
     /// # Errors
     /// Returns `TowlGitHubError::MissingToken` if the token is empty.
@@ -42,27 +45,42 @@ impl GitHubClient {
         if token.is_empty() {
             return Err(TowlGitHubError::MissingToken);
         }
+        let api_error = |e: octocrab::Error| TowlGitHubError::ApiError {
+            message: e.to_string(),
+            source: Some(e),
+        };
+        let web_url = Url::parse(&config.web_url()).map_err(|e| TowlGitHubError::ApiError {
+            message: format!("Invalid GitHub host {}: {e}", config.host),
+            source: None,
+        })?;
 
         let client = Octocrab::builder()
             // SECURITY: Single exposure point for GitHub token from SecretString
             .personal_token(token.to_string())
+            .base_uri(config.api_base_url())
+            .map_err(api_error)?
             .build()
-            .map_err(|e| TowlGitHubError::ApiError {
-                message: e.to_string(),
-                source: Some(e),
-            })?;
+            .map_err(api_error)?;
 
         Ok(Self {
             client,
+            web_url,
             owner: config.owner.to_string(),
             repo: config.repo.to_string(),
             default_branch: "main".to_string(),
+            link_ref: None,
             existing_issue_titles: HashSet::new(),
             existing_todo_ids: HashSet::new(),
             rate_limit_delay_ms: config.rate_limit_delay_ms,
         })
     }
 
+    /// Links file locations in issue bodies to `git_ref` (a branch, tag or
+    /// commit) instead of the repository's default branch.
+    pub fn set_link_ref(&mut self, git_ref: &str) {
+        self.link_ref = Some(git_ref.to_string());
+    }
+
     /// Loads existing issues for duplicate detection.
     ///
     /// # Errors
@@ -181,11 +199,13 @@ impl GitHubClient {
             return Err(TowlGitHubError::IssueAlreadyExists { title });
         }
 
-        let body = Self::generate_issue_body(todo, &self.owner, &self.repo, &self.default_branch)
-            .map_err(|e| TowlGitHubError::ApiError {
-            message: format!("Failed to format issue body: {e}"),
-            source: None,
-        })?;
+        let link_ref = self.link_ref.as_deref().unwrap_or(&self.default_branch);
+        let body =
+            Self::generate_issue_body(todo, &self.web_url, &self.owner, &self.repo, link_ref)
+                .map_err(|e| TowlGitHubError::ApiError {
+                    message: format!("Failed to format issue body: {e}"),
+                    source: None,
+                })?;
         let label = todo.todo_type.github_label();
 
         let issue = self.create_issue_with_retry(&title, &body, label).await?;
@@ -220,18 +240,20 @@ impl GitHubClient {
 
     fn generate_issue_body(
         todo: &TodoComment,
+        web_url: &Url,
         owner: &str,
         repo: &str,
-        default_branch: &str,
+        git_ref: &str,
     ) -> Result<String, std::fmt::Error> {
         use std::fmt::Write;
         let mut body = String::new();
         let file_display = todo.file_path.display().to_string();
 
         let location_line = build_file_url(
+            web_url,
             owner,
             repo,
-            default_branch,
+            git_ref,
             &todo.file_path,
             todo.line_number,
         )
@@ -274,9 +296,14 @@ impl GitHubClient {
             )?;
         }
 
+        let original_heading = if todo.todo_type.is_placeholder() {
+            "Original Code"
+        } else {
+            "Original Comment"
+        };
         write!(
             body,
-            "\n## Original Comment\n\n{}\n",
+            "\n## {original_heading}\n\n{}\n",
             code_block(&todo.original_text),
         )?;
 
@@ -373,20 +400,24 @@ impl GitHubClient {
     }
 }
 
-fn build_file_url(
+pub(crate) fn build_file_url(
+    web_url: &Url,
     owner: &str,
     repo: &str,
-    default_branch: &str,
+    git_ref: &str,
     file_path: &Path,
     line_number: usize,
 ) -> Option<Url> {
-    let mut url = Url::parse("https://github.com").ok()?;
+    let mut url = web_url.clone(); // clone: each link extends its own copy of the base
     {
         let mut segments = url.path_segments_mut().ok()?;
+        segments.pop_if_empty();
         segments.push(owner);
         segments.push(repo);
         segments.push("blob");
-        segments.push(default_branch);
+        // Branch names like release/2.x stay as path segments, which is how
+        // GitHub resolves them in blob URLs
+        segments.extend(git_ref.split('/'));
         // Path::components is separator-agnostic, so Windows paths do not end
         // up as one percent-encoded backslash segment; "." and ".." drop out
         for component in file_path.components() {
@@ -444,6 +475,10 @@ mod tests {
     const TEST_REPO: &str = "testrepo";
     const TEST_BRANCH: &str = "main";
 
+    fn github() -> Url {
+        Url::parse("https://github.com").unwrap()
+    }
+
     fn make_todo(desc: &str, todo_type: TodoType) -> TodoComment {
         TestTodoBuilder::new()
             .description(desc)
@@ -458,7 +493,8 @@ mod tests {
     }
 
     fn body_for(todo: &TodoComment) -> String {
-        GitHubClient::generate_issue_body(todo, TEST_OWNER, TEST_REPO, TEST_BRANCH).unwrap()
+        GitHubClient::generate_issue_body(todo, &github(), TEST_OWNER, TEST_REPO, TEST_BRANCH)
+            .unwrap()
     }
 
     #[test]
@@ -492,6 +528,33 @@ mod tests {
         assert!(!body.contains(marker));
     }
 
+    #[rstest]
+    #[case("v1.2.0", "/blob/v1.2.0/src/main.rs#L10")]
+    #[case("release/2.x", "/blob/release/2.x/src/main.rs#L10")]
+    fn test_generate_body_links_to_ref(#[case] git_ref: &str, #[case] expected: &str) {
+        let todo = make_todo("Fix the cache", TodoType::Todo);
+
+        let body =
+            GitHubClient::generate_issue_body(&todo, &github(), TEST_OWNER, TEST_REPO, git_ref)
+                .unwrap();
+
+        assert!(body.contains(expected), "{body}");
+    }
+
+    #[rstest]
+    #[case(TodoType::Todo, "## Original Comment", "## Original Code")]
+    #[case(TodoType::Placeholder, "## Original Code", "## Original Comment")]
+    fn test_generate_body_original_heading(
+        #[case] todo_type: TodoType,
+        #[case] expected: &str,
+        #[case] unexpected: &str,
+    ) {
+        let body = body_for(&make_todo("Wire up the cache", todo_type));
+        assert!(body.contains(expected), "{body}");
+        assert!(!body.contains(unexpected), "{body}");
+        assert!(body.contains(&format!("**Type:** {todo_type}")));
+    }
+
     #[test]
     fn test_generate_body_includes_ai_analysis() {
         use crate::llm::types::{AnalysisResult, Validity};
@@ -529,7 +592,7 @@ mod tests {
             desc in "[a-zA-Z0-9 ]{1,200}",
             todo_type in prop::sample::select(vec![
                 TodoType::Todo, TodoType::Fixme, TodoType::Hack,
-                TodoType::Note, TodoType::Bug
+                TodoType::Note, TodoType::Bug, TodoType::Placeholder
             ])
         ) {
             let todo = make_todo(&desc, todo_type);
@@ -671,7 +734,15 @@ mod tests {
 
     #[test]
     fn test_build_file_url_basic() {
-        let url = build_file_url("owner", "repo", "main", Path::new("src/lib.rs"), 42).unwrap();
+        let url = build_file_url(
+            &github(),
+            "owner",
+            "repo",
+            "main",
+            Path::new("src/lib.rs"),
+            42,
+        )
+        .unwrap();
         assert_eq!(
             url.as_str(),
             "https://github.com/owner/repo/blob/main/src/lib.rs#L42"
@@ -680,16 +751,41 @@ mod tests {
 
     #[test]
     fn test_build_file_url_strips_dot_slash() {
-        let url = build_file_url("owner", "repo", "main", Path::new("./src/lib.rs"), 1).unwrap();
+        let url = build_file_url(
+            &github(),
+            "owner",
+            "repo",
+            "main",
+            Path::new("./src/lib.rs"),
+            1,
+        )
+        .unwrap();
         assert_eq!(
             url.as_str(),
             "https://github.com/owner/repo/blob/main/src/lib.rs#L1"
         );
     }
 
+    #[rstest]
+    #[case(
+        "https://ghe.example.com",
+        "https://ghe.example.com/team/tool/blob/main/src/lib.rs#L7"
+    )]
+    #[case(
+        "https://ghe.example.com:8443/",
+        "https://ghe.example.com:8443/team/tool/blob/main/src/lib.rs#L7"
+    )]
+    fn test_build_file_url_enterprise_host(#[case] base: &str, #[case] expected: &str) {
+        let base = Url::parse(base).unwrap();
+        let url =
+            build_file_url(&base, "team", "tool", "main", Path::new("src/lib.rs"), 7).unwrap();
+        assert_eq!(url.as_str(), expected);
+    }
+
     #[test]
     fn test_build_file_url_nested_path() {
         let url = build_file_url(
+            &github(),
             "org",
             "project",
             "develop",
@@ -713,7 +809,7 @@ mod tests {
             line in 1usize..10000
         ) {
             let file_path = path_segments.join("/");
-            let url = build_file_url(&owner, &repo, &branch, Path::new(&file_path), line);
+            let url = build_file_url(&github(), &owner, &repo, &branch, Path::new(&file_path), line);
             prop_assert!(url.is_some());
             let url = url.unwrap();
             prop_assert!(url.as_str().starts_with("https://github.com/"));
@@ -730,7 +826,7 @@ mod tests {
             filename in "[a-zA-Z0-9]{1,20}\\.rs",
             line in 1usize..10000
         ) {
-            let url = build_file_url(&owner, &repo, &branch, Path::new(&filename), line).unwrap();
+            let url = build_file_url(&github(), &owner, &repo, &branch, Path::new(&filename), line).unwrap();
             let url_str = url.as_str();
             let expected_fragment = format!("#L{line}");
             prop_assert!(url_str.contains(&owner));
@@ -745,7 +841,7 @@ mod tests {
             desc in "[a-zA-Z0-9 ]{1,50}",
             todo_type in prop::sample::select(vec![
                 TodoType::Todo, TodoType::Fixme, TodoType::Hack,
-                TodoType::Note, TodoType::Bug
+                TodoType::Note, TodoType::Bug, TodoType::Placeholder
             ])
         ) {
             let todo = make_todo(&desc, todo_type);
@@ -19,7 +19,7 @@ pub enum TowlGitHubError {
     IssueAlreadyExists { title: String },
     #[error("Repository not found: {owner}/{repo}")]
     RepositoryNotFound { owner: String, repo: String },
-    #[error("No GitHub token configured. Set TOWL_GITHUB_TOKEN environment variable")]
+    #[error("No GitHub token configured. Set TOWL_GITHUB_TOKEN or github.token_command")]
     MissingToken,
 }
 
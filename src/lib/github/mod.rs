@@ -9,4 +9,4 @@ pub mod types;
 
 pub use client::GitHubClient;
 pub use error::TowlGitHubError;
-pub use types::CreatedIssue;
+pub use types::{CreatedIssue, SourceLinks};
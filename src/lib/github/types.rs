@@ -1,4 +1,10 @@
+use std::path::Path;
+
 use serde::{Deserialize, Serialize};
+use url::Url;
+
+use super::client::build_file_url;
+use crate::config::{GitHubConfig, Owner, Repo};
 
 /// Metadata for a successfully created GitHub issue.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -21,6 +27,52 @@ impl CreatedIssue {
     }
 }
 
+/// Builds links to source lines on GitHub without an API client, so
+/// offline reports link the same way issue bodies do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLinks {
+    web_url: Url,
+    owner: String,
+    repo: String,
+    git_ref: String,
+}
+
+impl SourceLinks {
+    /// Links for the configured repository at `git_ref`, or `None` if the
+    /// owner or repo is unknown or the host is not a valid URL.
+    #[must_use]
+    pub fn from_config(config: &GitHubConfig, git_ref: &str) -> Option<Self> {
+        let owner = config.owner.to_string();
+        let repo = config.repo.to_string();
+        if owner.is_empty()
+            || repo.is_empty()
+            || config.owner == Owner::default()
+            || config.repo == Repo::default()
+        {
+            return None;
+        }
+        Some(Self {
+            web_url: Url::parse(&config.web_url()).ok()?,
+            owner,
+            repo,
+            git_ref: git_ref.to_string(),
+        })
+    }
+
+    /// Blob URL of `file_path` at the link ref, anchored at `line_number`.
+    #[must_use]
+    pub fn file_url(&self, file_path: &Path, line_number: usize) -> Option<Url> {
+        build_file_url(
+            &self.web_url,
+            &self.owner,
+            &self.repo,
+            &self.git_ref,
+            file_path,
+            line_number,
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -47,4 +99,21 @@ mod tests {
             prop_assert_eq!(decoded, issue);
         }
     }
+
+    #[test]
+    fn test_source_links_from_config() {
+        let mut config = GitHubConfig::default();
+        assert!(SourceLinks::from_config(&config, "main").is_none());
+
+        config.owner = Owner::new_unchecked("acme");
+        config.repo = Repo::new_unchecked("widgets");
+        let links = SourceLinks::from_config(&config, "release/2.x").unwrap();
+        assert_eq!(
+            links
+                .file_url(Path::new("./src/lib.rs"), 12)
+                .unwrap()
+                .as_str(),
+            "https://github.com/acme/widgets/blob/release/2.x/src/lib.rs#L12"
+        );
+    }
 }
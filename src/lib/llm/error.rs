@@ -14,7 +14,7 @@ pub enum TowlLlmError {
     RateLimited { retry_after_secs: u64 },
     #[error("Failed to parse LLM response: {message}")]
     ParseError { message: String },
-    #[error("LLM not configured: set TOWL_LLM_API_KEY environment variable")]
+    #[error("LLM not configured: set TOWL_LLM_API_KEY or llm.api_key_command")]
     NotConfigured,
     #[error("Unsupported LLM provider: {provider}")]
     UnsupportedProvider { provider: String },
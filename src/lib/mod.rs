@@ -27,7 +27,7 @@
 //!
 //! - [`scanner`] — Directory traversal and file scanning
 //! - [`config`] — Configuration loading and validation
-//! - [`output`] — Formatting and writing results (JSON, CSV, Markdown, etc.)
+//! - [`output`] — Formatting and writing results (JSON, CSV, Markdown, SARIF, JUnit, etc.)
 //! - [`github`] — GitHub issue creation from TODO comments
 //! - [`tui`] — Interactive terminal UI for browsing and selecting TODOs
 //! - [`processor`] — Post-creation replacement of TODO comments with issue links
@@ -10,6 +10,6 @@ pub enum TowlOutputError {
     UnableToFormatTodos(#[from] FormatterError),
     #[error("Unable to write todos: {0}")]
     UnableToWriteTodos(#[from] WriterError),
-    #[error("Invalid output path: {0}")]
-    InvalidOutputPath(String),
+    #[error("Invalid template: {0}")]
+    InvalidTemplate(String),
 }
@@ -6,4 +6,6 @@ pub enum FormatterError {
     SerializationError(String),
     #[error("Integer overflow: value {0} exceeds i64 range")]
     IntegerOverflow(usize),
+    #[error("Template error: {0}")]
+    TemplateError(String),
 }
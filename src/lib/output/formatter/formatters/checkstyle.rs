@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::{code_point_column, escape_xml, Severity};
+use crate::{
+    comment::todo::{TodoComment, TodoType},
+    output::formatter::{error::FormatterError, Formatter},
+};
+
+/// Report format version understood by Checkstyle consumers.
+const CHECKSTYLE_VERSION: &str = "4.3";
+
+/// Checkstyle XML report for CI code-quality parsers.
+///
+/// TODOs are grouped into one `<file>` element per file, each as an
+/// `<error>` with its 1-based line and column and a severity from its type.
+pub struct CheckstyleFormatter;
+
+impl CheckstyleFormatter {
+    const fn severity(todo_type: TodoType) -> &'static str {
+        match Severity::of(todo_type) {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        }
+    }
+}
+
+impl Formatter for CheckstyleFormatter {
+    fn format(
+        &self,
+        groups: &[(TodoType, Vec<&TodoComment>)],
+        _total_count: usize,
+    ) -> Result<Vec<String>, FormatterError> {
+        let mut by_file: BTreeMap<&Path, Vec<&TodoComment>> = BTreeMap::new();
+        for (_, todos_of_type) in groups {
+            for todo in todos_of_type {
+                by_file
+                    .entry(todo.file_path.as_path())
+                    .or_default()
+                    .push(todo);
+            }
+        }
+
+        let mut lines = vec![
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string(),
+            format!(r#"<checkstyle version="{CHECKSTYLE_VERSION}">"#),
+        ];
+        for (path, mut todos) in by_file {
+            todos.sort_by_key(|t| (t.line_number, t.column_start));
+            lines.push(format!(
+                r#"  <file name="{}">"#,
+                escape_xml(&path.display().to_string())
+            ));
+            for todo in todos {
+                lines.push(format!(
+                    r#"    <error line="{line}" column="{column}" severity="{severity}" message="{message}" source="towl.{source}"/>"#,
+                    line = todo.line_number,
+                    column = code_point_column(&todo.original_text, todo.column_start),
+                    severity = Self::severity(todo.todo_type),
+                    message = escape_xml(&format!(
+                        "{}: {}",
+                        todo.todo_type,
+                        todo.description.trim()
+                    )),
+                    source = todo.todo_type.as_filter_str(),
+                ));
+            }
+            lines.push("  </file>".to_string());
+        }
+        lines.push("</checkstyle>".to_string());
+
+        Ok(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comment::todo::test_support::TestTodoBuilder;
+    use rstest::rstest;
+
+    fn format(todos: &[TodoComment]) -> String {
+        let groups = crate::output::Output::group_todos_by_type(todos);
+        CheckstyleFormatter
+            .format(&groups, todos.len())
+            .unwrap()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_errors_grouped_by_file() {
+        let make = |todo_type, file: &str, line| {
+            TestTodoBuilder::new()
+                .todo_type(todo_type)
+                .file_path(file)
+                .line_number(line)
+                .original_text("    // TODO: x")
+                .column_start(4)
+                .description("x")
+                .build()
+        };
+        let xml = format(&[
+            make(TodoType::Todo, "b.rs", 2),
+            make(TodoType::Bug, "a.rs", 9),
+            make(TodoType::Note, "a.rs", 1),
+        ]);
+
+        assert!(xml.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+        assert_eq!(xml.matches("<file ").count(), 2);
+        let a = xml.find(r#"<file name="a.rs">"#).unwrap();
+        let b = xml.find(r#"<file name="b.rs">"#).unwrap();
+        assert!(a < b);
+        assert!(xml.contains(
+            r#"<error line="9" column="5" severity="error" message="BUG: x" source="towl.bug"/>"#
+        ));
+        assert!(xml.find(r#"line="1""#).unwrap() < xml.find(r#"line="9""#).unwrap());
+        assert!(xml.ends_with("</checkstyle>"));
+    }
+
+    #[rstest]
+    #[case(TodoType::Bug, "error")]
+    #[case(TodoType::Fixme, "warning")]
+    #[case(TodoType::Hack, "warning")]
+    #[case(TodoType::Placeholder, "warning")]
+    #[case(TodoType::Todo, "info")]
+    #[case(TodoType::Note, "info")]
+    fn test_severity_follows_priority(#[case] todo_type: TodoType, #[case] expected: &str) {
+        assert_eq!(CheckstyleFormatter::severity(todo_type), expected);
+    }
+
+    #[test]
+    fn test_message_is_escaped() {
+        let todo = TestTodoBuilder::new().description("a < b & c").build();
+        let xml = format(&[todo]);
+        assert!(xml.contains(r#"message="TODO: a &lt; b &amp; c""#));
+    }
+}
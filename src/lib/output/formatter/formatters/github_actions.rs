@@ -0,0 +1,151 @@
+use std::borrow::Cow;
+
+use super::{code_point_column, Severity};
+use crate::{
+    comment::todo::{TodoComment, TodoType},
+    output::formatter::{error::FormatterError, Formatter},
+};
+
+/// GitHub Actions workflow commands that annotate each TODO on the PR diff.
+///
+/// Prints one `::notice`, `::warning` or `::error` line per TODO, with the
+/// level taken from its type. Lines must reach the runner on stdout.
+pub struct GithubActionsFormatter;
+
+impl GithubActionsFormatter {
+    const fn level(todo_type: TodoType) -> &'static str {
+        match Severity::of(todo_type) {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "notice",
+        }
+    }
+
+    /// Escapes a workflow command message.
+    fn escape_data(text: &str) -> Cow<'_, str> {
+        if !text.contains(['%', '\r', '\n']) {
+            return Cow::Borrowed(text);
+        }
+        Cow::Owned(
+            text.replace('%', "%25")
+                .replace('\r', "%0D")
+                .replace('\n', "%0A"),
+        )
+    }
+
+    /// Escapes a workflow command property value, which also ends at `,`
+    /// and `:`.
+    fn escape_property(text: &str) -> String {
+        Self::escape_data(text)
+            .replace(':', "%3A")
+            .replace(',', "%2C")
+    }
+
+    fn annotation(todo: &TodoComment) -> String {
+        let path = todo.file_path.display().to_string();
+        let file = path.strip_prefix("./").unwrap_or(&path);
+        let title = todo.function_context.as_ref().map_or_else(
+            || todo.todo_type.to_string(),
+            |function| format!("{} in {function}", todo.todo_type),
+        );
+        let mut message = todo.description.trim().to_string(); // clone: analysis is appended below
+        if let Some(analysis) = &todo.analysis {
+            message.push_str(&format!(
+                "\n\nAI: {} - {}",
+                analysis.validity, analysis.reasoning
+            ));
+        }
+        format!(
+            "::{level} file={file},line={line},col={col},endColumn={end},title={title}::{message}",
+            level = Self::level(todo.todo_type),
+            file = Self::escape_property(file),
+            line = todo.line_number,
+            col = code_point_column(&todo.original_text, todo.column_start),
+            end = code_point_column(&todo.original_text, todo.column_end),
+            title = Self::escape_property(&title),
+            message = Self::escape_data(&message),
+        )
+    }
+}
+
+impl Formatter for GithubActionsFormatter {
+    fn format(
+        &self,
+        groups: &[(TodoType, Vec<&TodoComment>)],
+        total_count: usize,
+    ) -> Result<Vec<String>, FormatterError> {
+        let mut lines = Vec::with_capacity(total_count);
+        for (_, todos_of_type) in groups {
+            lines.extend(todos_of_type.iter().map(|todo| Self::annotation(todo)));
+        }
+        Ok(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comment::todo::test_support::TestTodoBuilder;
+    use rstest::rstest;
+
+    fn format(todos: &[TodoComment]) -> Vec<String> {
+        let groups = crate::output::Output::group_todos_by_type(todos);
+        GithubActionsFormatter.format(&groups, todos.len()).unwrap()
+    }
+
+    #[test]
+    fn test_annotation_line() {
+        let todo = TestTodoBuilder::new()
+            .todo_type(TodoType::Fixme)
+            .file_path("./src/net.rs")
+            .line_number(108)
+            .original_text("    // FIXME: Handle timeout")
+            .column_start(4)
+            .column_end(28)
+            .description("Handle timeout")
+            .function_context("connect")
+            .build();
+
+        assert_eq!(
+            format(&[todo]),
+            vec![
+                "::warning file=src/net.rs,line=108,col=5,endColumn=29,title=FIXME in connect::Handle timeout"
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(TodoType::Bug, "::error ")]
+    #[case(TodoType::Fixme, "::warning ")]
+    #[case(TodoType::Hack, "::warning ")]
+    #[case(TodoType::Placeholder, "::warning ")]
+    #[case(TodoType::Todo, "::notice ")]
+    #[case(TodoType::Note, "::notice ")]
+    fn test_level_follows_type(#[case] todo_type: TodoType, #[case] expected: &str) {
+        let todo = TestTodoBuilder::new().todo_type(todo_type).build();
+        assert!(format(&[todo])[0].starts_with(expected));
+    }
+
+    #[test]
+    fn test_values_are_escaped() {
+        let todo = TestTodoBuilder::new()
+            .file_path("a,b:c.rs")
+            .description("100% done\nnot really")
+            .function_context("f::g")
+            .build();
+        let line = &format(&[todo])[0];
+
+        assert!(line.contains("file=a%2Cb%3Ac.rs,"), "{line}");
+        assert!(line.contains("title=TODO in f%3A%3Ag::"), "{line}");
+        assert!(line.ends_with("::100%25 done%0Anot really"), "{line}");
+    }
+
+    #[test]
+    fn test_one_line_per_todo() {
+        let todos = vec![
+            TestTodoBuilder::new().todo_type(TodoType::Bug).build(),
+            TestTodoBuilder::new().todo_type(TodoType::Note).build(),
+        ];
+        assert_eq!(format(&todos).len(), 2);
+    }
+}
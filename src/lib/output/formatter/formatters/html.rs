@@ -0,0 +1,307 @@
+use clap::ValueEnum;
+
+use super::{escape_xml, pluralize, Severity};
+use crate::{
+    comment::todo::{TodoComment, TodoType},
+    github::SourceLinks,
+    llm::types::Validity,
+    output::formatter::{error::FormatterError, Formatter},
+};
+
+/// Validity filter value for TODOs without AI analysis.
+const UNANALYSED: &str = "unanalysed";
+
+/// Styles for the report; inline so the file works offline.
+const STYLE: &str = r"
+body { font-family: system-ui, sans-serif; margin: 2rem; color: #1f2328; }
+h1 { margin-bottom: 0.25rem; }
+.summary { color: #59636e; margin-top: 0; }
+.filters { display: flex; flex-wrap: wrap; gap: 0.5rem; margin: 1rem 0; }
+.filters input, .filters select { padding: 0.3rem 0.5rem; font: inherit; }
+table { border-collapse: collapse; width: 100%; }
+th, td { border-bottom: 1px solid #d1d9e0; padding: 0.4rem 0.6rem; text-align: left; vertical-align: top; }
+th { cursor: pointer; user-select: none; background: #f6f8fa; position: sticky; top: 0; }
+th[aria-sort=ascending]::after { content: ' \25B2'; }
+th[aria-sort=descending]::after { content: ' \25BC'; }
+td.location, td.function { font-family: ui-monospace, monospace; word-break: break-all; }
+pre { background: #f6f8fa; padding: 0.5rem; overflow-x: auto; margin: 0.3rem 0 0; }
+details summary { cursor: pointer; color: #59636e; }
+.type { font-weight: 600; }
+.error .type { color: #d1242f; }
+.warning .type { color: #9a6700; }
+.info .type { color: #0969da; }
+";
+
+/// Sorting and filtering; inline so the file works offline.
+const SCRIPT: &str = r"
+(() => {
+  const body = document.querySelector('#todos tbody');
+  const rows = Array.from(body.rows);
+  const filters = Array.from(document.querySelectorAll('[data-filter]'));
+  const count = document.getElementById('shown');
+  const apply = () => {
+    let shown = 0;
+    for (const row of rows) {
+      const visible = filters.every((input) => {
+        const wanted = input.value.trim().toLowerCase();
+        return !wanted || (input.tagName === 'SELECT'
+          ? row.dataset[input.dataset.filter] === wanted
+          : row.dataset[input.dataset.filter].includes(wanted));
+      });
+      row.hidden = !visible;
+      if (visible) shown++;
+    }
+    count.textContent = shown;
+  };
+  filters.forEach((input) => input.addEventListener('input', apply));
+  document.querySelectorAll('th[data-sort]').forEach((th) => {
+    th.addEventListener('click', () => {
+      const key = th.dataset.sort;
+      const dir = th.getAttribute('aria-sort') === 'ascending' ? -1 : 1;
+      document.querySelectorAll('th').forEach((other) => other.removeAttribute('aria-sort'));
+      th.setAttribute('aria-sort', dir === 1 ? 'ascending' : 'descending');
+      const value = (row) => row.dataset[key];
+      const numeric = key === 'line' || key === 'priority';
+      rows.sort((a, b) => dir * (numeric
+        ? Number(value(a)) - Number(value(b))
+        : value(a).localeCompare(value(b))));
+      rows.forEach((row) => body.appendChild(row));
+    });
+  });
+  apply();
+})();
+";
+
+/// Self-contained, interactive HTML report.
+///
+/// A single file with inline styles and script: a table of TODOs that can
+/// be sorted by column and filtered by type, file, function and validity,
+/// with collapsible context and AI analysis. Locations link to GitHub when
+/// `links` is set.
+#[derive(Default)]
+pub struct HtmlFormatter {
+    pub links: Option<SourceLinks>,
+}
+
+impl HtmlFormatter {
+    const fn severity_class(todo_type: TodoType) -> &'static str {
+        match Severity::of(todo_type) {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        }
+    }
+
+    fn options(values: impl IntoIterator<Item = (String, String)>) -> String {
+        values
+            .into_iter()
+            .map(|(value, label)| {
+                format!(
+                    r#"<option value="{}">{}</option>"#,
+                    escape_xml(&value),
+                    escape_xml(&label)
+                )
+            })
+            .collect()
+    }
+
+    fn filters() -> String {
+        let types = Self::options(
+            TodoType::value_variants()
+                .iter()
+                .map(|t| (t.as_filter_str().to_string(), t.to_string())),
+        );
+        let validities = Self::options(
+            [Validity::Valid, Validity::Invalid, Validity::Uncertain]
+                .iter()
+                .map(|v| (v.as_str().to_lowercase(), v.to_string()))
+                .chain([(UNANALYSED.to_string(), "Not analysed".to_string())]),
+        );
+        format!(
+            r#"<div class="filters">
+<select data-filter="type" aria-label="Type"><option value="">All types</option>{types}</select>
+<input data-filter="file" type="search" placeholder="File" aria-label="File">
+<input data-filter="function" type="search" placeholder="Function" aria-label="Function">
+<select data-filter="validity" aria-label="Validity"><option value="">Any validity</option>{validities}</select>
+</div>"#
+        )
+    }
+
+    fn row(&self, todo: &TodoComment) -> String {
+        let file = todo.file_path.display().to_string();
+        let location = escape_xml(&format!("{file}:{}", todo.line_number)).into_owned();
+        let location = match self
+            .links
+            .as_ref()
+            .and_then(|links| links.file_url(&todo.file_path, todo.line_number))
+        {
+            Some(url) => format!(r#"<a href="{}">{location}</a>"#, escape_xml(url.as_str())),
+            None => location,
+        };
+        let function = todo.function_context.as_deref().unwrap_or_default();
+
+        let mut description = escape_xml(todo.description.trim()).into_owned();
+        if !todo.context_lines.is_empty() {
+            description.push_str(&format!(
+                "<details><summary>Context</summary><pre>{}</pre></details>",
+                escape_xml(&todo.context_lines.join("\n"))
+            ));
+        }
+
+        let (validity, analysis) = todo.analysis.as_ref().map_or_else(
+            || (UNANALYSED.to_string(), String::new()),
+            |analysis| {
+                let mut cell = format!(
+                    "{} <small>({:.0}% confidence)</small><details><summary>Reasoning</summary><p>{}</p>",
+                    escape_xml(analysis.validity.as_str()),
+                    analysis.confidence * 100.0,
+                    escape_xml(&analysis.reasoning)
+                );
+                if !analysis.enrichment.is_empty() {
+                    cell.push_str(&format!(
+                        "<p><strong>Enrichment:</strong> {}</p>",
+                        escape_xml(&analysis.enrichment)
+                    ));
+                }
+                cell.push_str("</details>");
+                (analysis.validity.as_str().to_lowercase(), cell)
+            },
+        );
+
+        format!(
+            r#"<tr class="{class}" data-type="{filter}" data-priority="{priority}" data-file="{file_key}" data-line="{line}" data-function="{function_key}" data-validity="{validity}"><td class="type">{todo_type}</td><td class="location">{location}</td><td class="function">{function}</td><td>{description}</td><td>{analysis}</td></tr>"#,
+            class = Self::severity_class(todo.todo_type),
+            filter = todo.todo_type.as_filter_str(),
+            priority = todo.todo_type.priority(),
+            file_key = escape_xml(&file.to_lowercase()),
+            line = todo.line_number,
+            function_key = escape_xml(&function.to_lowercase()),
+            todo_type = todo.todo_type,
+            function = escape_xml(function),
+        )
+    }
+}
+
+impl Formatter for HtmlFormatter {
+    fn format(
+        &self,
+        groups: &[(TodoType, Vec<&TodoComment>)],
+        total_count: usize,
+    ) -> Result<Vec<String>, FormatterError> {
+        let mut lines = Vec::with_capacity(total_count + 16);
+        lines.push("<!DOCTYPE html>".to_string());
+        lines.push(r#"<html lang="en">"#.to_string());
+        lines.push(r#"<head><meta charset="utf-8">"#.to_string());
+        lines.push(
+            r#"<meta name="viewport" content="width=device-width, initial-scale=1">"#.to_string(),
+        );
+        lines.push("<title>TODO Comments</title>".to_string());
+        lines.push(format!("<style>{STYLE}</style>"));
+        lines.push("</head>".to_string());
+        lines.push("<body>".to_string());
+        lines.push("<h1>TODO Comments</h1>".to_string());
+        lines.push(format!(
+            r#"<p class="summary">Showing <span id="shown">{total_count}</span> of {total_count} TODO comment{}</p>"#,
+            pluralize(total_count)
+        ));
+        lines.push(Self::filters());
+        lines.push(r#"<table id="todos"><thead><tr><th data-sort="priority">Type</th><th data-sort="file">Location</th><th data-sort="function">Function</th><th>Description</th><th data-sort="validity">Analysis</th></tr></thead><tbody>"#.to_string());
+        for (_, todos_of_type) in groups {
+            lines.extend(todos_of_type.iter().map(|todo| self.row(todo)));
+        }
+        lines.push("</tbody></table>".to_string());
+        lines.push(format!("<script>{SCRIPT}</script>"));
+        lines.push("</body>".to_string());
+        lines.push("</html>".to_string());
+
+        Ok(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comment::todo::test_support::TestTodoBuilder;
+    use crate::config::{GitHubConfig, Owner, Repo};
+    use crate::llm::types::AnalysisResult;
+    use crate::output::formatter::formatters::test_helpers::create_test_todo;
+
+    fn format(formatter: &HtmlFormatter, todos: &[TodoComment]) -> String {
+        let groups = crate::output::Output::group_todos_by_type(todos);
+        formatter.format(&groups, todos.len()).unwrap().join("\n")
+    }
+
+    #[test]
+    fn test_report_is_self_contained() {
+        let html = format(
+            &HtmlFormatter::default(),
+            &[create_test_todo("Task", TodoType::Todo, Some("run"), true)],
+        );
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<style>"));
+        assert!(html.contains("<script>"));
+        assert!(!html.contains(" src="), "no external scripts");
+        assert!(!html.contains("<link"), "no external stylesheets");
+        assert!(html.contains(r#"data-type="todo""#));
+        assert!(html.contains(r#"data-function="run""#));
+        assert!(html.contains(r#"data-validity="unanalysed""#));
+        assert!(html.contains("<details><summary>Context</summary>"));
+        assert!(html.ends_with("</html>"));
+    }
+
+    #[test]
+    fn test_text_is_escaped() {
+        let todo = TestTodoBuilder::new()
+            .file_path("<a>.rs")
+            .description("<script>alert(1)</script>")
+            .build();
+        let html = format(&HtmlFormatter::default(), &[todo]);
+
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(html.contains("&lt;a&gt;.rs"));
+        assert_eq!(html.matches("<script>").count(), 1);
+    }
+
+    #[test]
+    fn test_locations_link_to_source() {
+        let config = GitHubConfig {
+            owner: Owner::new_unchecked("acme"),
+            repo: Repo::new_unchecked("widgets"),
+            ..GitHubConfig::default()
+        };
+        let formatter = HtmlFormatter {
+            links: SourceLinks::from_config(&config, "main"),
+        };
+        let todo = TestTodoBuilder::new()
+            .file_path("src/lib.rs")
+            .line_number(7)
+            .build();
+        let html = format(&formatter, &[todo]);
+
+        assert!(html.contains(
+            r#"<a href="https://github.com/acme/widgets/blob/main/src/lib.rs#L7">src/lib.rs:7</a>"#
+        ));
+    }
+
+    #[test]
+    fn test_analysis_shown() {
+        let mut todo = create_test_todo("Task", TodoType::Bug, None, false);
+        todo.analysis = Some(AnalysisResult {
+            validity: Validity::Valid,
+            reasoning: "Still reachable".to_string(),
+            is_resolved: false,
+            is_relevant: true,
+            is_actionable: true,
+            confidence: 0.8,
+            enrichment: "Check the retry path".to_string(),
+        });
+        let html = format(&HtmlFormatter::default(), &[todo]);
+
+        assert!(html.contains(r#"data-validity="valid""#));
+        assert!(html.contains("Valid <small>(80% confidence)</small>"));
+        assert!(html.contains("Still reachable"));
+        assert!(html.contains("Check the retry path"));
+        assert!(html.contains(r#"<tr class="error""#));
+    }
+}
@@ -0,0 +1,119 @@
+use serde_json::Value;
+
+use crate::{
+    comment::todo::{TodoComment, TodoType},
+    output::formatter::{error::FormatterError, Formatter},
+};
+
+/// JSON Lines: one self-describing object per TODO, with no enclosing
+/// document, so each line can be piped to `jq` or read as it is written.
+///
+/// Objects carry every [`TodoComment`] field; `analysis` is `null` when the
+/// TODO was not analysed.
+pub struct JsonlFormatter;
+
+impl JsonlFormatter {
+    /// Serializes one TODO as a single line of JSON.
+    ///
+    /// # Errors
+    /// Returns `FormatterError::SerializationError` if serialization fails.
+    pub(crate) fn record(todo: &TodoComment) -> Result<String, FormatterError> {
+        Self::record_value(todo).map(|record| record.to_string())
+    }
+
+    /// Every field of `todo` as a JSON object, with `analysis` present even
+    /// when the TODO was not analysed.
+    ///
+    /// # Errors
+    /// Returns `FormatterError::SerializationError` if serialization fails.
+    pub(crate) fn record_value(todo: &TodoComment) -> Result<Value, FormatterError> {
+        let mut record = serde_json::to_value(todo)
+            .map_err(|e| FormatterError::SerializationError(e.to_string()))?;
+        if let Value::Object(fields) = &mut record {
+            fields.entry("analysis").or_insert(Value::Null);
+        }
+        Ok(record)
+    }
+}
+
+impl Formatter for JsonlFormatter {
+    fn format(
+        &self,
+        groups: &[(TodoType, Vec<&TodoComment>)],
+        total_count: usize,
+    ) -> Result<Vec<String>, FormatterError> {
+        let mut lines = Vec::with_capacity(total_count);
+        for (_, todos_of_type) in groups {
+            for todo in todos_of_type {
+                lines.push(Self::record(todo)?);
+            }
+        }
+        Ok(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::types::{AnalysisResult, Validity};
+    use crate::output::formatter::formatters::test_helpers::create_test_todo;
+
+    #[test]
+    fn test_one_object_per_line() {
+        let todos = vec![
+            create_test_todo("First", TodoType::Todo, Some("run"), true),
+            create_test_todo("Second", TodoType::Bug, None, false),
+        ];
+        let groups = crate::output::Output::group_todos_by_type(&todos);
+        let lines = JsonlFormatter.format(&groups, todos.len()).unwrap();
+
+        assert_eq!(lines.len(), 2);
+        for line in &lines {
+            assert!(!line.contains('\n'));
+            let record: Value = serde_json::from_str(line).unwrap();
+            for field in [
+                "id",
+                "file_path",
+                "line_number",
+                "column_start",
+                "column_end",
+                "todo_type",
+                "original_text",
+                "description",
+                "context_lines",
+                "function_context",
+                "analysis",
+            ] {
+                assert!(record.get(field).is_some(), "missing {field} in {line}");
+            }
+        }
+        // groups are ordered by priority, so the bug comes first
+        assert!(lines[0].contains(r#""description":"Second""#));
+    }
+
+    #[test]
+    fn test_record_round_trips() {
+        let mut todo = create_test_todo("Task", TodoType::Fixme, Some("run"), true);
+        todo.analysis = Some(AnalysisResult {
+            validity: Validity::Valid,
+            reasoning: "Still needed".to_string(),
+            is_resolved: false,
+            is_relevant: true,
+            is_actionable: true,
+            confidence: 0.75,
+            enrichment: String::new(),
+        });
+
+        let line = JsonlFormatter::record(&todo).unwrap();
+        let decoded: TodoComment = serde_json::from_str(&line).unwrap();
+        assert_eq!(decoded, todo);
+    }
+
+    #[test]
+    fn test_missing_analysis_is_null() {
+        let todo = create_test_todo("Task", TodoType::Todo, None, false);
+        let record: Value = serde_json::from_str(&JsonlFormatter::record(&todo).unwrap()).unwrap();
+        assert!(record["analysis"].is_null());
+        assert!(record["function_context"].is_null());
+    }
+}
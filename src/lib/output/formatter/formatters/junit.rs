@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::{code_point_column, escape_xml};
+use crate::{
+    comment::todo::{TodoComment, TodoType},
+    output::formatter::{error::FormatterError, Formatter},
+};
+
+/// TODO types reported as failed test cases unless configured otherwise.
+pub const DEFAULT_JUNIT_FAILURES: [TodoType; 2] = [TodoType::Bug, TodoType::Fixme];
+
+/// JUnit XML report for CI test-report parsers.
+///
+/// Each file is a `<testsuite>` and each TODO a `<testcase>` in it: failed
+/// if its type is in `failing`, skipped otherwise.
+pub struct JunitFormatter {
+    pub failing: Vec<TodoType>,
+}
+
+impl Default for JunitFormatter {
+    fn default() -> Self {
+        Self {
+            failing: DEFAULT_JUNIT_FAILURES.to_vec(),
+        }
+    }
+}
+
+impl JunitFormatter {
+    fn push_suite(&self, lines: &mut Vec<String>, path: &Path, todos: &[&TodoComment]) {
+        let file = path.display().to_string();
+        let failures = todos
+            .iter()
+            .filter(|t| self.failing.contains(&t.todo_type))
+            .count();
+        lines.push(format!(
+            r#"  <testsuite name="{name}" tests="{tests}" failures="{failures}" skipped="{skipped}">"#,
+            name = escape_xml(&file),
+            tests = todos.len(),
+            skipped = todos.len() - failures,
+        ));
+
+        for todo in todos {
+            let message = format!("{}: {}", todo.todo_type, todo.description.trim());
+            let column = code_point_column(&todo.original_text, todo.column_start);
+            lines.push(format!(
+                r#"    <testcase name="{name}" classname="{file}" file="{file}" line="{line}">"#,
+                name = escape_xml(&format!("{message} ({file}:{}:{column})", todo.line_number)),
+                file = escape_xml(&file),
+                line = todo.line_number,
+            ));
+            if self.failing.contains(&todo.todo_type) {
+                let mut body = todo.original_text.trim().to_string(); // clone: details are appended below
+                if let Some(function) = &todo.function_context {
+                    body.push_str(&format!("\nin {function}"));
+                }
+                if let Some(analysis) = &todo.analysis {
+                    body.push_str(&format!("\n{}: {}", analysis.validity, analysis.reasoning));
+                }
+                lines.push(format!(
+                    r#"      <failure message="{message}" type="{kind}">{body}</failure>"#,
+                    message = escape_xml(&message),
+                    kind = todo.todo_type,
+                    body = escape_xml(&body),
+                ));
+            } else {
+                lines.push(format!(
+                    r#"      <skipped message="{}"/>"#,
+                    escape_xml(&message)
+                ));
+            }
+            lines.push("    </testcase>".to_string());
+        }
+        lines.push("  </testsuite>".to_string());
+    }
+}
+
+impl Formatter for JunitFormatter {
+    fn format(
+        &self,
+        groups: &[(TodoType, Vec<&TodoComment>)],
+        total_count: usize,
+    ) -> Result<Vec<String>, FormatterError> {
+        let mut by_file: BTreeMap<&Path, Vec<&TodoComment>> = BTreeMap::new();
+        for (_, todos_of_type) in groups {
+            for todo in todos_of_type {
+                by_file
+                    .entry(todo.file_path.as_path())
+                    .or_default()
+                    .push(todo);
+            }
+        }
+        let failures = groups
+            .iter()
+            .filter(|(todo_type, _)| self.failing.contains(todo_type))
+            .map(|(_, todos)| todos.len())
+            .sum::<usize>();
+
+        let mut lines = vec![
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string(),
+            format!(
+                r#"<testsuites name="towl" tests="{total_count}" failures="{failures}" skipped="{}">"#,
+                total_count.saturating_sub(failures)
+            ),
+        ];
+        for (path, mut todos) in by_file {
+            todos.sort_by_key(|t| (t.line_number, t.column_start));
+            self.push_suite(&mut lines, path, &todos);
+        }
+        lines.push("</testsuites>".to_string());
+
+        Ok(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comment::todo::test_support::TestTodoBuilder;
+
+    fn todo(todo_type: TodoType, file: &str, line: usize, description: &str) -> TodoComment {
+        TestTodoBuilder::new()
+            .todo_type(todo_type)
+            .file_path(file)
+            .line_number(line)
+            .description(description)
+            .build()
+    }
+
+    fn format(formatter: &JunitFormatter, todos: &[TodoComment]) -> String {
+        let groups = crate::output::Output::group_todos_by_type(todos);
+        formatter.format(&groups, todos.len()).unwrap().join("\n")
+    }
+
+    #[test]
+    fn test_one_suite_per_file() {
+        let todos = vec![
+            todo(TodoType::Todo, "src/a.rs", 3, "one"),
+            todo(TodoType::Bug, "src/b.rs", 1, "two"),
+            todo(TodoType::Fixme, "src/a.rs", 1, "three"),
+        ];
+        let xml = format(&JunitFormatter::default(), &todos);
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+        assert!(xml.contains(r#"<testsuites name="towl" tests="3" failures="2" skipped="1">"#));
+        assert!(xml.contains(r#"<testsuite name="src/a.rs" tests="2" failures="1" skipped="1">"#));
+        assert!(xml.contains(r#"<testsuite name="src/b.rs" tests="1" failures="1" skipped="0">"#));
+        // cases within a suite follow line order
+        let three = xml.find("FIXME: three").unwrap();
+        let one = xml.find("TODO: one").unwrap();
+        assert!(three < one);
+        assert!(xml.ends_with("</testsuites>"));
+    }
+
+    #[test]
+    fn test_failing_types_are_configurable() {
+        let todos = vec![
+            todo(TodoType::Todo, "a.rs", 1, "todo"),
+            todo(TodoType::Bug, "a.rs", 2, "bug"),
+        ];
+        let formatter = JunitFormatter {
+            failing: vec![TodoType::Todo],
+        };
+        let xml = format(&formatter, &todos);
+
+        assert!(xml.contains(r#"<failure message="TODO: todo" type="TODO">"#));
+        assert!(xml.contains(r#"<skipped message="BUG: bug"/>"#));
+    }
+
+    #[test]
+    fn test_text_is_escaped() {
+        let todos = vec![todo(TodoType::Bug, "a.rs", 1, "handle <T> & \"U\"")];
+        let xml = format(&JunitFormatter::default(), &todos);
+
+        assert!(xml.contains("handle &lt;T&gt; &amp; &quot;U&quot;"));
+        assert!(!xml.contains("<T>"));
+    }
+
+    #[test]
+    fn test_empty_report() {
+        let xml = format(&JunitFormatter::default(), &[]);
+        assert!(xml.contains(r#"tests="0" failures="0" skipped="0""#));
+        assert!(!xml.contains("<testsuite "));
+    }
+}
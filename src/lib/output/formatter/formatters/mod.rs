@@ -1,7 +1,18 @@
+use std::borrow::Cow;
+
+use crate::comment::todo::TodoType;
+
+pub(crate) mod checkstyle;
 pub(crate) mod csv;
+pub(crate) mod github_actions;
+pub(crate) mod html;
 pub(crate) mod json;
+pub(crate) mod jsonl;
+pub(crate) mod junit;
 pub(crate) mod markdown;
+pub(crate) mod sarif;
 pub(crate) mod table;
+pub(crate) mod template;
 pub(crate) mod toml;
 
 #[must_use]
@@ -13,6 +24,86 @@ pub(crate) const fn pluralize(count: usize) -> &'static str {
     }
 }
 
+/// How loudly report formats flag a TODO type: bugs are errors; fixes,
+/// hacks and placeholders warnings; plain TODOs and notes informational.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl Severity {
+    #[must_use]
+    pub(crate) const fn of(todo_type: TodoType) -> Self {
+        match todo_type {
+            TodoType::Bug => Self::Error,
+            TodoType::Fixme | TodoType::Hack | TodoType::Placeholder => Self::Warning,
+            TodoType::Todo | TodoType::Note => Self::Info,
+        }
+    }
+}
+
+/// 1-based column, in Unicode code points, of a byte offset into `line`.
+#[must_use]
+pub(crate) fn code_point_column(line: &str, byte_offset: usize) -> usize {
+    line.get(..byte_offset)
+        .map_or(byte_offset, |prefix| prefix.chars().count())
+        + 1
+}
+
+/// Escapes text for an XML attribute or element, dropping control
+/// characters XML 1.0 cannot represent.
+#[must_use]
+pub(crate) fn escape_xml(text: &str) -> Cow<'_, str> {
+    let needs_escape = |c: char| {
+        matches!(c, '&' | '<' | '>' | '"' | '\'')
+            || (c.is_control() && !matches!(c, '\t' | '\n' | '\r'))
+    };
+    if !text.contains(needs_escape) {
+        return Cow::Borrowed(text);
+    }
+    let mut escaped = String::with_capacity(text.len() + 16);
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("plain", "plain")]
+    #[case(
+        "a < b && c > \"d\" 'e'",
+        "a &lt; b &amp;&amp; c &gt; &quot;d&quot; &apos;e&apos;"
+    )]
+    #[case("bell\u{7}\ttab", "bell\ttab")]
+    fn test_escape_xml(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(escape_xml(input), expected);
+    }
+
+    #[rstest]
+    #[case("// TODO: x", 3, 4)]
+    #[case("let é = 1; // TODO", 12, 12)]
+    #[case("short", 99, 100)]
+    fn test_code_point_column(#[case] line: &str, #[case] offset: usize, #[case] expected: usize) {
+        assert_eq!(code_point_column(line, offset), expected);
+    }
+}
+
 #[cfg(test)]
 pub mod test_helpers {
     use crate::comment::todo::test_support::TestTodoBuilder;
@@ -0,0 +1,248 @@
+use clap::ValueEnum;
+use serde_json::{json, Value};
+
+use super::{code_point_column, Severity};
+use crate::{
+    comment::todo::{TodoComment, TodoType},
+    output::formatter::{error::FormatterError, Formatter},
+};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// Key of the partial fingerprint that lets dashboards match a result
+/// across runs.
+const FINGERPRINT_KEY: &str = "towlTodoId/v1";
+
+/// SARIF 2.1.0 log for code-scanning dashboards.
+///
+/// Each TODO type is a rule, with a level derived from its priority. Regions
+/// are 1-based and measured in Unicode code points, so multi-byte characters
+/// before a TODO do not shift its column.
+pub struct SarifFormatter;
+
+impl SarifFormatter {
+    const fn level(todo_type: TodoType) -> &'static str {
+        match Severity::of(todo_type) {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "note",
+        }
+    }
+
+    fn rule(todo_type: TodoType) -> Value {
+        json!({
+            "id": todo_type.as_filter_str(),
+            "name": todo_type.to_string(),
+            "shortDescription": { "text": format!("{todo_type} comment") },
+            "defaultConfiguration": { "level": Self::level(todo_type) },
+            "properties": { "priority": todo_type.priority() },
+        })
+    }
+
+    fn result(todo: &TodoComment, rule_index: usize) -> Value {
+        let uri = todo.file_path.display().to_string().replace('\\', "/");
+        let mut location = json!({
+            "physicalLocation": {
+                "artifactLocation": { "uri": uri },
+                "region": {
+                    "startLine": todo.line_number,
+                    "startColumn": code_point_column(&todo.original_text, todo.column_start),
+                    "endColumn": code_point_column(&todo.original_text, todo.column_end),
+                    "snippet": { "text": todo.original_text.trim() },
+                },
+            },
+        });
+        if todo.file_path.is_relative() {
+            location["physicalLocation"]["artifactLocation"]["uriBaseId"] = json!("%SRCROOT%");
+        }
+        if let Some(function) = &todo.function_context {
+            location["logicalLocations"] = json!([{ "name": function, "kind": "function" }]);
+        }
+
+        let mut properties = json!({ "todoType": todo.todo_type.to_string() });
+        if let Some(analysis) = &todo.analysis {
+            properties["analysis"] = json!({
+                "validity": analysis.validity.as_str(),
+                "reasoning": analysis.reasoning,
+                "isResolved": analysis.is_resolved,
+                "isRelevant": analysis.is_relevant,
+                "isActionable": analysis.is_actionable,
+                "confidence": analysis.confidence,
+                "enrichment": analysis.enrichment,
+            });
+        }
+
+        json!({
+            "ruleId": todo.todo_type.as_filter_str(),
+            "ruleIndex": rule_index,
+            "level": Self::level(todo.todo_type),
+            "message": { "text": format!("{}: {}", todo.todo_type, todo.description.trim()) },
+            "locations": [location],
+            "partialFingerprints": { FINGERPRINT_KEY: todo.id },
+            "properties": properties,
+        })
+    }
+}
+
+impl Formatter for SarifFormatter {
+    fn format(
+        &self,
+        groups: &[(TodoType, Vec<&TodoComment>)],
+        total_count: usize,
+    ) -> Result<Vec<String>, FormatterError> {
+        let rule_types = TodoType::value_variants();
+        let rules: Vec<Value> = rule_types.iter().map(|&t| Self::rule(t)).collect();
+
+        let mut results = Vec::with_capacity(total_count);
+        for (todo_type, todos_of_type) in groups {
+            let rule_index = rule_types
+                .iter()
+                .position(|t| t == todo_type)
+                .unwrap_or_default();
+            results.extend(
+                todos_of_type
+                    .iter()
+                    .map(|todo| Self::result(todo, rule_index)),
+            );
+        }
+
+        let log = json!({
+            "$schema": SARIF_SCHEMA,
+            "version": SARIF_VERSION,
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                        "rules": rules,
+                    }
+                },
+                "columnKind": "unicodeCodePoints",
+                "results": results,
+            }],
+        });
+
+        let sarif_string = serde_json::to_string_pretty(&log)
+            .map_err(|e| FormatterError::SerializationError(e.to_string()))?;
+
+        Ok(vec![sarif_string])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comment::todo::test_support::TestTodoBuilder;
+    use crate::llm::types::{AnalysisResult, Validity};
+    use crate::output::formatter::formatters::test_helpers::create_test_todo;
+    use rstest::rstest;
+
+    fn format(todos: &[TodoComment]) -> Value {
+        let groups = crate::output::Output::group_todos_by_type(todos);
+        let result = SarifFormatter.format(&groups, todos.len()).unwrap();
+        assert_eq!(result.len(), 1);
+        serde_json::from_str(&result[0]).unwrap()
+    }
+
+    #[test]
+    fn test_sarif_log_structure() {
+        let log = format(&[create_test_todo("Task", TodoType::Todo, None, false)]);
+
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(log["$schema"], SARIF_SCHEMA);
+        let run = &log["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "towl");
+        assert_eq!(
+            run["tool"]["driver"]["rules"].as_array().unwrap().len(),
+            TodoType::value_variants().len()
+        );
+        assert_eq!(run["results"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_result_points_at_its_rule() {
+        let log = format(&[
+            create_test_todo("Broken", TodoType::Bug, None, false),
+            create_test_todo("Later", TodoType::Note, None, false),
+        ]);
+        let run = &log["runs"][0];
+        for result in run["results"].as_array().unwrap() {
+            let index = usize::try_from(result["ruleIndex"].as_u64().unwrap()).unwrap();
+            assert_eq!(
+                run["tool"]["driver"]["rules"][index]["id"],
+                result["ruleId"]
+            );
+        }
+    }
+
+    #[rstest]
+    #[case(TodoType::Bug, "error")]
+    #[case(TodoType::Fixme, "warning")]
+    #[case(TodoType::Hack, "warning")]
+    #[case(TodoType::Placeholder, "warning")]
+    #[case(TodoType::Todo, "note")]
+    #[case(TodoType::Note, "note")]
+    fn test_level_follows_priority(#[case] todo_type: TodoType, #[case] expected: &str) {
+        let log = format(&[create_test_todo("x", todo_type, None, false)]);
+        assert_eq!(log["runs"][0]["results"][0]["level"], expected);
+    }
+
+    #[test]
+    fn test_region_is_one_based_in_code_points() {
+        let todo = TestTodoBuilder::new()
+            .file_path("src/main.rs")
+            .line_number(7)
+            .original_text("let é = 1; // TODO: fix")
+            .column_start(12)
+            .column_end(24)
+            .build();
+        let log = format(&[todo]);
+        let location = &log["runs"][0]["results"][0]["locations"][0]["physicalLocation"];
+
+        assert_eq!(location["artifactLocation"]["uri"], "src/main.rs");
+        assert_eq!(location["artifactLocation"]["uriBaseId"], "%SRCROOT%");
+        assert_eq!(location["region"]["startLine"], 7);
+        assert_eq!(location["region"]["startColumn"], 12);
+        assert_eq!(location["region"]["endColumn"], 24);
+    }
+
+    #[test]
+    fn test_fingerprint_derived_from_id() {
+        let todo = create_test_todo("Task", TodoType::Todo, Some("run"), false);
+        let log = format(&[todo.clone()]); // clone: the id is compared below
+        let result = &log["runs"][0]["results"][0];
+
+        assert_eq!(result["partialFingerprints"][FINGERPRINT_KEY], todo.id);
+        assert_eq!(result["locations"][0]["logicalLocations"][0]["name"], "run");
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    fn test_analysis_in_properties(#[case] analysed: bool) {
+        let mut todo = create_test_todo("Task", TodoType::Todo, None, false);
+        if analysed {
+            todo.analysis = Some(AnalysisResult {
+                validity: Validity::Invalid,
+                reasoning: "Already done".to_string(),
+                is_resolved: true,
+                is_relevant: false,
+                is_actionable: false,
+                confidence: 0.9,
+                enrichment: String::new(),
+            });
+        }
+        let log = format(&[todo]);
+        let analysis = &log["runs"][0]["results"][0]["properties"]["analysis"];
+
+        if analysed {
+            assert_eq!(analysis["validity"], "Invalid");
+            assert_eq!(analysis["reasoning"], "Already done");
+            assert_eq!(analysis["isResolved"], true);
+        } else {
+            assert!(analysis.is_null());
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use std::path::Path;
+
+use minijinja::{Environment, UndefinedBehavior};
+use serde_json::{json, Map, Value};
+
+use super::jsonl::JsonlFormatter;
+use crate::{
+    comment::todo::{TodoComment, TodoType},
+    output::{
+        formatter::{error::FormatterError, Formatter},
+        ScanMetadata,
+    },
+};
+
+/// Renders a user-supplied Jinja template, for report layouts towl does not
+/// ship (wiki markup, chat messages, `AsciiDoc`, ...).
+///
+/// The template sees `towl.version`, `scan` (see [`ScanMetadata`], `none`
+/// if unset), `summary.total` and `summary.by_type`, and `groups`: one entry
+/// per TODO type in priority order, each with `type`, `name`, `priority`,
+/// `count` and `todos`. TODOs carry every field, as in JSON Lines output.
+/// Undefined variables are errors, so typos are reported with their line.
+#[derive(Default)]
+pub struct TemplateFormatter {
+    template: Option<(String, Environment<'static>)>,
+    pub metadata: Option<ScanMetadata>,
+}
+
+impl TemplateFormatter {
+    /// Compiles `source`, reporting syntax errors against `name`.
+    ///
+    /// # Errors
+    /// Returns `FormatterError::TemplateError` with the line of the first
+    /// syntax error.
+    pub(crate) fn load(&mut self, name: &Path, source: String) -> Result<(), FormatterError> {
+        let name = name.display().to_string();
+        let mut env = Environment::new();
+        env.set_undefined_behavior(UndefinedBehavior::Strict);
+        env.add_template_owned(name.clone(), source) // clone: the name also looks the template up
+            .map_err(|e| FormatterError::TemplateError(Self::describe(&e)))?;
+        self.template = Some((name, env));
+        Ok(())
+    }
+
+    /// `name:line: kind: detail`, followed by any underlying cause.
+    fn describe(error: &minijinja::Error) -> String {
+        let mut message = match (error.name(), error.line()) {
+            (Some(name), Some(line)) => format!("{name}:{line}: {}", error.kind()),
+            (Some(name), None) => format!("{name}: {}", error.kind()),
+            _ => error.kind().to_string(),
+        };
+        if let Some(detail) = error.detail() {
+            message.push_str(&format!(": {detail}"));
+        }
+        let mut cause = std::error::Error::source(error);
+        while let Some(inner) = cause {
+            message.push_str(&format!(" ({inner})"));
+            cause = inner.source();
+        }
+        message
+    }
+
+    fn context(
+        &self,
+        groups: &[(TodoType, Vec<&TodoComment>)],
+        total_count: usize,
+    ) -> Result<Value, FormatterError> {
+        let mut by_type = Map::new();
+        let mut group_values = Vec::with_capacity(groups.len());
+        for (todo_type, todos_of_type) in groups {
+            by_type.insert(todo_type.to_string(), json!(todos_of_type.len()));
+            let todos = todos_of_type
+                .iter()
+                .map(|todo| JsonlFormatter::record_value(todo))
+                .collect::<Result<Vec<_>, _>>()?;
+            group_values.push(json!({
+                "type": todo_type.to_string(),
+                "name": todo_type.as_filter_str(),
+                "priority": todo_type.priority(),
+                "count": todos_of_type.len(),
+                "todos": todos,
+            }));
+        }
+
+        Ok(json!({
+            "towl": { "version": env!("CARGO_PKG_VERSION") },
+            "scan": self.metadata,
+            "summary": { "total": total_count, "by_type": by_type },
+            "groups": group_values,
+        }))
+    }
+}
+
+impl Formatter for TemplateFormatter {
+    fn format(
+        &self,
+        groups: &[(TodoType, Vec<&TodoComment>)],
+        total_count: usize,
+    ) -> Result<Vec<String>, FormatterError> {
+        let (name, env) = self.template.as_ref().ok_or_else(|| {
+            FormatterError::TemplateError("no template given (use --template)".to_string())
+        })?;
+        let template = env
+            .get_template(name)
+            .map_err(|e| FormatterError::TemplateError(Self::describe(&e)))?;
+        let rendered = template
+            .render(self.context(groups, total_count)?)
+            .map_err(|e| FormatterError::TemplateError(Self::describe(&e)))?;
+
+        Ok(vec![rendered])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::formatter::formatters::test_helpers::create_test_todo;
+    use std::path::PathBuf;
+
+    fn render(source: &str, todos: &[TodoComment]) -> Result<String, FormatterError> {
+        let mut formatter = TemplateFormatter::default();
+        formatter.load(Path::new("report.tmpl"), source.to_string())?;
+        let groups = crate::output::Output::group_todos_by_type(todos);
+        formatter
+            .format(&groups, todos.len())
+            .map(|lines| lines.join("\n"))
+    }
+
+    #[test]
+    fn test_renders_groups_and_summary() {
+        let todos = vec![
+            create_test_todo("Later", TodoType::Todo, Some("run"), false),
+            create_test_todo("Broken", TodoType::Bug, None, false),
+        ];
+        let source = "\
+{{ summary.total }} total, {{ summary.by_type.BUG }} bug
+{% for group in groups %}h2. {{ group.type }} ({{ group.count }})
+{% for todo in group.todos %}* {{ todo.description }} - {{ todo.file_path }}:{{ todo.line_number }}{% if todo.function_context %} in {{ todo.function_context }}{% endif %}
+{% endfor %}{% endfor %}";
+
+        assert_eq!(
+            render(source, &todos).unwrap(),
+            "2 total, 1 bug\nh2. BUG (1)\n* Broken - test.rs:42\nh2. TODO (1)\n* Later - test.rs:42 in run\n"
+        );
+    }
+
+    #[test]
+    fn test_scan_metadata_in_context() {
+        let mut formatter = TemplateFormatter {
+            metadata: Some(ScanMetadata {
+                path: PathBuf::from("src"),
+                git_ref: Some("v1.0".to_string()),
+                files_scanned: 12,
+                ..ScanMetadata::default()
+            }),
+            ..TemplateFormatter::default()
+        };
+        formatter
+            .load(
+                Path::new("meta.tmpl"),
+                "{{ scan.path }}@{{ scan.git_ref }}: {{ scan.files_scanned }} files, towl {{ towl.version }}"
+                    .to_string(),
+            )
+            .unwrap();
+
+        let output = formatter.format(&[], 0).unwrap().join("\n");
+        assert_eq!(
+            output,
+            format!("src@v1.0: 12 files, towl {}", env!("CARGO_PKG_VERSION"))
+        );
+    }
+
+    #[test]
+    fn test_syntax_error_reports_line() {
+        let err = render("line one\n{% for x in groups %}\n{{ x.type }\n", &[]).unwrap_err();
+        let FormatterError::TemplateError(message) = err else {
+            panic!("expected a template error, got {err:?}");
+        };
+        assert!(
+            message.starts_with("report.tmpl:3: syntax error"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn test_undefined_variable_reports_line() {
+        let todos = vec![create_test_todo("Task", TodoType::Todo, None, false)];
+        let err = render(
+            "{% for group in groups %}\n{{ group.todos[0].descripton }}\n{% endfor %}",
+            &todos,
+        )
+        .unwrap_err();
+        let FormatterError::TemplateError(message) = err else {
+            panic!("expected a template error, got {err:?}");
+        };
+        assert!(
+            message.starts_with("report.tmpl:2: undefined value"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn test_missing_template_is_an_error() {
+        let err = TemplateFormatter::default().format(&[], 0).unwrap_err();
+        assert!(matches!(err, FormatterError::TemplateError(_)));
+    }
+}
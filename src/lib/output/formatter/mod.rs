@@ -4,8 +4,10 @@ pub mod formatters;
 use crate::comment::todo::{TodoComment, TodoType};
 use error::FormatterError;
 use formatters::{
-    csv::CsvFormatter, json::JsonFormatter, markdown::MarkdownFormatter, table::TableFormatter,
-    toml::TomlFormatter,
+    checkstyle::CheckstyleFormatter, csv::CsvFormatter, github_actions::GithubActionsFormatter,
+    html::HtmlFormatter, json::JsonFormatter, jsonl::JsonlFormatter, junit::JunitFormatter,
+    markdown::MarkdownFormatter, sarif::SarifFormatter, table::TableFormatter,
+    template::TemplateFormatter, toml::TomlFormatter,
 };
 
 pub(crate) trait Formatter {
@@ -16,7 +18,8 @@ pub(crate) trait Formatter {
     ///
     /// # Errors
     /// Returns `FormatterError::SerializationError` if serialization fails,
-    /// or `FormatterError::IntegerOverflow` if a count exceeds `i64` range.
+    /// `FormatterError::IntegerOverflow` if a count exceeds `i64` range, or
+    /// `FormatterError::TemplateError` if a user template fails to render.
     fn format(
         &self,
         groups: &[(TodoType, Vec<&TodoComment>)],
@@ -30,10 +33,17 @@ pub(crate) trait Formatter {
 /// dispatch, enabling zero-cost abstraction and eliminating object-safety
 /// constraints (no `Send + Sync` bounds needed).
 pub(crate) enum FormatterImpl {
+    Checkstyle(CheckstyleFormatter),
     Csv(CsvFormatter),
+    GithubActions(GithubActionsFormatter),
+    Html(HtmlFormatter),
     Json(JsonFormatter),
+    Jsonl(JsonlFormatter),
+    Junit(JunitFormatter),
     Markdown(MarkdownFormatter),
+    Sarif(SarifFormatter),
     Table(TableFormatter),
+    Template(TemplateFormatter),
     Toml(TomlFormatter),
 }
 
@@ -44,10 +54,17 @@ impl FormatterImpl {
         total_count: usize,
     ) -> Result<Vec<String>, FormatterError> {
         match self {
+            Self::Checkstyle(f) => f.format(groups, total_count),
             Self::Csv(f) => f.format(groups, total_count),
+            Self::GithubActions(f) => f.format(groups, total_count),
+            Self::Html(f) => f.format(groups, total_count),
             Self::Json(f) => f.format(groups, total_count),
+            Self::Jsonl(f) => f.format(groups, total_count),
+            Self::Junit(f) => f.format(groups, total_count),
             Self::Markdown(f) => f.format(groups, total_count),
+            Self::Sarif(f) => f.format(groups, total_count),
             Self::Table(f) => f.format(groups, total_count),
+            Self::Template(f) => f.format(groups, total_count),
             Self::Toml(f) => f.format(groups, total_count),
         }
     }
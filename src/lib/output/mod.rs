@@ -1,56 +1,88 @@
 //! Output formatting and writing for scan results.
 //!
-//! Supports JSON, CSV, TOML, Markdown, and terminal table formats. File-based
-//! formats write to a path; terminal formats write to stdout.
+//! Supports JSON, JSON Lines, CSV, TOML, Markdown, HTML, SARIF, JUnit XML, Checkstyle XML,
+//! GitHub Actions annotations, user templates and terminal table formats. Every format
+//! writes to stdout or to a file. JSON Lines can be written while the scan
+//! runs via [`Output::stream`].
 
 pub mod error;
 pub mod formatter;
+pub mod stream;
 pub mod writer;
 
 use std::path::Path;
 
 use error::TowlOutputError;
 use formatter::{
+    error::FormatterError,
     formatters::{
-        csv::CsvFormatter, json::JsonFormatter, markdown::MarkdownFormatter, table::TableFormatter,
-        toml::TomlFormatter,
+        checkstyle::CheckstyleFormatter, csv::CsvFormatter, github_actions::GithubActionsFormatter,
+        html::HtmlFormatter, json::JsonFormatter, jsonl::JsonlFormatter, junit::JunitFormatter,
+        markdown::MarkdownFormatter, sarif::SarifFormatter, table::TableFormatter,
+        template::TemplateFormatter, toml::TomlFormatter,
     },
-    FormatterImpl,
+    Formatter, FormatterImpl,
 };
 use writer::{
-    writers::{file::FileWriter, stdout::StdoutWriter},
-    WriterImpl,
+    writers::{append::AppendWriter, file::FileWriter, stdout::StdoutWriter},
+    Writer, WriterImpl,
 };
 
+use stream::TodoStream;
+
 use crate::{
     cli::OutputFormat,
     comment::todo::{TodoComment, TodoType},
+    github::SourceLinks,
 };
+use serde::Serialize;
 use std::{collections::HashMap, path::PathBuf};
+use tracing::warn;
+
+/// Output path meaning stdout, as in `--output -`.
+pub const STDOUT_PATH: &str = "-";
+
+/// Environment variable naming the GitHub Actions job summary file.
+pub const STEP_SUMMARY_ENV: &str = "GITHUB_STEP_SUMMARY";
 
-const TERMINAL_FORMAT_FILE_ERROR: &str = "Terminal format cannot write to file";
+/// Details of the scan that produced a report, for formats that show them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ScanMetadata {
+    /// The path that was scanned.
+    pub path: PathBuf,
+    /// The `--ref` scanned instead of the working tree, if any.
+    pub git_ref: Option<String>,
+    pub files_scanned: usize,
+    pub files_skipped: usize,
+    pub files_errored: usize,
+    pub duration_ms: u128,
+}
 
 /// Handles formatting and writing TODO comments to various output destinations.
 ///
-/// Supports multiple output formats (JSON, CSV, TOML, Markdown, Table) with
-/// appropriate writers (file or stdout) based on format constraints.
+/// Supports multiple output formats (JSON, JSON Lines, CSV, TOML, Markdown, HTML, SARIF, JUnit,
+/// Checkstyle, GitHub Actions, Table), each written to a file or stdout.
 pub struct Output {
     writer: WriterImpl,
     formatter: FormatterImpl,
+    /// Markdown job summary, appended to `$GITHUB_STEP_SUMMARY` by the
+    /// GitHub Actions format.
+    step_summary: Option<AppendWriter>,
 }
 
 impl Output {
     /// Creates a new output handler for the specified format and destination.
     ///
-    /// # Format Constraints
-    /// - `Terminal` and `Table`: Must output to stdout (`output_path` must be `None`)
-    /// - `Json`, `Csv`, `Toml`, `Markdown`: Require `output_path` with matching extension
+    /// Every format writes to stdout when `output_path` is `None` or
+    /// [`STDOUT_PATH`], and to the file otherwise. A file whose extension
+    /// does not match the format is still written, with a warning.
+    /// `GithubActions` also appends a Markdown job summary to the file named
+    /// by [`STEP_SUMMARY_ENV`] when it is set; set the `Template` format's
+    /// template with [`Output::with_template`].
     ///
     /// # Errors
-    /// Returns `TowlOutputError::InvalidOutputPath` if:
-    /// - Terminal/Table format is used with a file path
-    /// - File-based formats are used without an output path
-    /// - File extension doesn't match the expected format
+    /// Returns `TowlOutputError::UnableToWriteTodos` if the output path is
+    /// not a valid file path (for example, it contains `..`).
     ///
     /// # Example
     /// ```no_run
@@ -72,76 +104,136 @@ impl Output {
         output_format: OutputFormat,
         output_path: Option<PathBuf>,
     ) -> Result<Self, TowlOutputError> {
-        let (formatter, writer) = match output_format {
-            OutputFormat::Terminal => {
-                if output_path.is_some() {
-                    return Err(TowlOutputError::InvalidOutputPath(
-                        TERMINAL_FORMAT_FILE_ERROR.to_string(),
-                    ));
+        let formatter = match output_format {
+            OutputFormat::Json => FormatterImpl::Json(JsonFormatter),
+            OutputFormat::Jsonl => FormatterImpl::Jsonl(JsonlFormatter),
+            OutputFormat::Csv => FormatterImpl::Csv(CsvFormatter),
+            OutputFormat::Toml => FormatterImpl::Toml(TomlFormatter),
+            OutputFormat::Markdown => FormatterImpl::Markdown(MarkdownFormatter),
+            OutputFormat::Html => FormatterImpl::Html(HtmlFormatter::default()),
+            OutputFormat::Sarif => FormatterImpl::Sarif(SarifFormatter),
+            OutputFormat::Junit => FormatterImpl::Junit(JunitFormatter::default()),
+            OutputFormat::Checkstyle => FormatterImpl::Checkstyle(CheckstyleFormatter),
+            OutputFormat::GithubActions => FormatterImpl::GithubActions(GithubActionsFormatter),
+            OutputFormat::Template => FormatterImpl::Template(TemplateFormatter::default()),
+            OutputFormat::Terminal => FormatterImpl::Table(TableFormatter),
+        };
+        let writer = match output_path.filter(|path| path.as_os_str() != STDOUT_PATH) {
+            None => WriterImpl::Stdout(StdoutWriter::new()),
+            Some(path) => {
+                if let Some(warning) = Self::extension_warning(&path, output_format) {
+                    warn!("{warning}");
                 }
-                (
-                    FormatterImpl::Table(TableFormatter),
-                    WriterImpl::Stdout(StdoutWriter::new()),
+                WriterImpl::File(
+                    FileWriter::new(path).map_err(TowlOutputError::UnableToWriteTodos)?,
                 )
             }
-            OutputFormat::Json => Self::file_output(
-                output_path,
-                "JSON",
-                "json",
-                FormatterImpl::Json(JsonFormatter),
-            )?,
-            OutputFormat::Csv => {
-                Self::file_output(output_path, "CSV", "csv", FormatterImpl::Csv(CsvFormatter))?
-            }
-            OutputFormat::Toml => Self::file_output(
-                output_path,
-                "TOML",
-                "toml",
-                FormatterImpl::Toml(TomlFormatter),
-            )?,
-            OutputFormat::Markdown => Self::file_output(
-                output_path,
-                "Markdown",
-                "md",
-                FormatterImpl::Markdown(MarkdownFormatter),
-            )?,
         };
-        Ok(Self { writer, formatter })
+        let step_summary = if output_format == OutputFormat::GithubActions {
+            std::env::var_os(STEP_SUMMARY_ENV)
+                .filter(|path| !path.is_empty())
+                .map(|path| AppendWriter::new(PathBuf::from(path)))
+        } else {
+            None
+        };
+        Ok(Self {
+            writer,
+            formatter,
+            step_summary,
+        })
     }
 
-    fn file_output(
-        output_path: Option<PathBuf>,
-        format_name: &str,
-        extension: &str,
-        formatter: FormatterImpl,
-    ) -> Result<(FormatterImpl, WriterImpl), TowlOutputError> {
-        let path = output_path.ok_or_else(|| {
-            TowlOutputError::InvalidOutputPath(format!(
-                "{format_name} format requires an output file path"
-            ))
-        })?;
-        Self::validate_file_extension(&path, extension)?;
-        let writer = FileWriter::new(path).map_err(TowlOutputError::UnableToWriteTodos)?;
-        Ok((formatter, WriterImpl::File(writer)))
+    /// Sets the TODO types reported as failed test cases in JUnit output;
+    /// the others are skipped. Defaults to bugs and fixmes. Other formats
+    /// ignore it.
+    #[must_use]
+    pub fn with_junit_failures(mut self, failing: Vec<TodoType>) -> Self {
+        if let FormatterImpl::Junit(junit) = &mut self.formatter {
+            junit.failing = failing;
+        }
+        self
     }
 
-    fn validate_file_extension(path: &Path, expected_ext: &str) -> Result<(), TowlOutputError> {
-        path.extension().and_then(|e| e.to_str()).map_or_else(
-            || {
-                Err(TowlOutputError::InvalidOutputPath(format!(
-                    "Output file must have '{expected_ext}' extension"
-                )))
-            },
-            |ext| {
-                if ext.eq_ignore_ascii_case(expected_ext) {
-                    Ok(())
-                } else {
-                    Err(TowlOutputError::InvalidOutputPath(format!(
-                        "File extension '{ext}' does not match expected extension '{expected_ext}' for this format"
-                    )))
-                }
-            },
-        )
+    /// Sets where HTML report locations link to; without it they are plain
+    /// text. Other formats ignore it.
+    #[must_use]
+    pub fn with_source_links(mut self, links: Option<SourceLinks>) -> Self {
+        if let FormatterImpl::Html(html) = &mut self.formatter {
+            html.links = links;
+        }
+        self
+    }
+
+    /// Reads and compiles the template rendered by the `Template` format.
+    /// Other formats ignore it.
+    ///
+    /// # Errors
+    /// Returns `TowlOutputError::InvalidTemplate` if the file cannot be read
+    /// or has a syntax error, with the line of the error.
+    pub fn with_template(mut self, path: &Path) -> Result<Self, TowlOutputError> {
+        if let FormatterImpl::Template(template) = &mut self.formatter {
+            let source = std::fs::read_to_string(path).map_err(|e| {
+                TowlOutputError::InvalidTemplate(format!("{}: {e}", path.display()))
+            })?;
+            template.load(path, source).map_err(|e| match e {
+                FormatterError::TemplateError(message) => TowlOutputError::InvalidTemplate(message),
+                other => TowlOutputError::UnableToFormatTodos(other),
+            })?;
+        }
+        Ok(self)
+    }
+
+    /// Sets the scan details available to the `Template` format as `scan`.
+    /// Other formats ignore it.
+    #[must_use]
+    pub fn with_scan_metadata(mut self, metadata: ScanMetadata) -> Self {
+        if let FormatterImpl::Template(template) = &mut self.formatter {
+            template.metadata = Some(metadata);
+        }
+        self
+    }
+
+    /// Opens the destination for writing TODOs while the scan runs, for
+    /// formats that support it (JSON Lines); `None` for the others, which
+    /// need every TODO before they can be formatted. A file destination is
+    /// truncated and written in place rather than replaced atomically.
+    ///
+    /// # Errors
+    /// Returns `TowlOutputError::UnableToWriteTodos` if the file cannot be
+    /// created.
+    pub fn stream(&self) -> Result<Option<TodoStream>, TowlOutputError> {
+        if !matches!(self.formatter, FormatterImpl::Jsonl(_)) {
+            return Ok(None);
+        }
+        let out: Box<dyn std::io::Write + Send> = match &self.writer {
+            WriterImpl::Stdout(_) => Box::new(std::io::stdout()),
+            WriterImpl::File(file) => Box::new(
+                std::fs::File::create(file.path())
+                    .map_err(|e| TowlOutputError::UnableToWriteTodos(e.into()))?,
+            ),
+        };
+        Ok(Some(TodoStream::new(out)))
+    }
+
+    /// Why `path` looks wrong for `format`, if it does: its extension is
+    /// missing or names another format.
+    fn extension_warning(path: &Path, format: OutputFormat) -> Option<String> {
+        let expected = format.extension()?;
+        let Some(ext) = path.extension() else {
+            return Some(format!(
+                "Output file {} has no extension; expected '.{expected}'",
+                path.display()
+            ));
+        };
+        let ext = ext.to_string_lossy();
+        if ext.eq_ignore_ascii_case(expected) || OutputFormat::from_extension(&ext) == Some(format)
+        {
+            return None;
+        }
+        Some(format!(
+            "Output file {} has extension '.{ext}', expected '.{expected}'",
+            path.display()
+        ))
     }
 
     pub(crate) fn group_todos_by_type(todos: &[TodoComment]) -> Vec<(TodoType, Vec<&TodoComment>)> {
@@ -167,7 +259,8 @@ impl Output {
     /// Saves TODO comments using the configured formatter and writer.
     ///
     /// Formats the TODOs according to the output format and writes them to
-    /// the configured destination (file or stdout).
+    /// the configured destination (file or stdout), then appends the job
+    /// summary if the format has one.
     ///
     /// # Errors
     /// Returns `TowlOutputError` if formatting or writing fails.
@@ -195,7 +288,18 @@ impl Output {
         self.writer
             .write(formatted)
             .await
-            .map_err(TowlOutputError::UnableToWriteTodos)
+            .map_err(TowlOutputError::UnableToWriteTodos)?;
+
+        if let Some(summary) = &self.step_summary {
+            let markdown = MarkdownFormatter
+                .format(&groups, total_count)
+                .map_err(TowlOutputError::UnableToFormatTodos)?;
+            summary
+                .write(markdown)
+                .await
+                .map_err(TowlOutputError::UnableToWriteTodos)?;
+        }
+        Ok(())
     }
 }
 
@@ -209,15 +313,23 @@ mod tests {
 
     #[rstest]
     #[case(OutputFormat::Terminal, None, true)]
+    #[case(OutputFormat::Terminal, Some("-"), true)]
+    #[case(OutputFormat::Terminal, Some("file.txt"), true)]
     #[case(OutputFormat::Json, Some("todos.json"), true)]
-    #[case(OutputFormat::Csv, Some("todos.csv"), true)]
-    #[case(OutputFormat::Toml, Some("todos.toml"), true)]
-    #[case(OutputFormat::Markdown, Some("todos.md"), true)]
-    #[case(OutputFormat::Terminal, Some("file.txt"), false)]
-    #[case(OutputFormat::Json, None, false)]
-    #[case(OutputFormat::Csv, None, false)]
-    #[case(OutputFormat::Toml, None, false)]
-    #[case(OutputFormat::Markdown, None, false)]
+    #[case(OutputFormat::Json, Some("report.out"), true)]
+    #[case(OutputFormat::Json, None, true)]
+    #[case(OutputFormat::Jsonl, None, true)]
+    #[case(OutputFormat::Jsonl, Some("todos.json"), true)]
+    #[case(OutputFormat::Template, Some("report.wiki"), true)]
+    #[case(OutputFormat::Csv, None, true)]
+    #[case(OutputFormat::Toml, None, true)]
+    #[case(OutputFormat::Markdown, None, true)]
+    #[case(OutputFormat::Html, Some("report.htm"), true)]
+    #[case(OutputFormat::Sarif, None, true)]
+    #[case(OutputFormat::Junit, Some("report.json"), true)]
+    #[case(OutputFormat::Checkstyle, Some("checkstyle.xml"), true)]
+    #[case(OutputFormat::GithubActions, Some("annotations.txt"), true)]
+    #[case(OutputFormat::Json, Some("../todos.json"), false)]
     fn test_output_new_dispatch(
         #[case] format: OutputFormat,
         #[case] path: Option<&str>,
@@ -235,25 +347,36 @@ mod tests {
     }
 
     #[rstest]
-    #[case("todos.json", "json", true)]
-    #[case("todos.JSON", "json", true)]
-    #[case("todos.Json", "json", true)]
-    #[case("todos.csv", "csv", true)]
-    #[case("todos.toml", "toml", true)]
-    #[case("todos.md", "md", true)]
-    #[case("todos.txt", "json", false)]
-    #[case("todos.csv", "json", false)]
-    #[case("todos", "json", false)]
-    fn test_validate_file_extension(
+    #[case(OutputFormat::Jsonl, true)]
+    #[case(OutputFormat::Json, false)]
+    #[case(OutputFormat::Terminal, false)]
+    fn test_only_jsonl_streams(#[case] format: OutputFormat, #[case] streams: bool) {
+        let output = Output::new(format, None).unwrap();
+        assert_eq!(output.stream().unwrap().is_some(), streams);
+    }
+
+    #[rstest]
+    #[case("todos.json", OutputFormat::Json, false)]
+    #[case("todos.JSON", OutputFormat::Json, false)]
+    #[case("todos.markdown", OutputFormat::Markdown, false)]
+    #[case("report.htm", OutputFormat::Html, false)]
+    #[case("report.xml", OutputFormat::Checkstyle, false)]
+    #[case("notes.txt", OutputFormat::Terminal, false)]
+    #[case("report.wiki", OutputFormat::Template, false)]
+    #[case("todos.txt", OutputFormat::Json, true)]
+    #[case("todos.csv", OutputFormat::Json, true)]
+    #[case("todos", OutputFormat::Json, true)]
+    #[case("todos.json", OutputFormat::Jsonl, true)]
+    fn test_extension_warning(
         #[case] path: &str,
-        #[case] expected_ext: &str,
-        #[case] should_succeed: bool,
+        #[case] format: OutputFormat,
+        #[case] warns: bool,
     ) {
-        let result = Output::validate_file_extension(Path::new(path), expected_ext);
+        let warning = Output::extension_warning(Path::new(path), format);
         assert_eq!(
-            result.is_ok(),
-            should_succeed,
-            "validate_file_extension({path:?}, {expected_ext:?}) expected success={should_succeed}"
+            warning.is_some(),
+            warns,
+            "{path} as {format:?}: {warning:?}"
         );
     }
 
@@ -285,7 +408,6 @@ mod tests {
     fn test_grouping_sorted_by_priority_then_location() {
         use crate::comment::todo::test_support::TestTodoBuilder;
         use crate::output::formatter::formatters::json::JsonFormatter;
-        use crate::output::formatter::Formatter;
 
         let make = |todo_type, file: &str, line| {
             TestTodoBuilder::new()
@@ -338,9 +460,28 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_github_actions_appends_step_summary() {
+        let dir = tempfile::tempdir().unwrap();
+        let summary_path = dir.path().join("step_summary.md");
+        std::fs::write(&summary_path, "## Earlier step\n").unwrap();
+        let output = Output {
+            writer: WriterImpl::Stdout(StdoutWriter::new()),
+            formatter: FormatterImpl::GithubActions(GithubActionsFormatter),
+            step_summary: Some(AppendWriter::new(summary_path.clone())), // clone: read back below
+        };
+        let todos = vec![create_test_todo("Bug found", TodoType::Bug, None, false)];
+
+        output.save(&todos).await.unwrap();
+
+        let summary = std::fs::read_to_string(&summary_path).unwrap();
+        assert!(summary.starts_with("## Earlier step\n# TODO Comments"));
+        assert!(summary.contains("Bug found"));
+    }
+
     #[cfg(unix)]
     #[test]
-    fn test_validate_file_extension_non_utf8() {
+    fn test_extension_warning_non_utf8() {
         use std::ffi::OsStr;
         use std::os::unix::ffi::OsStrExt;
 
@@ -349,7 +490,7 @@ mod tests {
         let os_str = OsStr::from_bytes(invalid_bytes);
         let path = PathBuf::from(os_str);
 
-        let result = Output::validate_file_extension(&path, "json");
-        assert!(result.is_err(), "Non-UTF-8 extension should be rejected");
+        let warning = Output::extension_warning(&path, OutputFormat::Json);
+        assert!(warning.is_some(), "Non-UTF-8 extension should warn");
     }
 }
@@ -0,0 +1,140 @@
+use std::io::Write;
+
+use super::{
+    error::TowlOutputError, formatter::formatters::jsonl::JsonlFormatter,
+    writer::error::WriterError,
+};
+use crate::comment::todo::TodoComment;
+
+/// Writes TODOs as JSON Lines while a scan is still running.
+///
+/// Obtained from [`Output::stream`](super::Output::stream). Each batch is
+/// flushed as it is written, so a reader on the other end of a pipe sees
+/// results as files finish. Writing never fails mid-scan: the first error
+/// stops further output and is returned by [`TodoStream::finish`].
+pub struct TodoStream {
+    out: Box<dyn Write + Send>,
+    written: usize,
+    error: Option<TowlOutputError>,
+}
+
+impl TodoStream {
+    pub(crate) fn new(out: Box<dyn Write + Send>) -> Self {
+        Self {
+            out,
+            written: 0,
+            error: None,
+        }
+    }
+
+    /// Writes one line per TODO and flushes.
+    pub fn write<'a>(&mut self, todos: impl IntoIterator<Item = &'a TodoComment>) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(e) = self.try_write(todos) {
+            self.error = Some(e);
+        }
+    }
+
+    fn try_write<'a>(
+        &mut self,
+        todos: impl IntoIterator<Item = &'a TodoComment>,
+    ) -> Result<(), TowlOutputError> {
+        for todo in todos {
+            let line = JsonlFormatter::record(todo)?;
+            writeln!(self.out, "{line}").map_err(WriterError::IoError)?;
+            self.written += 1;
+        }
+        self.out.flush().map_err(WriterError::IoError)?;
+        Ok(())
+    }
+
+    /// Ends the stream, returning how many TODOs were written.
+    ///
+    /// # Errors
+    /// Returns the first error met while writing, if any.
+    pub fn finish(mut self) -> Result<usize, TowlOutputError> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        self.out.flush().map_err(WriterError::IoError)?;
+        Ok(self.written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comment::todo::TodoType;
+    use crate::output::formatter::formatters::test_helpers::create_test_todo;
+    use std::sync::{Arc, Mutex};
+
+    /// Shared buffer so the test can read what the stream wrote.
+    #[derive(Clone, Default)]
+    struct Sink(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Sink {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    struct Broken;
+
+    impl Write for Broken {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::from(std::io::ErrorKind::BrokenPipe))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_batches_are_written_as_they_arrive() {
+        let sink = Sink::default();
+        let mut stream = TodoStream::new(Box::new(sink.clone())); // clone: shares the buffer
+        let first = create_test_todo("First", TodoType::Todo, None, false);
+        let second = create_test_todo("Second", TodoType::Bug, None, false);
+
+        stream.write([&first]);
+        assert_eq!(
+            sink.0
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|&&b| b == b'\n')
+                .count(),
+            1
+        );
+        stream.write([&second]);
+
+        assert_eq!(stream.finish().unwrap(), 2);
+        let written = String::from_utf8(sink.0.lock().unwrap().clone()).unwrap(); // clone: buffer stays shared
+        let lines: Vec<&str> = written.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains(r#""description":"First""#));
+        assert!(lines[1].contains(r#""description":"Second""#));
+    }
+
+    #[test]
+    fn test_first_error_is_reported_on_finish() {
+        let mut stream = TodoStream::new(Box::new(Broken));
+        let todo = create_test_todo("Task", TodoType::Todo, None, false);
+
+        stream.write([&todo]);
+        stream.write([&todo]);
+
+        assert!(matches!(
+            stream.finish(),
+            Err(TowlOutputError::UnableToWriteTodos(WriterError::IoError(_)))
+        ));
+    }
+}
@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+use tokio::io::AsyncWriteExt;
+use tracing::info;
+
+use crate::output::writer::{error::WriterError, Writer};
+
+/// Appends to a file that another process owns, such as the GitHub Actions
+/// `$GITHUB_STEP_SUMMARY`, creating it if needed. Unlike [`FileWriter`],
+/// the path is not confined to the working directory and earlier content
+/// is kept.
+///
+/// [`FileWriter`]: super::file::FileWriter
+pub(crate) struct AppendWriter {
+    path: PathBuf,
+}
+
+impl AppendWriter {
+    #[must_use]
+    pub(crate) const fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl Writer for AppendWriter {
+    async fn write(&self, content: Vec<String>) -> Result<(), WriterError> {
+        let mut buf = String::with_capacity(content.iter().map(|s| s.len() + 1).sum());
+        for item in &content {
+            buf.push_str(item);
+            buf.push('\n');
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(buf.as_bytes()).await?;
+        file.flush().await?;
+
+        info!("Appended todos to file: {}", self.path.display());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_append_keeps_existing_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("summary.md");
+        std::fs::write(&path, "before\n").unwrap();
+
+        let writer = AppendWriter::new(path.clone()); // clone: read back below
+        writer.write(vec!["one".to_string()]).await.unwrap();
+        writer.write(vec!["two".to_string()]).await.unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "before\none\ntwo\n"
+        );
+    }
+}
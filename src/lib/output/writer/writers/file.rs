@@ -22,6 +22,11 @@ impl FileWriter {
         Ok(Self { path: resolved })
     }
 
+    /// The validated destination path.
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
     fn resolve_symlinks(path: PathBuf) -> PathBuf {
         if let Some(parent) = path.parent() {
             match parent.canonicalize() {
@@ -1,2 +1,3 @@
+pub mod append;
 pub mod file;
 pub mod stdout;
@@ -15,4 +15,8 @@ pub enum TowlParserError {
     PatternTooLong(usize, usize),
     #[error("Total pattern count {count} exceeds maximum of {max_allowed} across all categories")]
     TooManyTotalPatterns { count: usize, max_allowed: usize },
+    #[error("Filename pattern {0} is not a valid glob: {1}")]
+    InvalidFilenamePattern(String, globset::Error),
+    #[error("Could not build keyword prefilter: {0}")]
+    InvalidKeywordSet(aho_corasick::BuildError),
 }
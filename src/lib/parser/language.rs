@@ -0,0 +1,379 @@
+use std::path::Path;
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use regex::Regex;
+
+use crate::config::{BlockComment, LanguageProfile};
+
+use super::error::TowlParserError;
+use super::region::BlockComments;
+use super::types::Parser;
+
+/// A compiled [`LanguageProfile`].
+pub(super) struct Language {
+    extensions: Vec<String>,
+    filenames: GlobSet,
+    interpreters: Vec<String>,
+    pub(super) comment_patterns: Vec<Regex>,
+    pub(super) placeholder_patterns: Vec<Regex>,
+    pub(super) block_comments: Vec<BlockComment>,
+    pub(super) line_comments: Vec<String>,
+}
+
+impl Language {
+    pub(super) fn new(profile: &LanguageProfile) -> Result<Self, TowlParserError> {
+        let mut filenames = GlobSetBuilder::new();
+        for pattern in &profile.filenames {
+            let glob = GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .build()
+                .map_err(|e| TowlParserError::InvalidFilenamePattern(pattern.to_string(), e))?; // clone: error owns pattern
+            filenames.add(glob);
+        }
+        let filenames = filenames.build().map_err(|e| {
+            TowlParserError::InvalidFilenamePattern(profile.filenames.join(", "), e)
+        })?;
+
+        let comment_patterns = profile
+            .comment_prefixes
+            .iter()
+            .map(|p| Parser::build_regex(p))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let placeholder_patterns = profile
+            .placeholder_patterns
+            .iter()
+            .map(|p| Parser::build_regex(p))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            extensions: profile.extensions.clone(), // clone: compiled language owns its matchers
+            filenames,
+            interpreters: profile.interpreters.clone(), // clone: compiled language owns its matchers
+            comment_patterns,
+            placeholder_patterns,
+            block_comments: profile.block_comments.clone(), // clone: compiled language owns its delimiters
+            line_comments: profile.line_comments.clone(), // clone: compiled language owns its delimiters
+        })
+    }
+
+    fn matches_extension(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| self.extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+    }
+
+    fn matches_filename(&self, path: &Path) -> bool {
+        path.file_name()
+            .is_some_and(|name| self.filenames.is_match(Path::new(name)))
+    }
+
+    fn matches_interpreter(&self, interpreter: &str) -> bool {
+        self.interpreters.iter().any(|known| {
+            // python3.12 should still match a python3 or python profile
+            interpreter
+                .strip_prefix(known.as_str())
+                .is_some_and(|version| version.chars().all(|c| c.is_ascii_digit() || c == '.'))
+        })
+    }
+}
+
+/// Extracts the interpreter name from a shebang line, looking through `env`.
+///
+/// `#!/usr/bin/env -S python3 -u` and `#!/usr/bin/python3` both yield `python3`.
+pub(crate) fn shebang_interpreter(first_line: &str) -> Option<&str> {
+    let mut words = first_line.strip_prefix("#!")?.split_whitespace();
+    let program = words.next()?;
+    let name = program.rsplit('/').next()?;
+    if name != "env" {
+        return Some(name);
+    }
+    words
+        .find(|w| !w.starts_with('-') && !w.contains('='))
+        .and_then(|w| w.rsplit('/').next())
+}
+
+impl Parser {
+    /// Returns true if the file name matches a configured language's filename globs.
+    pub(crate) fn matches_language_filename(&self, path: &Path) -> bool {
+        self.languages.iter().any(|l| l.matches_filename(path))
+    }
+
+    /// Returns true if the shebang line names a configured language's interpreter.
+    pub(crate) fn matches_language_shebang(&self, first_line: &str) -> bool {
+        shebang_interpreter(first_line)
+            .is_some_and(|interp| self.languages.iter().any(|l| l.matches_interpreter(interp)))
+    }
+
+    /// Resolves the language of a file: filename globs first, then extension,
+    /// then the shebang on the first line.
+    pub(super) fn language_for(&self, path: &Path, content: &str) -> Option<&Language> {
+        self.languages
+            .iter()
+            .find(|l| l.matches_filename(path))
+            .or_else(|| self.languages.iter().find(|l| l.matches_extension(path)))
+            .or_else(|| {
+                let interp = shebang_interpreter(content.lines().next()?)?;
+                self.languages
+                    .iter()
+                    .find(|l| l.matches_interpreter(interp))
+            })
+    }
+
+    pub(super) fn comment_patterns_for(&self, path: &Path, content: &str) -> &[Regex] {
+        match self.language_for(path, content) {
+            Some(language) if !language.comment_patterns.is_empty() => &language.comment_patterns,
+            _ => &self.comment_patterns,
+        }
+    }
+
+    /// The block comment delimiters and line comment tokens of the file's
+    /// language, if any.
+    pub(super) fn block_comments_for(&self, path: &Path, content: &str) -> BlockComments<'_> {
+        self.language_for(path, content)
+            .map_or(BlockComments::new(&[], &[]), |language| {
+                BlockComments::new(&language.block_comments, &language.line_comments)
+            })
+    }
+
+    /// The placeholder patterns of the file's language, or none when
+    /// placeholder detection is off.
+    pub(super) fn placeholder_patterns_for(&self, path: &Path, content: &str) -> &[Regex] {
+        if !self.detect_placeholders {
+            return &[];
+        }
+        self.language_for(path, content)
+            .map_or(&[], |language| &language.placeholder_patterns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comment::todo::TodoType;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("#!/usr/bin/env python3", Some("python3"))]
+    #[case("#!/usr/bin/env -S python3 -u", Some("python3"))]
+    #[case("#!/usr/bin/env FOO=1 node", Some("node"))]
+    #[case("#!/bin/bash -e", Some("bash"))]
+    #[case("#! /bin/sh", Some("sh"))]
+    #[case("#!/usr/bin/env", None)]
+    #[case("# not a shebang", None)]
+    #[case("", None)]
+    fn test_shebang_interpreter(#[case] line: &str, #[case] expected: Option<&str>) {
+        assert_eq!(shebang_interpreter(line), expected);
+    }
+
+    #[rstest]
+    #[case("Dockerfile", "", "#", true)]
+    #[case("build/Dockerfile.dev", "", "#", true)]
+    #[case("Jenkinsfile", "", "//", true)]
+    #[case(".bashrc", "", "#", true)]
+    #[case("deploy", "#!/usr/bin/env python3.12\n", "#", true)]
+    #[case("deploy", "#!/usr/bin/env python3.12\n", "//", false)]
+    #[case("tool.sh", "", "//", false)]
+    #[case("main.rs", "", "//", true)]
+    fn test_comment_syntax_follows_language(
+        #[case] path: &str,
+        #[case] header: &str,
+        #[case] prefix: &str,
+        #[case] detected: bool,
+    ) {
+        let mut config = crate::config::test_parsing_config();
+        config.languages = crate::config::ParsingConfig::default().languages;
+        let parser = Parser::new(&config).unwrap();
+
+        let content = format!("{header}{prefix} TODO: language aware");
+        let todos = parser.parse(Path::new(path), &content).unwrap();
+
+        assert_eq!(!todos.is_empty(), detected, "{path}: {content}");
+    }
+
+    fn placeholder_parser(detect: bool) -> Parser {
+        let mut config = crate::config::test_parsing_config();
+        config.languages = crate::config::ParsingConfig::default().languages;
+        config.detect_placeholders = detect;
+        Parser::new(&config).unwrap()
+    }
+
+    #[rstest]
+    #[case("lib.rs", "    todo!()", Some("todo!"))]
+    #[case(
+        "lib.rs",
+        r#"    todo!("wire up the cache")"#,
+        Some("wire up the cache")
+    )]
+    #[case("lib.rs", r#"unimplemented!("tls {}", version)"#, Some("tls {}"))]
+    #[case("lib.rs", "fn f() -> u8 { unimplemented!() }", Some("unimplemented!"))]
+    #[case("lib.rs", "    // todo!() is fine in a comment", None)]
+    #[case("lib.rs", "let todo = 1;", None)]
+    #[case("lib.rs", "    todo!() // later", Some("todo!"))]
+    #[case("lib.rs", "    unimplemented!(); # fixme", Some("unimplemented!"))]
+    #[case("lib.rs", r#"    todo!("a // b")"#, Some("a // b"))]
+    #[case("lib.rs", "/* old */ todo!()", Some("todo!"))]
+    #[case("lib.rs", r#"let hint = "call todo!() here";"#, None)]
+    #[case("lib.rs", r##"let hint = r#"todo!()"#;"##, None)]
+    #[case("lib.rs", "/* todo!() */ let x = 1;", None)]
+    #[case("app.py", r#"msg = "raise NotImplementedError""#, None)]
+    #[case("app.py", "    return 1  # raise NotImplementedError later", None)]
+    #[case(
+        "app.py",
+        "    raise NotImplementedError  # later",
+        Some("raise NotImplementedError")
+    )]
+    #[case(
+        "app.py",
+        "    raise NotImplementedError",
+        Some("raise NotImplementedError")
+    )]
+    #[case(
+        "app.py",
+        "    raise NotImplementedError('subclass')",
+        Some("subclass")
+    )]
+    #[case(
+        "app.py",
+        r#"    raise NotImplementedError("")"#,
+        Some("raise NotImplementedError")
+    )]
+    #[case(
+        "App.java",
+        r#"throw new UnsupportedOperationException("TODO");"#,
+        Some("TODO")
+    )]
+    #[case(
+        "App.java",
+        "throw new UnsupportedOperationException();",
+        Some("throw new UnsupportedOperationException")
+    )]
+    #[case("tool.sh", "todo!()", None)]
+    fn test_placeholders_follow_language(
+        #[case] path: &str,
+        #[case] line: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let todos = placeholder_parser(true)
+            .parse(Path::new(path), line)
+            .unwrap();
+
+        let found: Vec<_> = todos
+            .iter()
+            .filter(|t| t.todo_type == TodoType::Placeholder)
+            .map(|t| t.description.as_str())
+            .collect();
+        assert_eq!(
+            found,
+            expected.into_iter().collect::<Vec<_>>(),
+            "{path}: {line}"
+        );
+    }
+
+    #[test]
+    fn test_placeholders_are_opt_in() {
+        let content = "fn f() {\n    todo!(\"later\")\n}\n";
+        assert!(placeholder_parser(false)
+            .parse(Path::new("lib.rs"), content)
+            .unwrap()
+            .is_empty());
+
+        let todos = placeholder_parser(true)
+            .parse(Path::new("lib.rs"), content)
+            .unwrap();
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].line_number, 2);
+        assert_eq!(todos[0].column_start, 4);
+        assert_eq!(todos[0].function_context.as_deref(), Some("f:1"));
+    }
+
+    #[rstest]
+    #[case("    not_implemented \"deploy\"", Some("deploy"))]
+    #[case("    not_implemented \"deploy\"  # later", Some("deploy"))]
+    #[case("    exit 0  # not_implemented \"deploy\"", None)]
+    #[case(
+        "    echo \"# not a comment\"; not_implemented",
+        Some("not_implemented")
+    )]
+    fn test_placeholders_skip_shell_trailing_comments(
+        #[case] line: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let mut config = crate::config::test_parsing_config();
+        config.languages = crate::config::ParsingConfig::default().languages;
+        config.detect_placeholders = true;
+        if let Some(shell) = config.languages.get_mut("shell") {
+            shell.placeholder_patterns = vec![r#"\bnot_implemented\b\s*("[^"]*")?"#.to_string()];
+        }
+        let todos = Parser::new(&config)
+            .unwrap()
+            .parse(Path::new("deploy.sh"), line)
+            .unwrap();
+
+        let found: Vec<_> = todos.iter().map(|t| t.description.as_str()).collect();
+        assert_eq!(found, expected.into_iter().collect::<Vec<_>>(), "{line}");
+    }
+
+    #[test]
+    fn test_placeholder_and_comment_on_one_line() {
+        let todos = placeholder_parser(true)
+            .parse(Path::new("lib.rs"), "    todo!() // TODO: wire up")
+            .unwrap();
+
+        let found: Vec<_> = todos
+            .iter()
+            .map(|t| (t.todo_type, t.description.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (TodoType::Placeholder, "todo!"),
+                (TodoType::Todo, "wire up")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_placeholders_in_block_comments_ignored() {
+        let content = "/*
+    todo!()
+*/
+unimplemented!()
+";
+        let todos = placeholder_parser(true)
+            .parse(Path::new("lib.rs"), content)
+            .unwrap();
+
+        let lines: Vec<_> = todos.iter().map(|t| t.line_number).collect();
+        assert_eq!(lines, [4]);
+    }
+
+    #[test]
+    fn test_comment_todos_still_found_with_placeholders() {
+        let content = "// TODO: comment\nunimplemented!()\n";
+        let todos = placeholder_parser(true)
+            .parse(Path::new("lib.rs"), content)
+            .unwrap();
+
+        let types: Vec<_> = todos.iter().map(|t| t.todo_type).collect();
+        assert_eq!(types, [TodoType::Todo, TodoType::Placeholder]);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_placeholder_message_becomes_description(
+            indent in "[ \t]{0,8}",
+            macro_name in prop::sample::select(vec!["todo", "unimplemented"]),
+            message in "[a-zA-Z0-9][a-zA-Z0-9 .,:-]{0,60}",
+        ) {
+            let line = format!("{indent}{macro_name}!(\"{message}\")");
+            let todos = placeholder_parser(true).parse(Path::new("lib.rs"), &line).unwrap();
+
+            prop_assert_eq!(todos.len(), 1);
+            prop_assert_eq!(todos[0].todo_type, TodoType::Placeholder);
+            prop_assert_eq!(todos[0].description.as_str(), message.trim());
+            prop_assert_eq!(todos[0].column_start, indent.len());
+        }
+    }
+}
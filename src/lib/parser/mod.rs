@@ -2,10 +2,18 @@
 //!
 //! Compiles comment-prefix and TODO-keyword patterns from [`crate::config::ParsingConfig`]
 //! into a reusable [`Parser`](types::Parser) that produces [`TodoComment`](crate::comment::todo::TodoComment) values.
+//! Comment syntax is chosen per file from the configured language profiles,
+//! including multi-line block comments and docstrings.
+//! A keyword prefilter rejects files and lines without any marker keyword
+//! before the regexes run.
 
 mod context;
 pub mod error;
+mod language;
 mod pattern;
+mod prefilter;
+mod region;
 mod types;
 
+pub(crate) use pattern::MAX_TOTAL_PATTERNS;
 pub(crate) use types::*;
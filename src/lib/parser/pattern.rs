@@ -8,7 +8,7 @@ use super::error::TowlParserError;
 // time cannot be rejected later at scanner construction
 pub(super) const MAX_PATTERN_LENGTH: usize = crate::config::MAX_CONFIG_STRING_LENGTH;
 pub(super) const REGEX_SIZE_LIMIT: usize = 262_144;
-pub(super) const MAX_TOTAL_PATTERNS: usize = 50;
+pub(crate) const MAX_TOTAL_PATTERNS: usize = 50;
 
 pub(super) struct Pattern {
     pub(super) regex: Regex,
@@ -18,7 +18,11 @@ pub(super) struct Pattern {
 use super::types::Parser;
 
 impl Parser {
-    pub(super) fn build_regex(pattern: &str) -> Result<Regex, TowlParserError> {
+    /// Compiles a config pattern with the parser's length and size limits.
+    ///
+    /// # Errors
+    /// Returns `TowlParserError::PatternTooLong` or `TowlParserError::InvalidRegexPattern`.
+    pub(crate) fn build_regex(pattern: &str) -> Result<Regex, TowlParserError> {
         if pattern.len() > MAX_PATTERN_LENGTH {
             return Err(TowlParserError::PatternTooLong(
                 pattern.len(),
@@ -0,0 +1,133 @@
+use aho_corasick::AhoCorasick;
+use regex_syntax::hir::literal::{ExtractKind, Extractor, Seq};
+
+use super::error::TowlParserError;
+
+/// Single-pass keyword check run before any regex.
+///
+/// The keywords are literals extracted from the TODO patterns, such that
+/// every match of a pattern starts (or ends) with one of them, so a line
+/// without any keyword cannot match. Patterns with no such literal, such as
+/// `.*`, disable the prefilter.
+pub(super) struct KeywordPrefilter {
+    automaton: AhoCorasick,
+}
+
+impl KeywordPrefilter {
+    /// Builds a prefilter for `patterns`, or `None` if some pattern has no
+    /// required literal and every line must be searched.
+    pub(super) fn new<'a>(
+        patterns: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Option<Self>, TowlParserError> {
+        let mut keywords = Vec::new();
+        for pattern in patterns {
+            let Some(literals) = Self::required_literals(pattern) else {
+                return Ok(None);
+            };
+            keywords.extend(literals);
+        }
+        keywords.sort_unstable();
+        keywords.dedup();
+
+        let automaton = AhoCorasick::builder()
+            .ascii_case_insensitive(true)
+            .build(&keywords)
+            .map_err(TowlParserError::InvalidKeywordSet)?;
+
+        Ok(Some(Self { automaton }))
+    }
+
+    /// Literals one of which every match of `pattern` starts with, else
+    /// ends with; `None` if there is no finite, non-empty set of either.
+    fn required_literals(pattern: &str) -> Option<Vec<Vec<u8>>> {
+        let hir = regex_syntax::parse(pattern).ok()?;
+        [ExtractKind::Prefix, ExtractKind::Suffix]
+            .into_iter()
+            .find_map(|kind| {
+                let seq: Seq = Extractor::new().kind(kind).extract(&hir);
+                let literals = seq.literals()?;
+                (!literals.is_empty() && literals.iter().all(|lit| !lit.is_empty()))
+                    .then(|| literals.iter().map(|lit| lit.as_bytes().to_vec()).collect())
+            })
+    }
+
+    pub(super) fn is_match(&self, haystack: &str) -> bool {
+        self.automaton.is_match(haystack)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    const DEFAULT_PATTERNS: [&str; 5] = [
+        r"(?i)\bTODO:\s*(.*)",
+        r"(?i)\bFIXME:\s*(.*)",
+        r"(?i)\bHACK:\s*(.*)",
+        r"(?i)\bNOTE:\s*(.*)",
+        r"(?i)\bBUG:\s*(.*)",
+    ];
+
+    fn prefilter(patterns: &[&str]) -> KeywordPrefilter {
+        KeywordPrefilter::new(patterns.iter().copied())
+            .unwrap()
+            .expect("patterns have required literals")
+    }
+
+    #[rstest]
+    #[case("// TODO: x", true)]
+    #[case("# fixme: lowercase", true)]
+    #[case("/* Hack: */", true)]
+    #[case("let x = 5;", false)]
+    #[case("", false)]
+    fn test_prefilter_matches_keywords(#[case] line: &str, #[case] expected: bool) {
+        let prefilter = prefilter(&DEFAULT_PATTERNS[..3]);
+        assert_eq!(prefilter.is_match(line), expected);
+    }
+
+    #[test]
+    fn test_prefilter_only_knows_configured_patterns() {
+        let prefilter = prefilter(&[r"(?i)\bBUG:\s*(.*)"]);
+        assert!(prefilter.is_match("// BUG: x"));
+        assert!(!prefilter.is_match("// TODO: x"));
+    }
+
+    #[test]
+    fn test_keywords_come_from_the_pattern_not_the_type() {
+        let prefilter = prefilter(&[r"(?i)\bTODO\b|\bXXX\b"]);
+        assert!(prefilter.is_match("// XXX: foo"));
+        assert!(prefilter.is_match("// todo later"));
+        assert!(!prefilter.is_match("// nothing here"));
+    }
+
+    #[test]
+    fn test_suffix_literals_are_used_without_a_prefix() {
+        let prefilter = prefilter(&[r".*TODO"]);
+        assert!(prefilter.is_match("anything TODO"));
+        assert!(!prefilter.is_match("nothing"));
+    }
+
+    #[rstest]
+    #[case(r"(.*)")]
+    #[case(r"\w+")]
+    #[case(r"TODO|.*")]
+    fn test_pattern_without_literal_disables_prefilter(#[case] pattern: &str) {
+        let result = KeywordPrefilter::new([r"(?i)\bTODO:\s*(.*)", pattern]).unwrap();
+        assert!(result.is_none(), "{pattern} should disable the prefilter");
+    }
+
+    proptest! {
+        #[test]
+        fn prop_prefilter_never_rejects_keyword_lines(
+            prefix in "[^\n]{0,30}",
+            keyword in prop::sample::select(vec!["TODO:", "fixme:", "Hack:", "nOtE:", "bug:"]),
+            suffix in "[^\n]{0,30}",
+        ) {
+            let prefilter = prefilter(&DEFAULT_PATTERNS);
+            let line = format!("{prefix}{keyword}{suffix}");
+            prop_assert!(prefilter.is_match(&line), "Rejected: {}", line);
+        }
+    }
+}
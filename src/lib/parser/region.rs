@@ -0,0 +1,382 @@
+use crate::config::BlockComment;
+
+/// Tracks multi-line comment regions across the lines of a file.
+///
+/// Delimiters are matched literally, left to right. While a region is open
+/// only its own `end` (and, for nested comments, its own `start`) is looked
+/// for, so a `'''` inside a `"""` docstring does not close it. Outside a
+/// region, string, raw string and character literals are skipped, so a
+/// delimiter inside one, as in `"target/*"`, does not open a region, and the
+/// rest of a line after a line comment token such as `//` is ignored.
+pub(super) struct BlockComments<'a> {
+    delimiters: &'a [BlockComment],
+    line_comments: &'a [String],
+}
+
+/// What is open at the end of a line: a comment region (which delimiter
+/// pair and how deeply nested) or a raw string.
+#[derive(Debug, Clone, Copy)]
+enum Open {
+    Comment {
+        delimiter: usize,
+        depth: usize,
+    },
+    /// A raw string closed by `"` and this many `#`s.
+    RawString {
+        hashes: usize,
+    },
+}
+
+impl<'a> BlockComments<'a> {
+    pub(super) const fn new(delimiters: &'a [BlockComment], line_comments: &'a [String]) -> Self {
+        Self {
+            delimiters,
+            line_comments,
+        }
+    }
+
+    /// Returns, for each line, whether any part of it lies in a block
+    /// comment. Empty when there are no delimiters.
+    pub(super) fn comment_lines(&self, lines: &[&str]) -> Vec<bool> {
+        if self.delimiters.is_empty() {
+            return Vec::new();
+        }
+
+        let mut open: Option<Open> = None;
+        lines
+            .iter()
+            .map(|line| {
+                let mut touched = matches!(open, Some(Open::Comment { .. }));
+                open = self.scan_line(line.as_bytes(), open, &mut touched, None);
+                touched
+            })
+            .collect()
+    }
+
+    /// Returns each line with block and line comments and string, raw
+    /// string and character literals replaced by spaces, so byte offsets still match
+    /// the original line.
+    pub(super) fn code_lines(&self, lines: &[&str]) -> Vec<String> {
+        let mut open: Option<Open> = None;
+        lines
+            .iter()
+            .map(|line| {
+                let mut code = line.as_bytes().to_vec();
+                open = self.scan_line(line.as_bytes(), open, &mut false, Some(&mut code));
+                // only whole UTF-8 sequences are blanked, so this cannot fail
+                String::from_utf8(code).unwrap_or_default()
+            })
+            .collect()
+    }
+
+    /// Scans one line, returning what is open at its end. With `code`,
+    /// blanks every byte that lies in a comment or literal.
+    fn scan_line(
+        &self,
+        line: &[u8],
+        mut open: Option<Open>,
+        touched: &mut bool,
+        mut code: Option<&mut [u8]>,
+    ) -> Option<Open> {
+        let mut blank = |range: std::ops::Range<usize>| {
+            if let Some(code) = code.as_deref_mut() {
+                code[range].fill(b' ');
+            }
+        };
+        let mut pos = 0;
+        while pos < line.len() {
+            let rest = &line[pos..];
+            match open {
+                Some(Open::Comment { delimiter, depth }) => {
+                    let pair = &self.delimiters[delimiter];
+                    if rest.starts_with(pair.end.as_bytes()) {
+                        blank(pos..pos + pair.end.len());
+                        pos += pair.end.len();
+                        open = depth
+                            .checked_sub(1)
+                            .filter(|&d| d > 0)
+                            .map(|depth| Open::Comment { delimiter, depth });
+                        continue;
+                    }
+                    if pair.nested && rest.starts_with(pair.start.as_bytes()) {
+                        blank(pos..pos + pair.start.len());
+                        pos += pair.start.len();
+                        open = Some(Open::Comment {
+                            delimiter,
+                            depth: depth.saturating_add(1),
+                        });
+                        continue;
+                    }
+                }
+                Some(Open::RawString { hashes }) => {
+                    if let Some(len) = raw_string_end(rest, hashes) {
+                        blank(pos..pos + len);
+                        pos += len;
+                        open = None;
+                        continue;
+                    }
+                }
+                None => {
+                    let opened = self
+                        .delimiters
+                        .iter()
+                        .position(|d| rest.starts_with(d.start.as_bytes()));
+                    if let Some(index) = opened {
+                        *touched = true;
+                        blank(pos..pos + self.delimiters[index].start.len());
+                        pos += self.delimiters[index].start.len();
+                        open = Some(Open::Comment {
+                            delimiter: index,
+                            depth: 1,
+                        });
+                        continue;
+                    }
+                    if self
+                        .line_comments
+                        .iter()
+                        .any(|token| rest.starts_with(token.as_bytes()))
+                    {
+                        blank(pos..line.len());
+                        break;
+                    }
+                    if let Some((len, hashes)) = raw_string_start(line, pos) {
+                        blank(pos..pos + len);
+                        pos += len;
+                        open = Some(Open::RawString { hashes });
+                        continue;
+                    }
+                    if let Some(len) = literal_len(rest) {
+                        blank(pos..(pos + len).min(line.len()));
+                        pos += len;
+                        continue;
+                    }
+                    pos += 1;
+                    continue;
+                }
+            }
+            blank(pos..pos + 1);
+            pos += 1;
+        }
+        open
+    }
+}
+
+/// Length of the `"…"` or `'…'` literal at the start of `rest`, escapes
+/// included. Literals end at the end of the line if unterminated. A `'`
+/// followed by a word and no closing `'`, as in a Rust lifetime (`&'a str`)
+/// or an apostrophe, is not a literal.
+fn literal_len(rest: &[u8]) -> Option<usize> {
+    let quote = *rest.first()?;
+    if quote != b'"' && quote != b'\'' {
+        return None;
+    }
+    if quote == b'\'' {
+        let word = rest[1..]
+            .iter()
+            .take_while(|&&b| b.is_ascii_alphanumeric() || b == b'_')
+            .count();
+        let after = rest.get(1 + word).copied();
+        if word > 0
+            && after.map_or(true, |b| {
+                b.is_ascii_whitespace() || b",:;)]}>+=|".contains(&b)
+            })
+        {
+            return None;
+        }
+    }
+
+    let mut pos = 1;
+    while pos < rest.len() {
+        match rest[pos] {
+            b'\\' => pos += 2,
+            b if b == quote => return Some(pos + 1),
+            _ => pos += 1,
+        }
+    }
+    // an unterminated `'` is an apostrophe; an unterminated `"` runs to the
+    // end of the line
+    (quote == b'"').then_some(rest.len())
+}
+
+/// If a raw string (`r"`, `r#"`, `br##"`, ...) starts at `pos`, the length
+/// of its opening and its number of `#`s.
+fn raw_string_start(line: &[u8], pos: usize) -> Option<(usize, usize)> {
+    let rest = &line[pos..];
+    let after_prefix = rest
+        .strip_prefix(b"br")
+        .or_else(|| rest.strip_prefix(b"r"))?;
+    let follows_word = pos
+        .checked_sub(1)
+        .is_some_and(|i| line[i].is_ascii_alphanumeric() || line[i] == b'_');
+    if follows_word {
+        return None;
+    }
+    let hashes = after_prefix.iter().take_while(|&&b| b == b'#').count();
+    (after_prefix.get(hashes) == Some(&b'"'))
+        .then(|| (rest.len() - after_prefix.len() + hashes + 1, hashes))
+}
+
+/// If `rest` starts with the end of a raw string with `hashes` `#`s, its
+/// length.
+fn raw_string_end(rest: &[u8], hashes: usize) -> Option<usize> {
+    let after_quote = rest.strip_prefix(b"\"")?;
+    (after_quote.len() >= hashes && after_quote[..hashes].iter().all(|&b| b == b'#'))
+        .then_some(1 + hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    fn block(start: &str, end: &str, nested: bool) -> BlockComment {
+        BlockComment {
+            start: start.to_string(),
+            end: end.to_string(),
+            nested,
+        }
+    }
+
+    fn comment_lines(delimiters: &[BlockComment], content: &str) -> Vec<bool> {
+        comment_lines_with(delimiters, &[], content)
+    }
+
+    fn comment_lines_with(
+        delimiters: &[BlockComment],
+        line_comments: &[String],
+        content: &str,
+    ) -> Vec<bool> {
+        let lines: Vec<&str> = content.lines().collect();
+        BlockComments::new(delimiters, line_comments).comment_lines(&lines)
+    }
+
+    #[rstest]
+    #[case::single_line("a /* b */ c\nd", &[true, false])]
+    #[case::multi_line("/*\nbody\n*/\ncode", &[true, true, true, false])]
+    #[case::nested("/* /*\n*/\ninner\n*/\ncode", &[true, true, true, true, false])]
+    #[case::stray_end("*/\ncode", &[false, false])]
+    #[case::reopen("/* a */ /*\nb\n*/", &[true, true, true])]
+    #[case::unterminated("/*\nrest\nof file", &[true, true, true])]
+    fn test_nested_c_comments(#[case] content: &str, #[case] expected: &[bool]) {
+        assert_eq!(comment_lines(&[block("/*", "*/", true)], content), expected);
+    }
+
+    #[test]
+    fn test_unnested_comment_closes_at_first_end() {
+        let content = "/* /*\n*/\nafter";
+        assert_eq!(
+            comment_lines(&[block("/*", "*/", false)], content),
+            [true, true, false]
+        );
+    }
+
+    #[rstest]
+    #[case::docstring("def f():\n    \"\"\"\n    body\n    \"\"\"\n    return 1", &[false, true, true, true, false])]
+    #[case::one_line("\"\"\"doc\"\"\"\ncode", &[true, false])]
+    #[case::other_quotes_inside("'''\n\"\"\"\n'''\ncode", &[true, true, true, false])]
+    fn test_python_docstrings(#[case] content: &str, #[case] expected: &[bool]) {
+        let delimiters = [block("\"\"\"", "\"\"\"", false), block("'''", "'''", false)];
+        assert_eq!(comment_lines(&delimiters, content), expected);
+    }
+
+    const RUST: (&str, &str, &str) = ("/*", "*/", "//");
+    const PYTHON: (&str, &str, &str) = ("\"\"\"", "\"\"\"", "#");
+    const HASKELL: (&str, &str, &str) = ("{-", "-}", "--");
+
+    #[rstest]
+    #[case::string(RUST, "let glob = \"target/*\";\nlet x = 1;", &[false, false])]
+    #[case::escaped_quote(RUST, "let s = \"a\\\"/*\";\ncode", &[false, false])]
+    #[case::raw_string(RUST, "let s = r#\"a \"/*\" b\"#;\ncode", &[false, false])]
+    #[case::multi_line_raw_string(RUST, "let s = r\"\n/*\n\";\ncode", &[false, false, false, false])]
+    #[case::char_literal(RUST, "let c = '\"'; /* x\n*/\ncode", &[true, true, false])]
+    #[case::lifetimes(RUST, "fn f<'a>(s: &'a str) /* c\n*/\ncode", &[true, true, false])]
+    #[case::comment_after_string(RUST, "let s = \"a\"; /* b\n*/", &[true, true])]
+    #[case::rust_line_comment(RUST, "// build outputs land in target/*\nlet s = 1;", &[false, false])]
+    #[case::rust_trailing_comment(RUST, "let s = 1; // see src/*\ncode", &[false, false])]
+    #[case::rust_token_in_string(RUST, "let u = \"http://x\"; /* c\n*/", &[true, true])]
+    #[case::python_line_comment(PYTHON, "# see also \"\"\" quoting\nx = 1", &[false, false])]
+    #[case::python_token_in_string(PYTHON, "x = \"#\"; \"\"\"doc\n\"\"\"", &[true, true])]
+    #[case::haskell_line_comment(HASKELL, "-- a {- in prose\nmain = pure ()", &[false, false])]
+    fn test_literals_do_not_open_comments(
+        #[case] syntax: (&str, &str, &str),
+        #[case] content: &str,
+        #[case] expected: &[bool],
+    ) {
+        let (start, end, line_comment) = syntax;
+        assert_eq!(
+            comment_lines_with(
+                &[block(start, end, true)],
+                &[line_comment.to_string()],
+                content
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_code_lines_blank_comments_and_literals() {
+        let lines = [
+            "let s = \"/* é */\"; /* c",
+            "still */ x('y'); // z",
+            "r#\"raw",
+        ];
+        let code =
+            BlockComments::new(&[block("/*", "*/", true)], &["//".to_string()]).code_lines(&lines);
+
+        assert_eq!(
+            code[0],
+            format!("let s = {};{}", " ".repeat(10), " ".repeat(5))
+        );
+        assert_eq!(
+            code[1],
+            format!("{} x(   );{}", " ".repeat(8), " ".repeat(5))
+        );
+        assert_eq!(code[2].trim(), "");
+        for (line, code) in lines.iter().zip(&code) {
+            assert_eq!(line.len(), code.len());
+        }
+    }
+
+    #[test]
+    fn test_no_delimiters_tracks_nothing() {
+        assert!(comment_lines(&[], "/*\n*/").is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_balanced_nesting_closes(
+            depth in 1usize..6,
+            body in "[a-z ]{0,20}",
+            after in "[a-z ]{1,20}",
+        ) {
+            let content = format!(
+                "{}\n{body}\n{}\n{after}",
+                "(* ".repeat(depth),
+                " *)".repeat(depth),
+            );
+            let lines = comment_lines(&[block("(*", "*)", true)], &content);
+            prop_assert_eq!(lines, vec![true, true, true, false]);
+        }
+
+        #[test]
+        fn prop_unbalanced_nesting_stays_open(
+            depth in 2usize..6,
+            after in "[a-z ]{1,20}",
+        ) {
+            let content = format!(
+                "{}\n{}\n{after}",
+                "{- ".repeat(depth),
+                " -}".repeat(depth - 1),
+            );
+            let lines = comment_lines(&[block("{-", "-}", true)], &content);
+            prop_assert_eq!(lines, vec![true, true, true]);
+        }
+
+        #[test]
+        fn prop_line_count_preserved(content in "[a-z/*\n ]{0,200}") {
+            let lines = comment_lines(&[block("/*", "*/", true)], &content);
+            prop_assert_eq!(lines.len(), content.lines().count());
+        }
+    }
+}
@@ -9,7 +9,9 @@ use crate::{
 };
 
 use super::error::TowlParserError;
+use super::language::Language;
 use super::pattern::{Pattern, MAX_TOTAL_PATTERNS};
+use super::prefilter::KeywordPrefilter;
 
 /// Parses file content to extract TODO comments with context.
 ///
@@ -20,6 +22,9 @@ pub struct Parser {
     pub(super) patterns: Vec<Pattern>,
     pub(super) function_patterns: Vec<Regex>,
     pub(super) context_lines: usize,
+    pub(super) languages: Vec<Language>,
+    pub(super) detect_placeholders: bool,
+    prefilter: Option<KeywordPrefilter>,
 }
 
 impl Parser {
@@ -29,13 +34,20 @@ impl Parser {
     ///
     /// # Errors
     /// Returns `TowlParserError::InvalidRegexPattern` if any pattern is malformed.
+    /// Returns `TowlParserError::InvalidFilenamePattern` if a language filename glob is malformed.
     /// Returns `TowlParserError::TooManyTotalPatterns` if total patterns exceed the budget.
     pub(crate) fn new(config: &ParsingConfig) -> Result<Self, TowlParserError> {
+        let language_patterns: usize = config
+            .languages
+            .values()
+            .map(|l| l.comment_prefixes.len() + l.placeholder_patterns.len())
+            .sum();
         let total_patterns = config
             .comment_prefixes
             .len()
             .saturating_add(config.todo_patterns.len())
-            .saturating_add(config.function_patterns.len());
+            .saturating_add(config.function_patterns.len())
+            .saturating_add(language_patterns);
 
         if total_patterns > MAX_TOTAL_PATTERNS {
             return Err(TowlParserError::TooManyTotalPatterns {
@@ -69,44 +81,107 @@ impl Parser {
             .map(|p| Self::build_regex(p))
             .collect::<Result<Vec<_>, _>>()?;
 
+        let languages = config
+            .languages
+            .values()
+            .map(Language::new)
+            .collect::<Result<Vec<_>, _>>()?;
+
         let context_lines = config
             .include_context_lines
             .clamp(MIN_CONTEXT_LINES, MAX_CONTEXT_LINES);
 
+        let prefilter = KeywordPrefilter::new(patterns.iter().map(|p| p.regex.as_str()))?;
+
         Ok(Self {
             comment_patterns,
             patterns,
             function_patterns,
             context_lines,
+            languages,
+            detect_placeholders: config.detect_placeholders,
+            prefilter,
         })
     }
 
     /// Parses file content to extract all TODO comments.
     ///
-    /// Identifies comment lines using configured patterns, then searches for TODO markers
-    /// within those comments. For each TODO found, extracts:
+    /// Content and lines without any literal the TODO patterns require are
+    /// rejected in a single pass before any regex runs. Remaining lines are checked
+    /// against the comment patterns of the file's language (resolved from
+    /// its name, extension or shebang), or found to lie in one of its block
+    /// comments, then searched for TODO markers. For each
+    /// TODO found, extracts:
     /// - Description text
     /// - Surrounding context lines
     /// - Function context (if applicable)
     /// - Location information (line, column)
     ///
+    /// With `detect_placeholders`, the code part of each line (before any
+    /// line comment, outside block comments and string literals) is also
+    /// matched against the language's placeholder patterns and reported as
+    /// [`TodoType::Placeholder`].
+    ///
     /// # Errors
     /// Returns `TowlParserError` if TODO extraction fails (rare, defensive).
     pub(crate) fn parse(
         &self,
         path: &Path,
         content: &str,
+    ) -> Result<Vec<TodoComment>, TowlParserError> {
+        self.parse_with(path, content, self.prefilter.as_ref())
+    }
+
+    fn parse_with(
+        &self,
+        path: &Path,
+        content: &str,
+        prefilter: Option<&KeywordPrefilter>,
     ) -> Result<Vec<TodoComment>, TowlParserError> {
         let mut todos = Vec::new();
+        let placeholder_patterns = self.placeholder_patterns_for(path, content);
+        let has_keywords = prefilter.map_or(true, |p| p.is_match(content));
+        if !has_keywords && placeholder_patterns.is_empty() {
+            return Ok(todos);
+        }
+
         let lines: Vec<&str> = content.lines().collect();
+        let comment_patterns = self.comment_patterns_for(path, content);
+        let block_comments = self.block_comments_for(path, content);
+        let in_block_comment = block_comments.comment_lines(&lines);
+        let code_lines = if placeholder_patterns.is_empty() {
+            Vec::new()
+        } else {
+            block_comments.code_lines(&lines)
+        };
 
         for (line_idx, line) in lines.iter().enumerate() {
-            let is_comment = self
-                .comment_patterns
-                .iter()
-                .any(|pattern| pattern.is_match(line));
+            let line_has_keyword = has_keywords && prefilter.map_or(true, |p| p.is_match(line));
+            if !line_has_keyword && placeholder_patterns.is_empty() {
+                continue;
+            }
 
-            if !is_comment {
+            let is_comment = in_block_comment.get(line_idx).copied().unwrap_or(false)
+                || comment_patterns
+                    .iter()
+                    .any(|pattern| pattern.is_match(line));
+
+            if let Some(code) = code_lines.get(line_idx) {
+                let captures = placeholder_patterns.iter().find_map(|p| {
+                    p.find(code)
+                        .and_then(|found| p.captures_at(line, found.start()))
+                });
+                if let Some(captures) = captures {
+                    todos.push(self.extract_placeholder(
+                        path,
+                        line,
+                        line_idx + 1,
+                        &captures,
+                        &lines,
+                    )?);
+                }
+            }
+            if !is_comment || !line_has_keyword {
                 continue;
             }
 
@@ -128,6 +203,50 @@ impl Parser {
         Ok(todos)
     }
 
+    /// Builds a [`TodoType::Placeholder`] from a placeholder match. The string
+    /// literal in the first capture group is the description; without one,
+    /// the construct itself (e.g. `todo!`) is used.
+    fn extract_placeholder(
+        &self,
+        path: &Path,
+        line: &str,
+        line_number: usize,
+        captures: &regex::Captures,
+        all_lines: &[&str],
+    ) -> Result<TodoComment, TowlParserError> {
+        let full_match = captures.get(0).ok_or(TowlParserError::RegexGroupMissing)?;
+        let message = captures
+            .get(1)
+            .map(|m| unquote(m.as_str()).trim())
+            .filter(|m| !m.is_empty());
+        let construct = full_match
+            .as_str()
+            .split('(')
+            .next()
+            .unwrap_or_default()
+            .trim();
+        let description = message.unwrap_or(construct).to_string(); // clone: owned String for TodoComment field
+
+        Ok(TodoComment {
+            id: format!(
+                "{}_L{}_C{}",
+                path.display(),
+                line_number,
+                full_match.start()
+            ),
+            file_path: path.to_path_buf(), // clone: owned path for TodoComment struct
+            line_number,
+            column_start: full_match.start(),
+            column_end: full_match.end(),
+            todo_type: TodoType::Placeholder,
+            original_text: line.to_string(), // clone: owned String for TodoComment struct
+            description,
+            context_lines: self.extract_context(all_lines, line_number - 1),
+            function_context: self.find_function_context(all_lines, line_number - 1),
+            analysis: None,
+        })
+    }
+
     fn extract_todo(
         &self,
         path: &Path,
@@ -170,6 +289,13 @@ impl Parser {
     }
 }
 
+/// Strips the surrounding quotes from a string literal.
+fn unquote(literal: &str) -> &str {
+    literal
+        .get(1..literal.len().saturating_sub(1))
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -321,6 +447,156 @@ mod tests {
         }
     }
 
+    proptest! {
+        #[test]
+        fn prop_prefilter_does_not_change_results(
+            lines in prop::collection::vec(
+                prop_oneof![
+                    "[^\n]{0,40}",
+                    valid_todo_keyword().prop_map(|k| format!("// {k}: item")),
+                    valid_todo_keyword().prop_map(|k| format!("let {} = 1;", k.to_lowercase())),
+                ],
+                1..40,
+            )
+        ) {
+            let config = crate::config::test_parsing_config();
+            let parser = Parser::new(&config).unwrap();
+            let path = PathBuf::from("test.rs");
+
+            let content = lines.join("\n");
+            let filtered = parser.parse(&path, &content).unwrap();
+            let unfiltered = parser.parse_with(&path, &content, None).unwrap();
+
+            prop_assert_eq!(filtered, unfiltered);
+        }
+    }
+
+    fn language_parser() -> Parser {
+        let mut config = crate::config::test_parsing_config();
+        config.languages = ParsingConfig::default().languages;
+        Parser::new(&config).unwrap()
+    }
+
+    #[rstest]
+    #[case::docstring("a.py", "def f():\n    \"\"\"\n    TODO: document\n    \"\"\"\n", &[3])]
+    #[case::single_quoted_docstring("a.py", "'''\nFIXME: x\n'''\n", &[2])]
+    #[case::after_docstring("a.py", "\"\"\"doc\"\"\"\nmsg = 'TODO: not a comment'\n", &[])]
+    #[case::nested_rust("a.rs", "/* outer /* inner */\n   TODO: still outer\n*/\nlet s = \"TODO: code\";\n", &[2])]
+    #[case::haskell("a.hs", "{- {- -}\nTODO: nested\n-}\nmain = putStrLn \"TODO: code\"\n", &[2])]
+    #[case::ocaml("a.ml", "(* outer (* inner *)\n   HACK: nested\n*)\nlet s = \"TODO: code\"\n", &[2])]
+    #[case::delimiter_in_string("a.rs", "let glob = \"target/*\";\nlet msg = \"TODO: this is a string, not a comment\";\n// TODO: real\n", &[3])]
+    #[case::delimiter_in_line_comment("a.rs", "// build outputs land in target/*\nlet msg = \"TODO: code\";\n// TODO: real\n", &[3])]
+    #[case::docstring_quote_in_line_comment("a.py", "# see also \"\"\" quoting\nmsg = 'TODO: code'\n# TODO: real\n", &[3])]
+    fn test_block_comment_regions(
+        #[case] path: &str,
+        #[case] content: &str,
+        #[case] expected_lines: &[usize],
+    ) {
+        let todos = language_parser().parse(Path::new(path), content).unwrap();
+        let lines: Vec<usize> = todos.iter().map(|t| t.line_number).collect();
+        assert_eq!(lines, expected_lines, "{path}: {content}");
+    }
+
+    proptest! {
+        #[test]
+        fn prop_todo_inside_nested_block_detected(
+            depth in 1usize..5,
+            keyword in valid_todo_keyword(),
+            description in valid_description(),
+        ) {
+            let content = format!(
+                "{}\n{keyword}: {description}\n{}\nlet x = 1;",
+                "/* ".repeat(depth),
+                " */".repeat(depth),
+            );
+            let todos = language_parser().parse(Path::new("lib.rs"), &content).unwrap();
+
+            prop_assert_eq!(todos.len(), 1);
+            prop_assert_eq!(todos[0].line_number, 2);
+        }
+
+        #[test]
+        fn prop_code_after_closed_block_ignored(
+            depth in 1usize..5,
+            keyword in valid_todo_keyword(),
+            description in valid_description(),
+        ) {
+            let content = format!(
+                "{}\n{}\nlet s = \"{keyword}: {description}\";",
+                "/* ".repeat(depth),
+                " */".repeat(depth),
+            );
+            let todos = language_parser().parse(Path::new("lib.rs"), &content).unwrap();
+
+            prop_assert!(todos.is_empty(), "Detected TODO in code after block: {}", content);
+        }
+
+        #[test]
+        fn prop_docstring_todo_detected(
+            quote in prop::sample::select(vec!["\"\"\"", "'''"]),
+            indent in "[ ]{0,8}",
+            keyword in valid_todo_keyword(),
+            description in valid_description(),
+        ) {
+            let content = format!(
+                "def f():\n{indent}{quote}Summary.\n\n{indent}{keyword}: {description}\n{indent}{quote}\n{indent}return 1"
+            );
+            let todos = language_parser().parse(Path::new("mod.py"), &content).unwrap();
+
+            prop_assert_eq!(todos.len(), 1);
+            prop_assert_eq!(todos[0].line_number, 4);
+        }
+    }
+
+    #[test]
+    #[ignore = "benchmark — run with --ignored --nocapture"]
+    fn bench_keyword_prefilter_speedup() {
+        use std::fmt::Write;
+        use std::time::Instant;
+
+        const ROUNDS: usize = 5;
+
+        let config = crate::config::test_parsing_config();
+        let parser = Parser::new(&config).unwrap();
+        let path = PathBuf::from("bench.rs");
+
+        // every line is a comment line, so without the prefilter each one
+        // is run through every TODO regex
+        let mut content = String::new();
+        for i in 0..100_000 {
+            if i % 500 == 0 {
+                writeln!(content, "    // TODO: item {i}").unwrap();
+            } else {
+                writeln!(
+                    content,
+                    "    let value_{i} = compute(x, y); // trailing comment"
+                )
+                .unwrap();
+            }
+        }
+
+        let start = Instant::now();
+        let mut unfiltered = Vec::new();
+        for _ in 0..ROUNDS {
+            unfiltered = parser.parse_with(&path, &content, None).unwrap();
+        }
+        let unfiltered_time = start.elapsed();
+
+        let start = Instant::now();
+        let mut filtered = Vec::new();
+        for _ in 0..ROUNDS {
+            filtered = parser.parse(&path, &content).unwrap();
+        }
+        let filtered_time = start.elapsed();
+
+        println!(
+            "keyword prefilter: {unfiltered_time:?} -> {filtered_time:?} ({:.1}x)",
+            unfiltered_time.as_secs_f64() / filtered_time.as_secs_f64()
+        );
+        assert_eq!(filtered, unfiltered);
+        assert!(filtered_time < unfiltered_time);
+    }
+
     #[test]
     fn test_total_pattern_budget_exceeded() {
         let mut config = crate::config::test_parsing_config();
@@ -351,6 +627,17 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_prefilter_keeps_pattern_keyword_unlike_type_name() {
+        let mut config = crate::config::test_parsing_config();
+        config.todo_patterns = vec![r"(?i)\bTODO\b|\bXXX\b".to_string()];
+        let parser = Parser::new(&config).unwrap();
+
+        let result = parser.parse(Path::new("test.rs"), "// XXX: foo").unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].todo_type, TodoType::Todo);
+    }
+
     #[test]
     fn test_column_position_accuracy() {
         let config = crate::config::test_parsing_config();
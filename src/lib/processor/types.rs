@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use tracing::debug;
+
 use crate::comment::todo::TodoComment;
 use crate::github::types::CreatedIssue;
 
@@ -23,8 +25,12 @@ impl Processor {
     ///
     /// Groups replacements by file to minimize I/O.
     /// Uses atomic writes to prevent partial updates.
+    /// Code placeholders (e.g. `todo!()`) are left untouched, as rewriting
+    /// them into a comment would change the program. Issue URLs must be on
+    /// `web_url` (see [`crate::config::GitHubConfig::web_url`]).
     pub async fn replace_todos(
         repo_root: &Path,
+        web_url: &str,
         replacements: &[(TodoComment, CreatedIssue)],
     ) -> ProcessorResult {
         if replacements.is_empty() {
@@ -37,6 +43,10 @@ impl Processor {
 
         let mut by_file: HashMap<&Path, Vec<(&TodoComment, &CreatedIssue)>> = HashMap::new();
         for (todo, issue) in replacements {
+            if todo.todo_type.is_placeholder() {
+                debug!("Not rewriting code placeholder {}", todo.id);
+                continue;
+            }
             by_file
                 .entry(todo.file_path.as_path())
                 .or_default()
@@ -47,8 +57,9 @@ impl Processor {
         let mut todos_replaced = 0;
         let mut errors: Vec<(PathBuf, TowlProcessorError)> = Vec::new();
 
+        let issue_prefix = format!("{}/", web_url.trim_end_matches('/'));
         for (path, file_replacements) in &by_file {
-            match Self::process_file(repo_root, path, file_replacements).await {
+            match Self::process_file(repo_root, &issue_prefix, path, file_replacements).await {
                 Ok(count) => {
                     files_modified += 1;
                     todos_replaced += count;
@@ -68,6 +79,7 @@ impl Processor {
 
     async fn process_file(
         repo_root: &Path,
+        issue_prefix: &str,
         path: &Path,
         replacements: &[(&TodoComment, &CreatedIssue)],
     ) -> Result<usize, TowlProcessorError> {
@@ -110,7 +122,7 @@ impl Processor {
                 });
             }
 
-            if !issue.html_url.starts_with("https://github.com/") {
+            if !issue.html_url.starts_with(issue_prefix) {
                 return Err(TowlProcessorError::InvalidIssueUrl {
                     url: issue.html_url.clone(), // clone: need owned String for error variant
                 });
@@ -167,6 +179,8 @@ mod tests {
     use proptest::prelude::*;
     use rstest::rstest;
 
+    const GITHUB: &str = "https://github.com";
+
     fn make_todo(
         path: &Path,
         line_number: usize,
@@ -211,7 +225,7 @@ mod tests {
             let issue = make_issue(issue_num);
 
             let rt = tokio::runtime::Runtime::new().unwrap();
-            let result = rt.block_on(Processor::replace_todos(temp_dir.path(), &[(todo, issue)]));
+            let result = rt.block_on(Processor::replace_todos(temp_dir.path(), GITHUB, &[(todo, issue)]));
 
             prop_assert_eq!(result.errors.len(), 0);
             prop_assert_eq!(result.todos_replaced, 1);
@@ -257,7 +271,7 @@ mod tests {
             let url = format!("https://github.com/owner/repo/issues/{issue_num}");
 
             let rt = tokio::runtime::Runtime::new().unwrap();
-            let result = rt.block_on(Processor::replace_todos(temp_dir.path(), &[(todo, issue)]));
+            let result = rt.block_on(Processor::replace_todos(temp_dir.path(), GITHUB, &[(todo, issue)]));
 
             prop_assert_eq!(result.errors.len(), 0);
 
@@ -291,7 +305,7 @@ mod tests {
         let todo = make_todo(&file_path, 1, column_start, original, desc);
         let issue = make_issue(42);
 
-        let result = Processor::replace_todos(temp_dir.path(), &[(todo, issue)]).await;
+        let result = Processor::replace_todos(temp_dir.path(), GITHUB, &[(todo, issue)]).await;
 
         assert_eq!(result.errors.len(), 0);
         assert_eq!(result.todos_replaced, 1);
@@ -332,7 +346,7 @@ mod tests {
 
         std::fs::write(&file_path, lines.join("\n")).unwrap();
 
-        let result = Processor::replace_todos(temp_dir.path(), &pairs).await;
+        let result = Processor::replace_todos(temp_dir.path(), GITHUB, &pairs).await;
 
         assert_eq!(result.errors.len(), 0);
         assert_eq!(result.todos_replaced, count);
@@ -350,13 +364,60 @@ mod tests {
     #[tokio::test]
     async fn test_empty_replacements() {
         let temp_dir = tempfile::TempDir::new().unwrap();
-        let result = Processor::replace_todos(temp_dir.path(), &[]).await;
+        let result = Processor::replace_todos(temp_dir.path(), GITHUB, &[]).await;
 
         assert_eq!(result.files_modified, 0);
         assert_eq!(result.todos_replaced, 0);
         assert!(result.errors.is_empty());
     }
 
+    #[rstest]
+    #[case::enterprise("https://ghe.example.com/team/tool/issues/3", true)]
+    #[case::public_host("https://github.com/team/tool/issues/3", false)]
+    #[case::lookalike_host("https://ghe.example.com.evil.net/team/tool/issues/3", false)]
+    #[tokio::test]
+    async fn test_issue_url_must_match_configured_host(#[case] url: &str, #[case] accepted: bool) {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test.rs");
+        std::fs::write(
+            &file_path,
+            "// TODO: fix
+",
+        )
+        .unwrap();
+
+        let todo = make_todo(&file_path, 1, 3, "// TODO: fix", "fix");
+        let issue = CreatedIssue::new(3, "Issue".to_string(), url.to_string(), "t".to_string());
+        let result =
+            Processor::replace_todos(temp_dir.path(), "https://ghe.example.com", &[(todo, issue)])
+                .await;
+
+        assert_eq!(result.todos_replaced, usize::from(accepted));
+        if !accepted {
+            assert!(matches!(
+                result.errors[0].1,
+                TowlProcessorError::InvalidIssueUrl { .. }
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_placeholders_are_not_rewritten() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test.rs");
+        let original = "    todo!(\"wire up\")\n";
+        std::fs::write(&file_path, original).unwrap();
+
+        let mut todo = make_todo(&file_path, 1, 4, "    todo!(\"wire up\")", "wire up");
+        todo.todo_type = crate::comment::todo::TodoType::Placeholder;
+        let result =
+            Processor::replace_todos(temp_dir.path(), GITHUB, &[(todo, make_issue(1))]).await;
+
+        assert_eq!(result.todos_replaced, 0);
+        assert!(result.errors.is_empty());
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), original);
+    }
+
     #[tokio::test]
     async fn test_line_out_of_bounds() {
         let temp_dir = tempfile::TempDir::new().unwrap();
@@ -366,7 +427,7 @@ mod tests {
         let todo = make_todo(&file_path, 99, 3, "// TODO: fix", "fix");
         let issue = make_issue(1);
 
-        let result = Processor::replace_todos(temp_dir.path(), &[(todo, issue)]).await;
+        let result = Processor::replace_todos(temp_dir.path(), GITHUB, &[(todo, issue)]).await;
 
         assert_eq!(result.errors.len(), 1);
         assert_eq!(result.todos_replaced, 0);
@@ -384,7 +445,7 @@ mod tests {
         );
         let issue = make_issue(1);
 
-        let result = Processor::replace_todos(temp_dir.path(), &[(todo, issue)]).await;
+        let result = Processor::replace_todos(temp_dir.path(), GITHUB, &[(todo, issue)]).await;
 
         assert_eq!(result.errors.len(), 1);
         assert_eq!(result.files_modified, 0);
@@ -399,7 +460,7 @@ mod tests {
         let todo = make_todo(&file_path, 1, 100, "// TODO: fix", "fix");
         let issue = make_issue(1);
 
-        let result = Processor::replace_todos(temp_dir.path(), &[(todo, issue)]).await;
+        let result = Processor::replace_todos(temp_dir.path(), GITHUB, &[(todo, issue)]).await;
 
         assert_eq!(result.errors.len(), 1);
         assert!(matches!(
@@ -418,7 +479,7 @@ mod tests {
         let todo = make_todo(&file_path, 1, 3, "// TODO: fix", "fix");
         let issue = make_issue(1);
 
-        let result = Processor::replace_todos(temp_dir.path(), &[(todo, issue)]).await;
+        let result = Processor::replace_todos(temp_dir.path(), GITHUB, &[(todo, issue)]).await;
 
         assert_eq!(result.errors.len(), 1);
         assert!(matches!(
@@ -439,7 +500,7 @@ mod tests {
         let todo = make_todo(&file_path, 1, 3, "// TODO: fix", "fix");
         let issue = make_issue(7);
 
-        let result = Processor::replace_todos(temp_dir.path(), &[(todo, issue)]).await;
+        let result = Processor::replace_todos(temp_dir.path(), GITHUB, &[(todo, issue)]).await;
 
         assert_eq!(result.errors.len(), 0);
         assert_eq!(result.todos_replaced, 1);
@@ -460,7 +521,7 @@ mod tests {
         let todo = make_todo(&file_path, 1, 3, "// TODO: fix", "fix");
         let issue = make_issue(1);
 
-        let result = Processor::replace_todos(root_dir.path(), &[(todo, issue)]).await;
+        let result = Processor::replace_todos(root_dir.path(), GITHUB, &[(todo, issue)]).await;
 
         assert_eq!(result.errors.len(), 1);
         assert_eq!(result.files_modified, 0);
@@ -480,9 +541,12 @@ mod tests {
         let issue_a = make_issue(1);
         let issue_b = make_issue(2);
 
-        let result =
-            Processor::replace_todos(temp_dir.path(), &[(todo_a, issue_a), (todo_b, issue_b)])
-                .await;
+        let result = Processor::replace_todos(
+            temp_dir.path(),
+            GITHUB,
+            &[(todo_a, issue_a), (todo_b, issue_b)],
+        )
+        .await;
 
         assert_eq!(result.errors.len(), 0);
         assert_eq!(result.files_modified, 2);
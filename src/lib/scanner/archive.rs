@@ -0,0 +1,459 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use futures::stream::{self, StreamExt};
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use crate::comment::todo::TodoComment;
+
+use super::error::TowlScannerError;
+use super::limits::{ScanResult, MAX_CONCURRENT_FILES, MAX_FILES_SCANNED, MAX_FILE_SIZE};
+use super::types::Scanner;
+use super::walker::PathFilter;
+
+/// Separates the archive path from the entry path in reported file paths.
+pub const ARCHIVE_ENTRY_SEPARATOR: &str = "!/";
+
+/// Archive formats that are scanned in place, without extracting to disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ArchiveKind {
+    Tar,
+    /// Gzip-compressed tar, including published `.crate` files.
+    TarGz,
+    Zip,
+}
+
+impl ArchiveKind {
+    pub(super) fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") || name.ends_with(".crate") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// An entry to parse, or the error that stopped it being read.
+type ArchiveEntry = (PathBuf, Result<String, TowlScannerError>);
+
+impl Scanner {
+    /// Returns true if `path` is an archive file that [`Scanner::scan`]
+    /// reads in place, so its TODOs do not come from the working tree.
+    #[must_use]
+    pub fn is_archive(path: &Path) -> bool {
+        ArchiveKind::from_path(path).is_some() && path.is_file()
+    }
+
+    /// Scans the entries of an archive without extracting it.
+    ///
+    /// Entries are read sequentially on the blocking pool and streamed to the
+    /// parser. Each is reported as `archive.tar.gz!/inner/path.rs`;
+    /// `exclude_patterns` and `include_patterns` match the inner path, and
+    /// the per-file size and count limits apply to every entry.
+    ///
+    /// # Errors
+    /// Returns `TowlScannerError::UnableToReadArchive` if the archive cannot be
+    /// opened or is corrupt, or `TowlScannerError::UnableToWalkFile` if exclude
+    /// or include patterns are invalid.
+    pub(super) async fn scan_archive(
+        &self,
+        archive: &Path,
+        kind: ArchiveKind,
+        on_found: &mut impl FnMut(&[TodoComment]),
+    ) -> Result<ScanResult, TowlScannerError> {
+        let scan_start = std::time::Instant::now();
+        debug!("Scanning archive {}", archive.display());
+
+        let (tx, rx) = mpsc::channel::<ArchiveEntry>(MAX_CONCURRENT_FILES);
+        let mut reader = ArchiveReader {
+            scanner: self.clone(), // clone: blocking task needs an owned scanner (Arc fields)
+            filter: self.build_path_filter(Path::new(""))?,
+            archive: archive.to_path_buf(), // clone: blocking task needs an owned path
+            tx,
+            files_queued: 0,
+            files_skipped: 0,
+        };
+        let read_task = tokio::task::spawn_blocking(move || {
+            reader.read(kind)?;
+            Ok::<_, TowlScannerError>(reader.files_skipped)
+        });
+
+        let entries = stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|entry| (entry, rx))
+        });
+        let results = entries
+            .map(|(path, content)| async move {
+                let result = match content {
+                    Ok(content) => self.parse_content(&path, content).await,
+                    Err(e) => Err(e),
+                };
+                (path, result)
+            })
+            .buffer_unordered(MAX_CONCURRENT_FILES);
+        let (todos, files_scanned, files_errored) = Self::collect_results(results, on_found).await;
+
+        let files_skipped = read_task
+            .await
+            .map_err(TowlScannerError::ParseTaskFailed)??;
+
+        Ok(Self::finish_scan(
+            todos,
+            files_scanned,
+            files_skipped,
+            files_errored,
+            scan_start,
+        ))
+    }
+}
+
+/// Reads archive entries on a blocking thread and queues the ones to scan.
+struct ArchiveReader {
+    scanner: Scanner,
+    filter: PathFilter,
+    archive: PathBuf,
+    tx: mpsc::Sender<ArchiveEntry>,
+    files_queued: usize,
+    files_skipped: usize,
+}
+
+impl ArchiveReader {
+    fn read(&mut self, kind: ArchiveKind) -> Result<(), TowlScannerError> {
+        let file = File::open(&self.archive).map_err(|e| self.archive_error(&e))?;
+        match kind {
+            ArchiveKind::Tar => self.read_tar(file),
+            ArchiveKind::TarGz => self.read_tar(flate2::read::GzDecoder::new(file)),
+            ArchiveKind::Zip => self.read_zip(file),
+        }
+    }
+
+    fn read_tar(&mut self, reader: impl Read) -> Result<(), TowlScannerError> {
+        let mut tar = tar::Archive::new(reader);
+        for entry in tar.entries().map_err(|e| self.archive_error(&e))? {
+            let mut entry = entry.map_err(|e| self.archive_error(&e))?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let inner = entry
+                .path()
+                .map_err(|e| self.archive_error(&e))?
+                .into_owned();
+            let size = entry.size();
+            if !self.offer(&inner, size, &mut entry) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_zip(&mut self, file: File) -> Result<(), TowlScannerError> {
+        let mut zip = zip::ZipArchive::new(file).map_err(|e| self.archive_error(&e))?;
+        for index in 0..zip.len() {
+            let mut entry = match zip.by_index(index) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    warn!(
+                        "Skipping unreadable entry {index} in {}: {e}",
+                        self.archive.display()
+                    );
+                    self.files_skipped += 1;
+                    continue;
+                }
+            };
+            if !entry.is_file() || entry.is_symlink() {
+                continue;
+            }
+            let inner = PathBuf::from(entry.name());
+            let size = entry.size();
+            if !self.offer(&inner, size, &mut entry) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the scanner's filters and limits to one entry and queues it.
+    /// Returns false once scanning should stop.
+    fn offer(&mut self, inner: &Path, size: u64, content: &mut impl Read) -> bool {
+        let Some(inner) = normalize_entry_path(inner) else {
+            debug!("Skipping unsafe archive entry path {}", inner.display());
+            self.files_skipped += 1;
+            return true;
+        };
+        if !self.filter.allows_file(Path::new(""), &inner) {
+            return true;
+        }
+
+        let matches_name = self.scanner.matches_file_name(&inner);
+        if !matches_name && inner.extension().is_some() {
+            debug!("{} will not be scanned", inner.display());
+            self.files_skipped += 1;
+            return true;
+        }
+
+        let path = self.entry_path(&inner);
+        if self.files_queued >= MAX_FILES_SCANNED {
+            warn!(
+                "File scan limit reached ({} files), stopping discovery",
+                MAX_FILES_SCANNED
+            );
+            return false;
+        }
+
+        let text = match read_entry(&path, size, content) {
+            Ok(text) => text,
+            Err(e) => return self.queue(path, Err(e)),
+        };
+        if !matches_name && !self.scanner.matches_shebang(&inner, &text) {
+            debug!("{} will not be scanned", inner.display());
+            self.files_skipped += 1;
+            return true;
+        }
+        self.queue(path, Ok(text))
+    }
+
+    fn queue(&mut self, path: PathBuf, content: Result<String, TowlScannerError>) -> bool {
+        self.files_queued += 1;
+        // A closed channel means the consumer stopped early (TODO limit reached)
+        self.tx.blocking_send((path, content)).is_ok()
+    }
+
+    fn entry_path(&self, inner: &Path) -> PathBuf {
+        let mut path = self.archive.clone().into_os_string(); // clone: reported path is built per entry
+        path.push(ARCHIVE_ENTRY_SEPARATOR);
+        path.push(inner.as_os_str());
+        PathBuf::from(path)
+    }
+
+    fn archive_error(&self, e: &dyn std::fmt::Display) -> TowlScannerError {
+        TowlScannerError::UnableToReadArchive {
+            path: self.archive.clone(), // clone: error owns PathBuf
+            message: e.to_string(),
+        }
+    }
+}
+
+/// Reads an entry as UTF-8, enforcing `MAX_FILE_SIZE` even if the header
+/// understates the size.
+fn read_entry(path: &Path, size: u64, content: &mut impl Read) -> Result<String, TowlScannerError> {
+    let too_large = |size| TowlScannerError::FileTooLarge {
+        path: path.to_path_buf(), // clone: owned path for error variant
+        size,
+        max_allowed: MAX_FILE_SIZE,
+    };
+    if size > MAX_FILE_SIZE {
+        return Err(too_large(size));
+    }
+
+    let to_read_err = |e| TowlScannerError::UnableToReadFileAtPath(path.to_path_buf(), e); // clone: owned path for error closure
+    let mut bytes = Vec::new();
+    content
+        .take(MAX_FILE_SIZE + 1)
+        .read_to_end(&mut bytes)
+        .map_err(to_read_err)?;
+    let read = u64::try_from(bytes.len()).unwrap_or(u64::MAX);
+    if read > MAX_FILE_SIZE {
+        return Err(too_large(read));
+    }
+
+    String::from_utf8(bytes)
+        .map_err(|e| to_read_err(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
+}
+
+/// Keeps the normal components of an entry path, rejecting absolute paths
+/// and `..` so a crafted archive cannot make an entry look like a file
+/// outside it.
+fn normalize_entry_path(inner: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+    for component in inner.components() {
+        match component {
+            Component::Normal(part) => normalized.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    (!normalized.as_os_str().is_empty()).then_some(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    const ENTRIES: &[(&str, &str)] = &[
+        (
+            "pkg-0.1.0/src/lib.rs",
+            "fn a() {}\n// TODO: inside archive\n",
+        ),
+        ("pkg-0.1.0/target/gen.rs", "// TODO: excluded"),
+        ("pkg-0.1.0/run", "#!/bin/sh\n# FIXME: packaged script\n"),
+        ("pkg-0.1.0/README.md", "TODO: not scanned"),
+    ];
+
+    fn write_tar(writer: impl Write) {
+        let mut builder = tar::Builder::new(writer);
+        for (name, content) in ENTRIES {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, content.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap();
+    }
+
+    fn write_archive(dir: &Path, name: &str) -> PathBuf {
+        let path = dir.join(name);
+        let file = File::create(&path).unwrap();
+        match ArchiveKind::from_path(&path).unwrap() {
+            ArchiveKind::Tar => write_tar(file),
+            ArchiveKind::TarGz => {
+                let mut gz = flate2::write::GzEncoder::new(file, flate2::Compression::fast());
+                write_tar(&mut gz);
+                gz.finish().unwrap();
+            }
+            ArchiveKind::Zip => {
+                let mut zip = zip::ZipWriter::new(file);
+                for (name, content) in ENTRIES {
+                    zip.start_file(*name, zip::write::SimpleFileOptions::default())
+                        .unwrap();
+                    zip.write_all(content.as_bytes()).unwrap();
+                }
+                zip.finish().unwrap();
+            }
+        }
+        path
+    }
+
+    fn scanner() -> Scanner {
+        let mut config = crate::config::test_parsing_config();
+        config.exclude_patterns = vec!["*/target/*".to_string()];
+        config.languages = crate::config::ParsingConfig::default().languages;
+        Scanner::new(config).unwrap()
+    }
+
+    #[rstest]
+    #[case("release.tar.gz")]
+    #[case("release.tgz")]
+    #[case("pkg-0.1.0.crate")]
+    #[case("release.tar")]
+    #[case("release.zip")]
+    #[tokio::test]
+    async fn test_scan_archive_entries(#[case] name: &str) {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = write_archive(temp_dir.path(), name);
+
+        let result = scanner().scan(archive.clone()).await.unwrap();
+
+        let prefix = format!("{}!/pkg-0.1.0/", archive.display());
+        let mut found: Vec<_> = result
+            .todos
+            .iter()
+            .map(|t| {
+                let path = t.file_path.to_str().unwrap();
+                (
+                    path.strip_prefix(&prefix).unwrap(),
+                    t.line_number,
+                    t.description.as_str(),
+                )
+            })
+            .collect();
+        found.sort_unstable();
+        assert_eq!(
+            found,
+            vec![
+                ("run", 2, "packaged script"),
+                ("src/lib.rs", 2, "inside archive")
+            ]
+        );
+        assert_eq!(result.files_scanned, 2);
+        assert_eq!(
+            result.files_skipped, 1,
+            "README.md is not a scanned extension"
+        );
+    }
+
+    #[test]
+    fn test_is_archive_requires_an_archive_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = write_archive(temp_dir.path(), "release.zip");
+        let directory = temp_dir.path().join("unpacked.zip");
+        std::fs::create_dir(&directory).unwrap();
+        let source = temp_dir.path().join("lib.rs");
+        std::fs::write(&source, "// TODO: x\n").unwrap();
+
+        assert!(Scanner::is_archive(&archive));
+        assert!(!Scanner::is_archive(&directory));
+        assert!(!Scanner::is_archive(&source));
+    }
+
+    #[tokio::test]
+    async fn test_scan_archive_oversized_entry_is_errored() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("big.tar");
+        let mut builder = tar::Builder::new(File::create(&path).unwrap());
+        let content = vec![b'a'; usize::try_from(MAX_FILE_SIZE).unwrap() + 1];
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "big.rs", content.as_slice())
+            .unwrap();
+        builder.into_inner().unwrap();
+
+        let result = scanner().scan(path).await.unwrap();
+
+        assert_eq!(result.files_errored, 1);
+        assert!(result.todos.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_scan_corrupt_archive_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("broken.zip");
+        std::fs::write(&path, b"not a zip").unwrap();
+
+        let result = scanner().scan(path).await;
+
+        assert!(matches!(
+            result,
+            Err(TowlScannerError::UnableToReadArchive { .. })
+        ));
+    }
+
+    #[rstest]
+    #[case("a/b.rs", Some("a/b.rs"))]
+    #[case("./a/./b.rs", Some("a/b.rs"))]
+    #[case("../escape.rs", None)]
+    #[case("a/../../escape.rs", None)]
+    #[case("/etc/passwd", None)]
+    #[case(".", None)]
+    fn test_normalize_entry_path(#[case] input: &str, #[case] expected: Option<&str>) {
+        assert_eq!(
+            normalize_entry_path(Path::new(input)),
+            expected.map(PathBuf::from)
+        );
+    }
+
+    #[rstest]
+    #[case("x.tar.gz", Some(ArchiveKind::TarGz))]
+    #[case("X.TGZ", Some(ArchiveKind::TarGz))]
+    #[case("serde-1.0.0.crate", Some(ArchiveKind::TarGz))]
+    #[case("x.tar", Some(ArchiveKind::Tar))]
+    #[case("x.zip", Some(ArchiveKind::Zip))]
+    #[case("x.gz", None)]
+    #[case("x.rs", None)]
+    fn test_archive_kind_from_path(#[case] name: &str, #[case] expected: Option<ArchiveKind>) {
+        assert_eq!(ArchiveKind::from_path(Path::new(name)), expected);
+    }
+}
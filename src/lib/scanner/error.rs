@@ -10,8 +10,19 @@ pub enum TowlScannerError {
     UnableToWalkFile(#[from] ignore::Error),
     #[error("Parsing error {0}")]
     ParsingError(#[from] TowlParserError),
+    #[error("Parsing task failed: {0}")]
+    ParseTaskFailed(tokio::task::JoinError),
     #[error("Unable to read file at path {0}: {1}")]
     UnableToReadFileAtPath(PathBuf, tokio::io::Error),
+    #[error("Unable to read archive {path}: {message}")]
+    UnableToReadArchive { path: PathBuf, message: String },
+    #[error("Invalid git ref '{git_ref}': {message}")]
+    InvalidGitRef { git_ref: String, message: String },
+    #[error("git {command} failed: {message}")]
+    GitCommandFailed {
+        command: &'static str,
+        message: String,
+    },
     #[error("Invalid Path. {path}")]
     InvalidPath { path: PathBuf },
     #[error("File too large: {path} ({size} bytes exceeds maximum of {max_allowed} bytes)")]
@@ -0,0 +1,186 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tracing::{debug, warn};
+
+use super::error::TowlScannerError;
+use super::types::Scanner;
+
+impl Scanner {
+    /// Lists files under `root` with `git ls-files -z`, adding untracked
+    /// files that are not ignored when `untracked` is set. `exclude_patterns`
+    /// and `include_patterns` are applied to the result.
+    ///
+    /// Returns `Ok(None)` when `root` is not a directory inside a git work
+    /// tree, or git cannot be run, so the caller can fall back to the walker.
+    ///
+    /// # Errors
+    /// Returns `TowlScannerError::UnableToWalkFile` if exclude or include patterns are invalid.
+    pub(super) fn git_files(
+        &self,
+        root: &Path,
+        untracked: bool,
+    ) -> Result<Option<Vec<PathBuf>>, TowlScannerError> {
+        if !root.is_dir() {
+            return Ok(None);
+        }
+
+        let mut command = Command::new("git");
+        command
+            .args(["ls-files", "-z", "--cached"])
+            .current_dir(root);
+        if untracked {
+            command.args(["--others", "--exclude-standard"]);
+        }
+
+        let output = match command.output() {
+            Ok(output) => output,
+            Err(e) => {
+                warn!("Unable to run git, falling back to directory walk: {e}");
+                return Ok(None);
+            }
+        };
+        if !output.status.success() {
+            debug!(
+                "git ls-files failed in {}, falling back to directory walk: {}",
+                root.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            return Ok(None);
+        }
+
+        let filter = self.build_path_filter(root)?;
+        let files = parse_ls_files(&output.stdout)
+            .map(|relative| root.join(relative))
+            .filter(|path| filter.allows_file(root, path))
+            .collect();
+
+        Ok(Some(files))
+    }
+}
+
+/// Splits NUL-separated `git ls-files -z` output, deduplicating the entries
+/// that appear once per stage for files with merge conflicts.
+fn parse_ls_files(stdout: &[u8]) -> impl Iterator<Item = &str> {
+    let mut previous = None;
+    stdout
+        .split(|&b| b == 0)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let path = std::str::from_utf8(entry).ok();
+            if path.is_none() {
+                debug!(
+                    "Skipping non UTF-8 path from git: {}",
+                    String::from_utf8_lossy(entry)
+                );
+            }
+            path
+        })
+        .filter(move |path| previous.replace(*path) != Some(*path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DiscoveryMode;
+    use rstest::rstest;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn git(root: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    fn repo_with_files() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        git(root, &["init", "-q"]);
+        fs::write(root.join(".gitignore"), "ignored.rs\n").unwrap();
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        fs::write(root.join("target/debug/built.rs"), "// TODO: built").unwrap();
+        fs::write(root.join("tracked.rs"), "// TODO: tracked").unwrap();
+        git(root, &["add", "-f", "tracked.rs", "target/debug/built.rs"]);
+        fs::write(root.join("untracked.rs"), "// TODO: untracked").unwrap();
+        fs::write(root.join("ignored.rs"), "// TODO: ignored").unwrap();
+        temp_dir
+    }
+
+    #[rstest]
+    #[case(b"a.rs\0b.rs\0", &["a.rs", "b.rs"])]
+    #[case(b"a.rs\0a.rs\0a.rs\0b.rs\0", &["a.rs", "b.rs"])]
+    #[case(b"dir/with space.rs\0", &["dir/with space.rs"])]
+    #[case(b"ok.rs\0\xff\xfe.rs\0", &["ok.rs"])]
+    #[case(b"", &[])]
+    fn test_parse_ls_files(#[case] stdout: &[u8], #[case] expected: &[&str]) {
+        assert_eq!(parse_ls_files(stdout).collect::<Vec<_>>(), expected);
+    }
+
+    #[rstest]
+    #[case::tracked(false, &["tracked.rs"])]
+    #[case::with_untracked(true, &[".gitignore", "tracked.rs", "untracked.rs"])]
+    fn test_git_files_lists_what_git_sees(#[case] untracked: bool, #[case] expected: &[&str]) {
+        let repo = repo_with_files();
+        let scanner = Scanner::new(crate::config::test_parsing_config()).unwrap();
+
+        let files = scanner.git_files(repo.path(), untracked).unwrap().unwrap();
+
+        // target/debug/built.rs is tracked but pruned by the target/* exclude
+        let mut names: Vec<_> = files
+            .iter()
+            .map(|f| f.strip_prefix(repo.path()).unwrap().to_str().unwrap())
+            .collect();
+        names.sort_unstable();
+        assert_eq!(names, expected);
+    }
+
+    #[rstest]
+    #[case::walk(DiscoveryMode::Walk, &["tracked", "untracked"])]
+    #[case::git(DiscoveryMode::Git, &["tracked"])]
+    #[case::git_untracked(DiscoveryMode::GitUntracked, &["tracked", "untracked"])]
+    #[tokio::test]
+    async fn test_scan_uses_discovery_mode(
+        #[case] discovery: DiscoveryMode,
+        #[case] expected: &[&str],
+    ) {
+        let repo = repo_with_files();
+        let mut config = crate::config::test_parsing_config();
+        config.discovery = discovery;
+        let scanner = Scanner::new(config).unwrap();
+
+        let result = scanner.scan(repo.path().to_path_buf()).await.unwrap();
+
+        let mut descriptions: Vec<_> = result
+            .todos
+            .iter()
+            .map(|t| t.description.as_str())
+            .collect();
+        descriptions.sort_unstable();
+        assert_eq!(descriptions, expected);
+    }
+
+    #[tokio::test]
+    async fn test_git_discovery_outside_repo_walks() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("plain.rs"), "// TODO: plain").unwrap();
+        let mut config = crate::config::test_parsing_config();
+        config.discovery = DiscoveryMode::Git;
+        let scanner = Scanner::new(config).unwrap();
+
+        let result = scanner.scan(temp_dir.path().to_path_buf()).await.unwrap();
+
+        assert_eq!(result.todos.len(), 1);
+    }
+
+    #[test]
+    fn test_git_files_outside_repo_falls_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let scanner = Scanner::new(crate::config::test_parsing_config()).unwrap();
+
+        assert!(scanner.git_files(temp_dir.path(), false).unwrap().is_none());
+    }
+}
@@ -0,0 +1,584 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use futures::stream::{self, StreamExt};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdout, Command};
+use tracing::{debug, error, warn};
+
+use crate::comment::todo::TodoComment;
+
+use super::error::TowlScannerError;
+use super::limits::{ScanResult, MAX_CONCURRENT_FILES, MAX_FILES_SCANNED, MAX_FILE_SIZE};
+use super::types::Scanner;
+
+const SYMLINK_MODE: &str = "120000";
+
+/// A git revision resolved to a commit, so every blob is read from the same
+/// snapshot even if the ref moves during the scan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitRef {
+    commit: String,
+    link_name: String,
+}
+
+impl GitRef {
+    /// Resolves `git_ref` in the repository containing `repo`.
+    ///
+    /// # Errors
+    /// Returns `TowlScannerError::InvalidGitRef` if the ref does not name a
+    /// commit, or `TowlScannerError::GitCommandFailed` if git cannot be run.
+    pub async fn resolve(repo: &Path, git_ref: &str) -> Result<Self, TowlScannerError> {
+        let invalid = |message: &str| TowlScannerError::InvalidGitRef {
+            git_ref: git_ref.to_string(), // clone: error owns the ref
+            message: message.to_string(),
+        };
+        // A leading '-' would be parsed by git as an option
+        if git_ref.is_empty() || git_ref.starts_with('-') {
+            return Err(invalid("expected a branch, tag or commit"));
+        }
+
+        let cwd = existing_dir(repo);
+        let commit = git_stdout(
+            cwd,
+            "rev-parse",
+            &[
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("{git_ref}^{{commit}}"),
+            ],
+        )
+        .await?
+        .ok_or_else(|| invalid("not a commit in this repository"))?;
+
+        let full_name = git_stdout(
+            cwd,
+            "rev-parse",
+            &["rev-parse", "--symbolic-full-name", git_ref],
+        )
+        .await?
+        .unwrap_or_default();
+        let link_name = full_name
+            .strip_prefix("refs/heads/")
+            .or_else(|| full_name.strip_prefix("refs/tags/"))
+            .unwrap_or(&commit)
+            .to_string();
+
+        Ok(Self { commit, link_name })
+    }
+
+    /// The resolved commit SHA.
+    #[must_use]
+    pub fn commit(&self) -> &str {
+        &self.commit
+    }
+
+    /// The branch or tag name when the ref was one, otherwise the commit SHA.
+    /// Suitable for `blob/<ref>/` links.
+    #[must_use]
+    pub fn link_name(&self) -> &str {
+        &self.link_name
+    }
+}
+
+/// One blob from `git ls-tree -l`.
+#[derive(Debug, PartialEq, Eq)]
+struct TreeEntry {
+    object: String,
+    size: u64,
+    path: PathBuf,
+}
+
+impl Scanner {
+    /// Scans the files of a git commit without checking it out.
+    ///
+    /// Lists the tree under `path` with `git ls-tree` and streams the blobs
+    /// through a single `git cat-file --batch` process into the parser.
+    /// Reported file paths, `exclude_patterns` and `include_patterns` are all
+    /// relative to the repository root. Symlinks and submodules are skipped.
+    ///
+    /// # Errors
+    /// Returns `TowlScannerError::GitCommandFailed` if git cannot list or read
+    /// the tree, or `TowlScannerError::UnableToWalkFile` if exclude or include
+    /// patterns are invalid.
+    pub async fn scan_ref(
+        &self,
+        path: &Path,
+        git_ref: &GitRef,
+    ) -> Result<ScanResult, TowlScannerError> {
+        self.scan_ref_with(path, git_ref, |_| {}).await
+    }
+
+    /// Like [`Scanner::scan_ref`], but passes each file's TODOs to `on_found`
+    /// as soon as the file is parsed.
+    ///
+    /// # Errors
+    /// As for [`Scanner::scan_ref`].
+    pub async fn scan_ref_with(
+        &self,
+        path: &Path,
+        git_ref: &GitRef,
+        mut on_found: impl FnMut(&[TodoComment]),
+    ) -> Result<ScanResult, TowlScannerError> {
+        let scan_start = std::time::Instant::now();
+        debug!("Scanning {} at {}", path.display(), git_ref.commit());
+
+        let cwd = existing_dir(path);
+        let pathspec = if path.is_dir() {
+            Path::new(".")
+        } else {
+            path.file_name().map_or(Path::new("."), Path::new)
+        };
+        let (candidates, mut files_skipped, mut files_errored) =
+            self.select_tree_entries(cwd, git_ref, pathspec).await?;
+
+        let shebang_skipped = AtomicUsize::new(0);
+        let reader = BlobReader::spawn(cwd, &candidates)?;
+        let blobs = stream::unfold(
+            (Some(reader), candidates.into_iter()),
+            |(mut reader, mut entries)| async move {
+                let entry = entries.next()?;
+                let blob = match reader.as_mut() {
+                    Some(active) => active.next_blob().await,
+                    None => Err(TowlScannerError::GitCommandFailed {
+                        command: "cat-file",
+                        message: "stopped after an earlier error".to_string(),
+                    }),
+                };
+                // After a bad response the stream is out of step with our
+                // requests, so stop reading rather than mislabel blobs
+                if blob.is_err() {
+                    reader = None;
+                }
+                Some(((entry, blob), (reader, entries)))
+            },
+        );
+        let results = blobs
+            .filter_map(|(entry, blob)| {
+                let outcome = match blob {
+                    Ok(content) => self.blob_task(entry, content, &shebang_skipped),
+                    Err(e) => Some((entry.path, Err(e))),
+                };
+                async move { outcome }
+            })
+            .map(|(path, task)| async move {
+                let result = match task {
+                    Ok(content) => self.parse_content(&path, content).await,
+                    Err(e) => Err(e),
+                };
+                (path, result)
+            })
+            .buffer_unordered(MAX_CONCURRENT_FILES);
+
+        let (todos, files_scanned, errored) = Self::collect_results(results, &mut on_found).await;
+        files_errored += errored;
+        files_skipped += shebang_skipped.into_inner();
+
+        Ok(Self::finish_scan(
+            todos,
+            files_scanned,
+            files_skipped,
+            files_errored,
+            scan_start,
+        ))
+    }
+
+    /// Lists the tree and drops entries that cannot be scanned, returning the
+    /// candidates plus skipped and errored (oversized) counts.
+    async fn select_tree_entries(
+        &self,
+        cwd: &Path,
+        git_ref: &GitRef,
+        pathspec: &Path,
+    ) -> Result<(Vec<TreeEntry>, usize, usize), TowlScannerError> {
+        let pathspec = pathspec.to_string_lossy();
+        let stdout = git_stdout_bytes(
+            cwd,
+            "ls-tree",
+            &[
+                "ls-tree",
+                "-r",
+                "-z",
+                "-l",
+                "--full-name",
+                git_ref.commit(),
+                "--",
+                &pathspec,
+            ],
+        )
+        .await?;
+
+        let root = Path::new("");
+        let filter = self.build_path_filter(root)?;
+        let mut candidates = Vec::new();
+        let mut files_skipped: usize = 0;
+        let mut files_errored: usize = 0;
+
+        for entry in parse_ls_tree(&stdout) {
+            if !filter.allows_file(root, &entry.path) {
+                continue;
+            }
+            if !self.matches_file_name(&entry.path) && entry.path.extension().is_some() {
+                debug!("{} will not be scanned", entry.path.display());
+                files_skipped += 1;
+                continue;
+            }
+            if entry.size > MAX_FILE_SIZE {
+                error!(
+                    "Error scanning {}: {}",
+                    entry.path.display(),
+                    TowlScannerError::FileTooLarge {
+                        path: entry.path.clone(), // clone: error owns the path, entry is dropped
+                        size: entry.size,
+                        max_allowed: MAX_FILE_SIZE,
+                    }
+                );
+                files_errored += 1;
+                continue;
+            }
+            if candidates.len() >= MAX_FILES_SCANNED {
+                warn!(
+                    "File scan limit reached ({} files), stopping discovery",
+                    MAX_FILES_SCANNED
+                );
+                break;
+            }
+            candidates.push(entry);
+        }
+
+        Ok((candidates, files_skipped, files_errored))
+    }
+
+    /// Decides what to do with a fetched blob: `None` to skip it (an
+    /// extensionless file without a known shebang), otherwise the path and
+    /// its text, or an error if it is not UTF-8.
+    fn blob_task(
+        &self,
+        entry: TreeEntry,
+        content: Vec<u8>,
+        shebang_skipped: &AtomicUsize,
+    ) -> Option<(PathBuf, Result<String, TowlScannerError>)> {
+        let text = match String::from_utf8(content) {
+            Ok(text) => text,
+            Err(e) => {
+                let err = std::io::Error::new(std::io::ErrorKind::InvalidData, e);
+                let path = entry.path.clone(); // clone: error and result both need the path
+                return Some((
+                    entry.path,
+                    Err(TowlScannerError::UnableToReadFileAtPath(path, err)),
+                ));
+            }
+        };
+        if !self.matches_file_name(&entry.path) && !self.matches_shebang(&entry.path, &text) {
+            debug!("{} will not be scanned", entry.path.display());
+            shebang_skipped.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        Some((entry.path, Ok(text)))
+    }
+}
+
+/// A running `git cat-file --batch` that returns blobs in request order.
+struct BlobReader {
+    _child: Child,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl BlobReader {
+    fn spawn(cwd: &Path, entries: &[TreeEntry]) -> Result<Self, TowlScannerError> {
+        let cat_file_err = |message: String| TowlScannerError::GitCommandFailed {
+            command: "cat-file",
+            message,
+        };
+        let mut child = Command::new("git")
+            .args(["cat-file", "--batch"])
+            .current_dir(cwd)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| cat_file_err(e.to_string()))?;
+
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| cat_file_err("stdin unavailable".to_string()))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| cat_file_err("stdout unavailable".to_string()))?;
+
+        // Requests are written from a separate task so a full stdout pipe
+        // cannot block us while git waits for its stdin to drain.
+        let request: String = entries.iter().map(|e| format!("{}\n", e.object)).collect();
+        tokio::spawn(async move {
+            if let Err(e) = stdin.write_all(request.as_bytes()).await {
+                debug!("Failed to send object ids to git cat-file: {e}");
+            }
+        });
+
+        Ok(Self {
+            _child: child,
+            stdout: BufReader::new(stdout),
+        })
+    }
+
+    /// Reads the next `<oid> <type> <size>\n<content>\n` response.
+    async fn next_blob(&mut self) -> Result<Vec<u8>, TowlScannerError> {
+        let protocol_err = |message: String| TowlScannerError::GitCommandFailed {
+            command: "cat-file",
+            message,
+        };
+
+        let mut header = String::new();
+        self.stdout
+            .read_line(&mut header)
+            .await
+            .map_err(|e| protocol_err(e.to_string()))?;
+        let size = parse_batch_header(&header)
+            .ok_or_else(|| protocol_err(format!("unexpected response '{}'", header.trim())))?;
+
+        let mut content = vec![0; size + 1];
+        self.stdout
+            .read_exact(&mut content)
+            .await
+            .map_err(|e| protocol_err(e.to_string()))?;
+        content.pop();
+        Ok(content)
+    }
+}
+
+/// Returns the blob size from a `cat-file --batch` header line.
+fn parse_batch_header(header: &str) -> Option<usize> {
+    let mut fields = header.split_whitespace();
+    let _object = fields.next()?;
+    if fields.next()? != "blob" {
+        return None;
+    }
+    fields.next()?.parse().ok()
+}
+
+/// Parses `git ls-tree -r -z -l` output, keeping only regular-file blobs.
+fn parse_ls_tree(stdout: &[u8]) -> impl Iterator<Item = TreeEntry> + '_ {
+    stdout
+        .split(|&b| b == 0)
+        .filter(|record| !record.is_empty())
+        .filter_map(|record| {
+            let Ok(record) = std::str::from_utf8(record) else {
+                debug!(
+                    "Skipping non UTF-8 path from git: {}",
+                    String::from_utf8_lossy(record)
+                );
+                return None;
+            };
+            let (meta, path) = record.split_once('\t')?;
+            let mut fields = meta.split_whitespace();
+            let mode = fields.next()?;
+            let kind = fields.next()?;
+            let object = fields.next()?;
+            let size = fields.next()?.parse().ok()?;
+            (kind == "blob" && mode != SYMLINK_MODE).then(|| TreeEntry {
+                object: object.to_string(),
+                size,
+                path: PathBuf::from(path),
+            })
+        })
+}
+
+/// `path` if it is a directory, otherwise its parent (or `.`).
+fn existing_dir(path: &Path) -> &Path {
+    if path.is_dir() {
+        return path;
+    }
+    path.parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+}
+
+async fn git_stdout_bytes(
+    cwd: &Path,
+    command: &'static str,
+    args: &[&str],
+) -> Result<Vec<u8>, TowlScannerError> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .await
+        .map_err(|e| TowlScannerError::GitCommandFailed {
+            command,
+            message: e.to_string(),
+        })?;
+    if !output.status.success() {
+        return Err(TowlScannerError::GitCommandFailed {
+            command,
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(output.stdout)
+}
+
+/// Runs a git query, returning `None` when git exits unsuccessfully.
+async fn git_stdout(
+    cwd: &Path,
+    command: &'static str,
+    args: &[&str],
+) -> Result<Option<String>, TowlScannerError> {
+    match git_stdout_bytes(cwd, command, args).await {
+        Ok(stdout) => Ok(Some(String::from_utf8_lossy(&stdout).trim().to_string())),
+        Err(TowlScannerError::GitCommandFailed { message, .. })
+            if !message.contains("not a git repository") =>
+        {
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::fs;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn git(root: &Path, args: &[&str]) {
+        let status = StdCommand::new("git")
+            .args(["-c", "user.name=towl", "-c", "user.email=towl@example.com"])
+            .args(args)
+            .current_dir(root)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    /// A repo whose `v1` tag has different TODOs from the working tree.
+    fn tagged_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        git(root, &["init", "-q"]);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("target")).unwrap();
+        fs::write(root.join("src/lib.rs"), "fn a() {}\n// TODO: from v1\n").unwrap();
+        fs::write(root.join("target/out.rs"), "// TODO: excluded").unwrap();
+        fs::write(root.join("deploy"), "#!/bin/sh\n# FIXME: script at v1\n").unwrap();
+        fs::write(root.join("LICENSE"), "# TODO: not source").unwrap();
+        git(root, &["add", "-A"]);
+        git(root, &["commit", "-q", "-m", "v1"]);
+        git(root, &["tag", "v1"]);
+        git(root, &["branch", "release-1"]);
+        fs::write(root.join("src/lib.rs"), "// TODO: working tree\n").unwrap();
+        git(root, &["commit", "-q", "-am", "v2"]);
+        temp_dir
+    }
+
+    fn scanner() -> Scanner {
+        let mut config = crate::config::test_parsing_config();
+        config.languages = crate::config::ParsingConfig::default().languages;
+        Scanner::new(config).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_scan_ref_reads_blobs_from_the_ref() {
+        let repo = tagged_repo();
+        let git_ref = GitRef::resolve(repo.path(), "v1").await.unwrap();
+
+        let result = scanner().scan_ref(repo.path(), &git_ref).await.unwrap();
+
+        let mut found: Vec<_> = result
+            .todos
+            .iter()
+            .map(|t| {
+                (
+                    t.file_path.to_str().unwrap(),
+                    t.line_number,
+                    t.description.as_str(),
+                )
+            })
+            .collect();
+        found.sort_unstable();
+        assert_eq!(
+            found,
+            vec![("deploy", 2, "script at v1"), ("src/lib.rs", 2, "from v1")]
+        );
+        assert_eq!(result.files_scanned, 2);
+        assert_eq!(result.files_skipped, 1, "LICENSE has no shebang");
+    }
+
+    #[tokio::test]
+    async fn test_scan_ref_from_subdirectory_keeps_repo_relative_paths() {
+        let repo = tagged_repo();
+        let src = repo.path().join("src");
+        let git_ref = GitRef::resolve(&src, "v1").await.unwrap();
+
+        let result = scanner().scan_ref(&src, &git_ref).await.unwrap();
+
+        assert_eq!(result.todos.len(), 1);
+        assert_eq!(result.todos[0].file_path, PathBuf::from("src/lib.rs"));
+    }
+
+    #[rstest]
+    #[case::tag("v1", Some("v1"))]
+    #[case::branch("release-1", Some("release-1"))]
+    #[case::expression("v1~0", None)]
+    #[tokio::test]
+    async fn test_resolve_link_name(#[case] name: &str, #[case] expected: Option<&str>) {
+        let repo = tagged_repo();
+
+        let git_ref = GitRef::resolve(repo.path(), name).await.unwrap();
+
+        assert_eq!(git_ref.commit().len(), 40);
+        let expected = expected.unwrap_or(git_ref.commit());
+        assert_eq!(git_ref.link_name(), expected);
+    }
+
+    #[rstest]
+    #[case("no-such-ref")]
+    #[case("--output=/tmp/x")]
+    #[case("")]
+    #[tokio::test]
+    async fn test_resolve_rejects_bad_refs(#[case] name: &str) {
+        let repo = tagged_repo();
+
+        let result = GitRef::resolve(repo.path(), name).await;
+
+        assert!(matches!(
+            result,
+            Err(TowlScannerError::InvalidGitRef { .. })
+        ));
+    }
+
+    #[rstest]
+    #[case(
+        b"100644 blob abc      12\tsrc/a.rs\0",
+        vec![("abc", 12, "src/a.rs")]
+    )]
+    #[case(b"120000 blob abc 5\tlink.rs\0", vec![])]
+    #[case(b"160000 commit abc       -\tvendor\0", vec![])]
+    #[case(b"100644 blob abc 1\twith space.rs\0", vec![("abc", 1, "with space.rs")])]
+    #[case(b"", vec![])]
+    fn test_parse_ls_tree(#[case] stdout: &[u8], #[case] expected: Vec<(&str, u64, &str)>) {
+        let entries: Vec<_> = parse_ls_tree(stdout).collect();
+        let expected: Vec<_> = expected
+            .into_iter()
+            .map(|(object, size, path)| TreeEntry {
+                object: object.to_string(),
+                size,
+                path: PathBuf::from(path),
+            })
+            .collect();
+        assert_eq!(entries, expected);
+    }
+
+    #[rstest]
+    #[case("abc blob 12\n", Some(12))]
+    #[case("abc missing\n", None)]
+    #[case("abc tree 30\n", None)]
+    #[case("", None)]
+    fn test_parse_batch_header(#[case] header: &str, #[case] expected: Option<usize>) {
+        assert_eq!(parse_batch_header(header), expected);
+    }
+}
@@ -8,6 +8,10 @@ pub const MAX_TODO_COUNT: usize = 10_000;
 pub const MAX_TOTAL_TODO_COUNT: usize = 100_000;
 /// Maximum number of files to discover during directory traversal.
 pub const MAX_FILES_SCANNED: usize = 100_000;
+/// Maximum number of files read and parsed at the same time.
+pub const MAX_CONCURRENT_FILES: usize = 64;
+/// Bytes read from an extensionless file when looking for a shebang line.
+pub const MAX_SHEBANG_LENGTH: usize = 256;
 
 /// Structured result from a scan operation, distinguishing "no TODOs found"
 /// from "all files failed to scan".
@@ -5,10 +5,15 @@
 //! bounded parallelism. Resource limits prevent excessive memory use on
 //! large codebases.
 
+mod archive;
 pub mod error;
+mod git_files;
+mod git_ref;
 mod limits;
 mod types;
 mod walker;
 
+pub use archive::ARCHIVE_ENTRY_SEPARATOR;
+pub use git_ref::GitRef;
 pub use limits::ScanResult;
 pub use types::Scanner;
@@ -1,22 +1,33 @@
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use futures::stream::{self, StreamExt};
+use futures::stream::{self, Stream, StreamExt};
+use tokio::io::{AsyncRead, AsyncReadExt};
 use tracing::{debug, error, info, warn};
 
-use crate::{comment::todo::TodoComment, config::ParsingConfig, parser::Parser};
+use crate::{
+    comment::todo::TodoComment,
+    config::{DiscoveryMode, ParsingConfig},
+    parser::Parser,
+};
 
+use super::archive::ArchiveKind;
 use super::error::TowlScannerError;
 use super::limits::{
-    ScanResult, MAX_FILES_SCANNED, MAX_FILE_SIZE, MAX_TODO_COUNT, MAX_TOTAL_TODO_COUNT,
+    ScanResult, MAX_CONCURRENT_FILES, MAX_FILES_SCANNED, MAX_FILE_SIZE, MAX_SHEBANG_LENGTH,
+    MAX_TODO_COUNT, MAX_TOTAL_TODO_COUNT,
 };
 
 /// Scans files for TODO comments with configurable patterns and resource limits.
 ///
 /// The scanner walks directory trees, filtering files by extension and exclude patterns,
-/// while enforcing safety limits to prevent resource exhaustion.
+/// while enforcing safety limits to prevent resource exhaustion. File reads are
+/// async; parsing is CPU-bound and runs on tokio's blocking thread pool.
+#[derive(Clone)]
 pub struct Scanner {
-    pub(super) parser: Parser,
-    pub(super) config: ParsingConfig,
+    pub(super) parser: Arc<Parser>,
+    pub(super) config: Arc<ParsingConfig>,
 }
 
 impl Scanner {
@@ -36,25 +47,56 @@ impl Scanner {
     /// ```
     pub fn new(config: ParsingConfig) -> Result<Self, TowlScannerError> {
         let parser = Parser::new(&config).map_err(TowlScannerError::ParsingError)?;
-        Ok(Self { parser, config })
+        Ok(Self {
+            parser: Arc::new(parser),
+            config: Arc::new(config),
+        })
     }
 
     /// See: <https://github.com/glottologist/towl/issues/6>
+    ///
+    /// A file is scanned if its extension is in `file_extensions`, its name
+    /// matches a language's `filenames`, or it has no extension and a shebang
+    /// naming a language's interpreter. Symlinks are only scanned when
+    /// `follow_symlinks` is set.
     fn should_file_be_scanned(&self, path: &Path) -> bool {
-        let Ok(metadata) = path.symlink_metadata() else {
+        let metadata = if self.config.follow_symlinks {
+            path.metadata()
+        } else {
+            path.symlink_metadata()
+        };
+        let Ok(metadata) = metadata else {
             return false;
         };
         if !metadata.is_file() {
             return false;
         }
 
-        if let Some(extension) = path.extension() {
-            if let Some(ext_str) = extension.to_str() {
-                return self.config.file_extensions.contains(ext_str);
-            }
+        if self.matches_file_name(path) {
+            return true;
         }
 
-        false
+        path.extension().is_none()
+            && read_shebang(path).is_some_and(|line| self.parser.matches_language_shebang(&line))
+    }
+
+    /// The name-only part of [`Self::should_file_be_scanned`]: a configured
+    /// extension or a language filename glob.
+    pub(super) fn matches_file_name(&self, path: &Path) -> bool {
+        let has_extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| self.config.file_extensions.contains(ext));
+        has_extension || self.parser.matches_language_filename(path)
+    }
+
+    /// Whether an extensionless file should be scanned based on its content's shebang.
+    pub(super) fn matches_shebang(&self, path: &Path, content: &str) -> bool {
+        path.extension().is_none()
+            && content
+                .lines()
+                .next()
+                .is_some_and(|line| self.parser.matches_language_shebang(line))
     }
 
     pub(super) async fn scan_file(
@@ -90,9 +132,21 @@ impl Scanner {
             .await
             .map_err(to_read_err)?;
 
-        let todos = self
-            .parser
-            .parse(path, &content)
+        self.parse_content(path, content).await
+    }
+
+    /// Parses already-read file content on the blocking pool and enforces
+    /// the per-file TODO limit.
+    pub(super) async fn parse_content(
+        &self,
+        path: &Path,
+        content: String,
+    ) -> Result<Vec<TodoComment>, TowlScannerError> {
+        let parser = Arc::clone(&self.parser);
+        let owned_path = path.to_path_buf(); // clone: blocking task needs an owned path
+        let todos = tokio::task::spawn_blocking(move || parser.parse(&owned_path, &content))
+            .await
+            .map_err(TowlScannerError::ParseTaskFailed)?
             .map_err(TowlScannerError::ParsingError)?;
 
         if todos.len() > MAX_TODO_COUNT {
@@ -130,13 +184,51 @@ impl Scanner {
     }
 
     fn discover_files(&self, path: &Path) -> Result<(Vec<PathBuf>, usize), TowlScannerError> {
+        let untracked = match self.config.discovery {
+            DiscoveryMode::Walk => None,
+            DiscoveryMode::Git => Some(false),
+            DiscoveryMode::GitUntracked => Some(true),
+        };
+        if let Some(untracked) = untracked {
+            if let Some(files) = self.git_files(path, untracked)? {
+                return self.select_scannable(path, files.into_iter().map(Ok));
+            }
+            info!(
+                "{} is not inside a git work tree, walking the directory instead",
+                path.display()
+            );
+        }
+
         let file_walker = self.build_walker(path)?;
+        let entries = file_walker.filter_map(|entry| match entry {
+            Ok(entry) => Some(Ok(entry.into_path())),
+            Err(e) if is_symlink_loop(&e) => {
+                warn!("Skipping symlink cycle: {e}");
+                None
+            }
+            Err(e) => Some(Err(TowlScannerError::UnableToWalkFile(e))),
+        });
+        self.select_scannable(path, entries)
+    }
+
+    /// Keeps the candidates that should be scanned. When following symlinks,
+    /// files are deduplicated by their canonical path and, unless allowed,
+    /// files resolving outside `root` are skipped.
+    fn select_scannable(
+        &self,
+        root: &Path,
+        candidates: impl Iterator<Item = Result<PathBuf, TowlScannerError>>,
+    ) -> Result<(Vec<PathBuf>, usize), TowlScannerError> {
         let mut scannable_paths = Vec::new();
         let mut files_skipped: usize = 0;
-
-        for walk_result in file_walker {
-            let entry = walk_result.map_err(TowlScannerError::UnableToWalkFile)?;
-            if self.should_file_be_scanned(entry.path()) {
+        let confine_to = self.symlink_root(root);
+        let mut seen = HashSet::new();
+
+        for candidate in candidates {
+            let path = candidate?;
+            if self.should_file_be_scanned(&path)
+                && self.is_first_sighting(&path, confine_to.as_deref(), &mut seen)
+            {
                 if scannable_paths.len() >= MAX_FILES_SCANNED {
                     warn!(
                         "File scan limit reached ({} files), stopping discovery",
@@ -144,9 +236,9 @@ impl Scanner {
                     );
                     break;
                 }
-                scannable_paths.push(entry.into_path());
+                scannable_paths.push(path);
             } else {
-                debug!("{} will not be scanned", entry.path().display());
+                debug!("{} will not be scanned", path.display());
                 files_skipped += 1;
             }
         }
@@ -154,7 +246,28 @@ impl Scanner {
         Ok((scannable_paths, files_skipped))
     }
 
-    fn accumulate_result(
+    /// Returns false for a followed-symlink path whose real file was already
+    /// selected or lies outside `confine_to`. Always true when not following.
+    fn is_first_sighting(
+        &self,
+        path: &Path,
+        confine_to: Option<&Path>,
+        seen: &mut HashSet<PathBuf>,
+    ) -> bool {
+        if !self.config.follow_symlinks {
+            return true;
+        }
+        let Ok(real) = path.canonicalize() else {
+            return false;
+        };
+        if confine_to.is_some_and(|root| !real.starts_with(root)) {
+            debug!("{} resolves outside the scan root", path.display());
+            return false;
+        }
+        seen.insert(real)
+    }
+
+    pub(super) fn accumulate_result(
         file_path: &Path,
         result: Result<Vec<TodoComment>, TowlScannerError>,
         todos: &mut Vec<TodoComment>,
@@ -181,21 +294,32 @@ impl Scanner {
     async fn scan_files_concurrently(
         &self,
         paths: Vec<PathBuf>,
+        on_found: &mut impl FnMut(&[TodoComment]),
     ) -> (Vec<TodoComment>, usize, usize) {
-        const CONCURRENCY_LIMIT: usize = 64;
-
-        let mut todos = Vec::new();
-        let mut files_scanned: usize = 0;
-        let mut files_errored: usize = 0;
-
-        let mut result_stream = stream::iter(paths)
+        let results = stream::iter(paths)
             .map(|file_path| async move {
                 let result = self.scan_file(&file_path).await;
                 (file_path, result)
             })
-            .buffer_unordered(CONCURRENCY_LIMIT);
+            .buffer_unordered(MAX_CONCURRENT_FILES);
+
+        Self::collect_results(results, on_found).await
+    }
 
-        while let Some((file_path, result)) = result_stream.next().await {
+    /// Drains per-file scan results, counting scanned and errored files and
+    /// stopping once the aggregate TODO limit is exceeded. Each file's TODOs
+    /// are passed to `on_found` as they arrive.
+    pub(super) async fn collect_results(
+        results: impl Stream<Item = (PathBuf, Result<Vec<TodoComment>, TowlScannerError>)>,
+        on_found: &mut impl FnMut(&[TodoComment]),
+    ) -> (Vec<TodoComment>, usize, usize) {
+        let mut todos = Vec::new();
+        let mut files_scanned: usize = 0;
+        let mut files_errored: usize = 0;
+
+        let mut results = std::pin::pin!(results);
+        while let Some((file_path, result)) = results.next().await {
+            let already_found = todos.len();
             Self::accumulate_result(
                 &file_path,
                 result,
@@ -211,17 +335,52 @@ impl Scanner {
                     MAX_TOTAL_TODO_COUNT
                 );
                 todos.truncate(MAX_TOTAL_TODO_COUNT);
+                on_found(&todos[already_found.min(todos.len())..]);
                 break;
             }
+            if todos.len() > already_found {
+                on_found(&todos[already_found..]);
+            }
         }
 
         (todos, files_scanned, files_errored)
     }
 
+    /// Logs scan metrics and packages them into a [`ScanResult`].
+    pub(super) fn finish_scan(
+        todos: Vec<TodoComment>,
+        files_scanned: usize,
+        files_skipped: usize,
+        files_errored: usize,
+        scan_start: std::time::Instant,
+    ) -> ScanResult {
+        let elapsed = scan_start.elapsed();
+        Self::log_scan_metrics(
+            files_scanned,
+            files_skipped,
+            files_errored,
+            todos.len(),
+            elapsed,
+        );
+
+        ScanResult {
+            todos,
+            files_scanned,
+            files_skipped,
+            files_errored,
+            duration: elapsed,
+        }
+    }
+
     /// Recursively scans a directory for TODO comments in supported files.
     ///
     /// Walks the directory tree starting at `path`, then scans matching files
-    /// concurrently with bounded parallelism.
+    /// concurrently with bounded parallelism. Parsing runs on the blocking
+    /// thread pool so large scans use every available core.
+    ///
+    /// If `path` is a `.tar`, `.tar.gz`/`.tgz`, `.crate` or `.zip` file, its
+    /// entries are scanned in place instead and reported as
+    /// `archive.tar.gz!/inner/path.rs`.
     ///
     /// # Resource Limits
     /// - Rejects files larger than 10 MB (counted as errored)
@@ -232,7 +391,8 @@ impl Scanner {
     /// The scan continues processing remaining files.
     ///
     /// # Errors
-    /// Returns `TowlScannerError` if directory traversal fails.
+    /// Returns `TowlScannerError` if directory traversal fails or an archive
+    /// cannot be read.
     ///
     /// # Example
     /// ```no_run
@@ -252,37 +412,157 @@ impl Scanner {
     /// # }
     /// ```
     pub async fn scan(&self, path: PathBuf) -> Result<ScanResult, TowlScannerError> {
+        self.scan_with(path, |_| {}).await
+    }
+
+    /// Like [`Scanner::scan`], but passes each file's TODOs to `on_found` as
+    /// soon as the file is parsed, so callers can stream results. Files
+    /// arrive in completion order, not path order.
+    ///
+    /// # Errors
+    /// Returns `TowlScannerError` if directory traversal fails or an archive
+    /// cannot be read.
+    pub async fn scan_with(
+        &self,
+        path: PathBuf,
+        mut on_found: impl FnMut(&[TodoComment]),
+    ) -> Result<ScanResult, TowlScannerError> {
         let scan_start = std::time::Instant::now();
         debug!("Scanning {}", path.display());
 
-        let (scannable_paths, files_skipped) = self.discover_files(&path)?;
-        let (todos, files_scanned, files_errored) =
-            self.scan_files_concurrently(scannable_paths).await;
+        if let Some(kind) = ArchiveKind::from_path(&path).filter(|_| path.is_file()) {
+            return self.scan_archive(&path, kind, &mut on_found).await;
+        }
 
-        let elapsed = scan_start.elapsed();
-        Self::log_scan_metrics(
-            files_scanned,
-            files_skipped,
-            files_errored,
-            todos.len(),
-            elapsed,
-        );
+        let (scannable_paths, files_skipped) = self.discover_files(&path)?;
+        let (todos, files_scanned, files_errored) = self
+            .scan_files_concurrently(scannable_paths, &mut on_found)
+            .await;
 
-        Ok(ScanResult {
+        Ok(Self::finish_scan(
             todos,
             files_scanned,
             files_skipped,
             files_errored,
-            duration: elapsed,
-        })
+            scan_start,
+        ))
+    }
+
+    /// Scans in-memory content as if it were the file at `path`, e.g. an
+    /// unsaved editor buffer. `path` need not exist; it selects the comment
+    /// syntax and is reported as the TODOs' file path. `file_extensions` and
+    /// exclude/include patterns are not applied.
+    ///
+    /// # Errors
+    /// Returns `TowlScannerError::FileTooLarge` or `TowlScannerError::TooManyTodos`
+    /// if the content exceeds the per-file limits, or
+    /// `TowlScannerError::ParsingError` if parsing fails.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use towl::scanner::Scanner;
+    /// use towl::config::ParsingConfig;
+    /// use std::path::Path;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let scanner = Scanner::new(ParsingConfig::default())?;
+    /// let result = scanner
+    ///     .scan_content(Path::new("src/foo.rs"), "// TODO: unsaved\n")
+    ///     .await?;
+    /// assert_eq!(result.todos.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn scan_content(
+        &self,
+        path: &Path,
+        content: &str,
+    ) -> Result<ScanResult, TowlScannerError> {
+        let scan_start = std::time::Instant::now();
+
+        let size = u64::try_from(content.len()).unwrap_or(u64::MAX);
+        if size > MAX_FILE_SIZE {
+            return Err(TowlScannerError::FileTooLarge {
+                path: path.to_path_buf(), // clone: owned path for error variant
+                size,
+                max_allowed: MAX_FILE_SIZE,
+            });
+        }
+
+        let todos = self.parse_content(path, content.to_string()).await?; // clone: blocking parse needs owned content
+
+        Ok(Self::finish_scan(todos, 1, 0, 0, scan_start))
+    }
+
+    /// Reads `reader` to its end and scans it like [`Scanner::scan_content`],
+    /// e.g. for stdin. At most `MAX_FILE_SIZE` bytes are buffered, so an
+    /// oversized or endless stream fails without exhausting memory.
+    ///
+    /// # Errors
+    /// Returns `TowlScannerError::FileTooLarge` once more than
+    /// `MAX_FILE_SIZE` bytes have been read,
+    /// `TowlScannerError::UnableToReadFileAtPath` if reading fails or the
+    /// content is not UTF-8, and otherwise the errors of `scan_content`.
+    pub async fn scan_reader(
+        &self,
+        path: &Path,
+        reader: impl AsyncRead + Unpin,
+    ) -> Result<ScanResult, TowlScannerError> {
+        let to_read_err = |e| TowlScannerError::UnableToReadFileAtPath(path.to_path_buf(), e); // clone: owned path for error closure
+        let mut bytes = Vec::new();
+        reader
+            .take(MAX_FILE_SIZE + 1)
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(to_read_err)?;
+        let read = u64::try_from(bytes.len()).unwrap_or(u64::MAX);
+        if read > MAX_FILE_SIZE {
+            return Err(TowlScannerError::FileTooLarge {
+                path: path.to_path_buf(), // clone: owned path for error variant
+                size: read,
+                max_allowed: MAX_FILE_SIZE,
+            });
+        }
+        let content = String::from_utf8(bytes)
+            .map_err(|e| to_read_err(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))?;
+
+        self.scan_content(path, &content).await
     }
 }
 
+/// Returns true if a walk error is a symlink cycle, which is skipped rather
+/// than aborting the scan.
+fn is_symlink_loop(error: &ignore::Error) -> bool {
+    match error {
+        ignore::Error::Loop { .. } => true,
+        ignore::Error::WithPath { err, .. }
+        | ignore::Error::WithDepth { err, .. }
+        | ignore::Error::WithLineNumber { err, .. } => is_symlink_loop(err),
+        _ => false,
+    }
+}
+
+/// Reads the first line of a file if it starts with `#!`.
+fn read_shebang(path: &Path) -> Option<String> {
+    use std::io::Read;
+
+    let mut buf = [0u8; MAX_SHEBANG_LENGTH];
+    let mut file = std::fs::File::open(path).ok()?;
+    let len = file.read(&mut buf).ok()?;
+    let head = buf.get(..len)?;
+    if !head.starts_with(b"#!") {
+        return None;
+    }
+    let line = head.split(|&b| b == b'\n').next()?;
+    Some(String::from_utf8_lossy(line).into_owned())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::scanner::limits::{MAX_FILE_SIZE, MAX_TODO_COUNT};
     use proptest::prelude::*;
+    use rstest::rstest;
     use std::fmt::Write;
     use std::fs;
     use tempfile::TempDir;
@@ -476,6 +756,145 @@ def main():
         assert!(result.todos[0].description.contains("from source"));
     }
 
+    #[rstest]
+    #[case::include_narrows(&["src/**"], &["from src"])]
+    #[case::include_cannot_reinclude_gitignored(&["src/**", "generated/**"], &["from src"])]
+    #[case::include_cannot_reinclude_excluded(&["target/**", "src/**"], &["from src"])]
+    #[case::nested_glob(&["**/lib.rs"], &["from src"])]
+    #[case::no_match(&["docs/**"], &[])]
+    #[case::empty_scans_everything(&[], &["from root", "from src"])]
+    #[tokio::test]
+    async fn test_include_patterns_precedence(#[case] include: &[&str], #[case] expected: &[&str]) {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir(root.join(".git")).unwrap();
+        fs::write(root.join(".gitignore"), "generated/\n").unwrap();
+        for dir in ["src", "generated", "target"] {
+            fs::create_dir(root.join(dir)).unwrap();
+            fs::write(
+                root.join(dir).join("lib.rs"),
+                format!("// TODO: from {dir}"),
+            )
+            .unwrap();
+        }
+        fs::write(root.join("main.rs"), "// TODO: from root").unwrap();
+
+        let mut config = crate::config::test_parsing_config();
+        config.include_patterns = include.iter().map(ToString::to_string).collect();
+        let scanner = Scanner::new(config).unwrap();
+
+        let result = scanner.scan(root.to_path_buf()).await.unwrap();
+
+        let mut descriptions: Vec<_> = result
+            .todos
+            .iter()
+            .map(|t| t.description.as_str())
+            .collect();
+        descriptions.sort_unstable();
+        assert_eq!(descriptions, expected);
+    }
+
+    #[rstest]
+    #[case::rust("src/foo.rs", "fn a() {}\n// TODO: unsaved", Some(2))]
+    #[case::python_hash("tools/gen.py", "# TODO: unsaved", Some(1))]
+    #[case::python_ignores_slashes("tools/gen.py", "// TODO: unsaved", None)]
+    #[case::by_filename("Dockerfile", "FROM x\n# TODO: unsaved", Some(2))]
+    #[case::unscanned_extension("notes.md", "// TODO: unsaved", Some(1))]
+    #[tokio::test]
+    async fn test_scan_content_uses_virtual_name(
+        #[case] name: &str,
+        #[case] content: &str,
+        #[case] expected_line: Option<usize>,
+    ) {
+        let mut config = crate::config::test_parsing_config();
+        config.languages = ParsingConfig::default().languages;
+        let scanner = Scanner::new(config).unwrap();
+
+        let result = scanner
+            .scan_content(Path::new(name), content)
+            .await
+            .unwrap();
+
+        assert_eq!(result.files_scanned, 1);
+        let found: Vec<_> = result
+            .todos
+            .iter()
+            .map(|t| (t.file_path.as_path(), t.line_number))
+            .collect();
+        let expected: Vec<_> = expected_line
+            .map(|line| (Path::new(name), line))
+            .into_iter()
+            .collect();
+        assert_eq!(found, expected);
+    }
+
+    #[tokio::test]
+    async fn test_scan_content_rejects_oversized_content() {
+        let scanner = Scanner::new(crate::config::test_parsing_config()).unwrap();
+        let content = "a".repeat(usize::try_from(MAX_FILE_SIZE).unwrap() + 1);
+
+        let result = scanner.scan_content(Path::new("big.rs"), &content).await;
+
+        assert!(matches!(result, Err(TowlScannerError::FileTooLarge { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_scan_reader_stops_at_size_limit() {
+        let scanner = Scanner::new(crate::config::test_parsing_config()).unwrap();
+
+        let result = scanner
+            .scan_reader(Path::new("endless.rs"), tokio::io::repeat(b'a'))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(TowlScannerError::FileTooLarge { size, .. }) if size == MAX_FILE_SIZE + 1
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_scan_reader_scans_content() {
+        let scanner = Scanner::new(crate::config::test_parsing_config()).unwrap();
+
+        let result = scanner
+            .scan_reader(Path::new("a.rs"), &b"// TODO: piped\n"[..])
+            .await
+            .unwrap();
+
+        assert_eq!(result.todos.len(), 1);
+        assert_eq!(result.todos[0].description, "piped");
+    }
+
+    #[tokio::test]
+    async fn test_extensionless_files_matched_by_name_and_shebang() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("Dockerfile"), "# TODO: pin base image").unwrap();
+        fs::write(temp_dir.path().join("Makefile"), "# FIXME: parallel build").unwrap();
+        fs::write(
+            temp_dir.path().join("deploy"),
+            "#!/usr/bin/env python3\n# TODO: python script\n",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("LICENSE"), "# TODO: not a script").unwrap();
+
+        let mut config = crate::config::test_parsing_config();
+        config.languages = ParsingConfig::default().languages;
+        let scanner = Scanner::new(config).unwrap();
+
+        let result = scanner.scan(temp_dir.path().to_path_buf()).await.unwrap();
+
+        let mut descriptions: Vec<_> = result
+            .todos
+            .iter()
+            .map(|t| t.description.as_str())
+            .collect();
+        descriptions.sort_unstable();
+        assert_eq!(
+            descriptions,
+            vec!["parallel build", "pin base image", "python script"]
+        );
+    }
+
     #[tokio::test]
     async fn test_empty_directory() {
         let temp_dir = TempDir::new().unwrap();
@@ -505,6 +924,28 @@ def main():
         assert!(result.todos[0].description.contains("Nested file"));
     }
 
+    #[tokio::test]
+    async fn test_scan_with_reports_each_file() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.rs"), "// TODO: one\n// FIXME: two").unwrap();
+        fs::write(temp_dir.path().join("b.rs"), "// TODO: three").unwrap();
+        fs::write(temp_dir.path().join("c.rs"), "fn main() {}").unwrap();
+
+        let scanner = Scanner::new(crate::config::test_parsing_config()).unwrap();
+        let mut batches = Vec::new();
+        let result = scanner
+            .scan_with(temp_dir.path().to_path_buf(), |found| {
+                batches.push(found.len());
+            })
+            .await
+            .unwrap();
+
+        // files without TODOs are not reported
+        batches.sort_unstable();
+        assert_eq!(batches, vec![1, 2]);
+        assert_eq!(result.todos.len(), 3);
+    }
+
     #[tokio::test]
     async fn test_large_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -640,6 +1081,47 @@ def main():
         }
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore = "benchmark — run with --ignored --nocapture"]
+    async fn bench_parallel_parsing_speedup() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let mut content = String::new();
+        for i in 0..20_000 {
+            if i % 200 == 0 {
+                writeln!(content, "    // TODO: item {i}").unwrap();
+            } else {
+                writeln!(content, "    let value_{i} = compute(x, y); // comment").unwrap();
+            }
+        }
+        for i in 0..64 {
+            fs::write(temp_dir.path().join(format!("file_{i}.rs")), &content).unwrap();
+        }
+
+        let config = crate::config::test_parsing_config();
+        let scanner = Scanner::new(config).unwrap();
+
+        let start = std::time::Instant::now();
+        let mut sequential_count = 0;
+        for entry in fs::read_dir(temp_dir.path()).unwrap() {
+            let path = entry.unwrap().path();
+            let text = fs::read_to_string(&path).unwrap();
+            sequential_count += scanner.parser.parse(&path, &text).unwrap().len();
+        }
+        let sequential_time = start.elapsed();
+
+        let start = std::time::Instant::now();
+        let result = scanner.scan(temp_dir.path().to_path_buf()).await.unwrap();
+        let parallel_time = start.elapsed();
+
+        let cores = std::thread::available_parallelism().map_or(1, std::num::NonZero::get);
+        println!(
+            "parsing 64 files on {cores} cores: sequential {sequential_time:?}, parallel {parallel_time:?} ({:.1}x)",
+            sequential_time.as_secs_f64() / parallel_time.as_secs_f64()
+        );
+        assert_eq!(result.todos.len(), sequential_count);
+    }
+
     #[tokio::test]
     async fn test_resource_limits_in_scan_directory() {
         let temp_dir = TempDir::new().unwrap();
@@ -663,4 +1145,70 @@ def main():
         assert_eq!(scan_result.files_errored, 1);
         assert!(!scan_result.all_files_failed());
     }
+
+    /// Lays out `root/src/lib.rs`, a `root/linked` dir link and a
+    /// `root/loop` cycle inside the root, and `root/outside` pointing at a
+    /// sibling directory.
+    #[cfg(unix)]
+    fn symlinked_tree() -> (TempDir, std::path::PathBuf) {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("root");
+        let elsewhere = temp_dir.path().join("elsewhere");
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(&elsewhere).unwrap();
+        fs::write(root.join("src/lib.rs"), "// TODO: inside").unwrap();
+        fs::write(elsewhere.join("ext.rs"), "// TODO: outside").unwrap();
+        symlink(root.join("src"), root.join("linked")).unwrap();
+        symlink(&root, root.join("src/loop")).unwrap();
+        symlink(&elsewhere, root.join("outside")).unwrap();
+        (temp_dir, root)
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case::not_followed(false, false, &["inside"])]
+    #[case::followed_within_root(true, false, &["inside"])]
+    #[case::followed_outside_root(true, true, &["inside", "outside"])]
+    #[tokio::test]
+    async fn test_follow_symlinks(
+        #[case] follow: bool,
+        #[case] allow_outside: bool,
+        #[case] expected: &[&str],
+    ) {
+        let (_temp_dir, root) = symlinked_tree();
+        let mut config = crate::config::test_parsing_config();
+        config.follow_symlinks = follow;
+        config.allow_symlinks_outside_root = allow_outside;
+        let scanner = Scanner::new(config).unwrap();
+
+        let result = scanner.scan(root).await.unwrap();
+
+        let mut descriptions: Vec<_> = result
+            .todos
+            .iter()
+            .map(|t| t.description.as_str())
+            .collect();
+        descriptions.sort_unstable();
+        assert_eq!(descriptions, expected);
+        assert_eq!(result.files_errored, 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_file_symlink_only_scanned_when_following() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("real.rs");
+        let link = temp_dir.path().join("link.rs");
+        fs::write(&target, "// TODO: real").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let mut config = crate::config::test_parsing_config();
+        assert!(!Scanner::new(config.clone())
+            .unwrap()
+            .should_file_be_scanned(&link));
+        config.follow_symlinks = true;
+        assert!(Scanner::new(config).unwrap().should_file_be_scanned(&link));
+    }
 }
@@ -1,6 +1,9 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use ignore::{overrides::OverrideBuilder, WalkBuilder};
+use ignore::{
+    overrides::{Override, OverrideBuilder},
+    Match, WalkBuilder,
+};
 
 use super::error::TowlScannerError;
 use super::types::Scanner;
@@ -8,33 +11,121 @@ use super::types::Scanner;
 impl Scanner {
     /// Builds a walker with gitignore semantics (`.gitignore`, `.ignore`, git
     /// excludes — within git repositories) that also prunes entries matching
-    /// the configured `exclude_patterns`.
+    /// the configured `exclude_patterns` and, when `include_patterns` is
+    /// non-empty, skips files that match none of them.
+    ///
+    /// Precedence: gitignore rules and `exclude_patterns` always win; an
+    /// include pattern only narrows what is left and never re-includes an
+    /// ignored or excluded path. Includes apply to files only, so directories
+    /// are still descended into.
+    ///
+    /// With `follow_symlinks` set, links are followed and, unless
+    /// `allow_symlinks_outside_root` is also set, links that resolve outside
+    /// the scan root are pruned before they are descended into.
     ///
     /// # Errors
-    /// Returns `TowlScannerError::UnableToWalkFile` if exclude patterns are invalid.
+    /// Returns `TowlScannerError::UnableToWalkFile` if exclude or include patterns are invalid.
     pub(super) fn build_walker(&self, path: &Path) -> Result<ignore::Walk, TowlScannerError> {
         let mut builder = WalkBuilder::new(path);
-        builder.hidden(false).follow_links(false);
+        builder
+            .hidden(false)
+            .follow_links(self.config.follow_symlinks);
 
-        if !self.config.exclude_patterns.is_empty() {
-            // Excludes are applied via filter_entry rather than
+        let filter = self.build_path_filter(path)?;
+        let confine_to = self.symlink_root(path);
+        if !filter.is_empty() || confine_to.is_some() {
+            // Excludes and includes are applied via filter_entry rather than
             // WalkBuilder::overrides: an override whitelist would take
             // precedence over gitignore rules and silently disable them.
-            let mut excludes = OverrideBuilder::new(path);
-            for pattern in &self.config.exclude_patterns {
-                excludes.add(&format!("!{pattern}"))?;
-            }
-            let excludes = excludes.build()?;
-
             builder.filter_entry(move |entry| {
                 let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
-                !matches!(
-                    excludes.matched(entry.path(), is_dir),
-                    ignore::Match::Ignore(_)
-                )
+                let confined = confine_to.as_ref().map_or(true, |root| {
+                    !entry.path_is_symlink() || resolves_within(entry.path(), root)
+                });
+                confined && filter.allows(entry.path(), is_dir)
             });
         }
 
         Ok(builder.build())
     }
+
+    /// The canonical scan root that followed symlinks must resolve inside, or
+    /// `None` when links are not followed or may point anywhere.
+    pub(super) fn symlink_root(&self, root: &Path) -> Option<PathBuf> {
+        if !self.config.follow_symlinks || self.config.allow_symlinks_outside_root {
+            return None;
+        }
+        // clone: an unresolvable root is reported when it is read
+        Some(root.canonicalize().unwrap_or_else(|_| root.to_path_buf()))
+    }
+
+    /// Compiles `exclude_patterns` and `include_patterns` relative to `root`.
+    ///
+    /// # Errors
+    /// Returns `TowlScannerError::UnableToWalkFile` if a pattern is not a valid glob.
+    pub(super) fn build_path_filter(&self, root: &Path) -> Result<PathFilter, TowlScannerError> {
+        let excludes = if self.config.exclude_patterns.is_empty() {
+            None
+        } else {
+            let mut excludes = OverrideBuilder::new(root);
+            for pattern in &self.config.exclude_patterns {
+                excludes.add(&format!("!{pattern}"))?;
+            }
+            Some(excludes.build()?)
+        };
+
+        let includes = if self.config.include_patterns.is_empty() {
+            None
+        } else {
+            let mut includes = OverrideBuilder::new(root);
+            for pattern in &self.config.include_patterns {
+                includes.add(pattern)?;
+            }
+            Some(includes.build()?)
+        };
+
+        Ok(PathFilter { excludes, includes })
+    }
+}
+
+/// Returns true if `path` resolves to a location under the canonical `root`.
+pub(super) fn resolves_within(path: &Path, root: &Path) -> bool {
+    path.canonicalize().is_ok_and(|real| real.starts_with(root))
+}
+
+/// Compiled `exclude_patterns` / `include_patterns` for one scan root.
+pub(super) struct PathFilter {
+    excludes: Option<Override>,
+    includes: Option<Override>,
+}
+
+impl PathFilter {
+    fn is_empty(&self) -> bool {
+        self.excludes.is_none() && self.includes.is_none()
+    }
+
+    /// Checks a single walk entry. Parent directories are assumed to have
+    /// been checked already, as the walker prunes excluded directories.
+    fn allows(&self, path: &Path, is_dir: bool) -> bool {
+        let excluded = self
+            .excludes
+            .as_ref()
+            .is_some_and(|excludes| matches!(excludes.matched(path, is_dir), Match::Ignore(_)));
+        let included = is_dir
+            || self.includes.as_ref().map_or(true, |includes| {
+                matches!(includes.matched(path, false), Match::Whitelist(_))
+            });
+        !excluded && included
+    }
+
+    /// Checks a file path under `root` together with each of its parent
+    /// directories, for file lists that were not produced by a walk.
+    pub(super) fn allows_file(&self, root: &Path, path: &Path) -> bool {
+        let parent_excluded = path
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| *dir != root && dir.starts_with(root))
+            .any(|dir| !self.allows(dir, true));
+        !parent_excluded && self.allows(path, false)
+    }
 }
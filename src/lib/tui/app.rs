@@ -52,12 +52,13 @@ pub enum SortField {
     Type,
 }
 
-const ALL_TYPES: [TodoType; 5] = [
+const ALL_TYPES: [TodoType; 6] = [
     TodoType::Todo,
     TodoType::Fixme,
     TodoType::Hack,
     TodoType::Note,
     TodoType::Bug,
+    TodoType::Placeholder,
 ];
 
 /// Core TUI application state: TODO list, selection, filtering, sorting, and mode.
@@ -149,11 +149,16 @@ async fn send_event(tx: &mpsc::Sender<CreationEvent>, event: CreationEvent) {
 }
 
 async fn init_github_client(
-    config: &GitHubConfig,
+    config: &mut GitHubConfig,
     tx: &mpsc::Sender<CreationEvent>,
 ) -> Option<GitHubClient> {
     send_event(tx, CreationEvent::Phase("Initializing client...".into())).await;
 
+    if let Err(e) = config.resolve_token().await {
+        send_event(tx, CreationEvent::Error(format!("Token: {e}"))).await;
+        return None;
+    }
+
     let mut client = match GitHubClient::new(config) {
         Ok(c) => c,
         Err(e) => {
@@ -178,11 +183,11 @@ async fn init_github_client(
 
 async fn create_issues_task(
     todos: Vec<TodoComment>,
-    github_config: GitHubConfig,
+    mut github_config: GitHubConfig,
     repo_root: PathBuf,
     tx: mpsc::Sender<CreationEvent>,
 ) {
-    let Some(mut client) = init_github_client(&github_config, &tx).await else {
+    let Some(mut client) = init_github_client(&mut github_config, &tx).await else {
         send_event(&tx, CreationEvent::Finished).await;
         return;
     };
@@ -226,7 +231,8 @@ async fn create_issues_task(
             CreationEvent::Phase("Replacing TODOs in files...".into()),
         )
         .await;
-        let result = Processor::replace_todos(&repo_root, &replacements).await;
+        let result =
+            Processor::replace_todos(&repo_root, &github_config.web_url(), &replacements).await;
         for (path, err) in &result.errors {
             send_event(
                 &tx,
@@ -1,13 +1,19 @@
-use towl::config::{GitHubConfig, LlmConfig, ParsingConfig, TowlConfig};
+use std::collections::BTreeMap;
+use towl::config::{
+    DiscoveryMode, GitHubConfig, LlmConfig, MergeConfig, ParsingConfig, TowlConfig,
+};
 
 #[must_use]
 pub fn mock_towl_config() -> TowlConfig {
     TowlConfig {
+        extends: Vec::new(),
+        merge: MergeConfig::default(),
         parsing: ParsingConfig {
             file_extensions: ["rs".to_string(), "py".to_string(), "txt".to_string()]
                 .into_iter()
                 .collect(),
             exclude_patterns: vec!["target/*".to_string(), "*.log".to_string()],
+            include_patterns: Vec::new(),
             comment_prefixes: vec![
                 r"//".to_string(),
                 r"^\s*#".to_string(),
@@ -26,8 +32,14 @@ pub fn mock_towl_config() -> TowlConfig {
                 r"^\s*def\s+(\w+)".to_string(),
             ],
             include_context_lines: 3,
+            languages: BTreeMap::new(),
+            discovery: DiscoveryMode::Walk,
+            follow_symlinks: false,
+            allow_symlinks_outside_root: false,
+            detect_placeholders: false,
         },
         github: GitHubConfig::default(),
         llm: LlmConfig::default(),
+        profile: BTreeMap::new(),
     }
 }
@@ -71,6 +71,11 @@ fn test_scan_table_format(test_project: TempDir) {
 #[case("csv", "csv")]
 #[case("markdown", "md")]
 #[case("toml", "toml")]
+#[case("sarif", "sarif")]
+#[case("junit", "xml")]
+#[case("checkstyle", "xml")]
+#[case("html", "html")]
+#[case("jsonl", "jsonl")]
 fn test_scan_file_output_formats(
     test_project: TempDir,
     #[case] format: &str,
@@ -92,6 +97,238 @@ fn test_scan_file_output_formats(
     assert!(output_file.exists());
 }
 
+#[rstest]
+fn test_scan_jsonl_to_stdout(test_project: TempDir) {
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.arg("scan")
+        .arg(test_project.path())
+        .arg("-N")
+        .arg("-f")
+        .arg("jsonl")
+        .arg("-t")
+        .arg("fixme");
+
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let stdout = String::from_utf8(output).unwrap();
+    let records: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(records.len(), 1, "{stdout}");
+    assert_eq!(records[0]["todo_type"], "Fixme");
+    assert_eq!(records[0]["description"], "handle error case");
+    assert!(records[0]["analysis"].is_null());
+}
+
+#[rstest]
+#[case(&["-f", "json"])]
+#[case(&["-f", "json", "-o", "-"])]
+fn test_scan_json_to_stdout(test_project: TempDir, #[case] args: &[&str]) {
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.arg("scan")
+        .arg(test_project.path())
+        .arg("-N")
+        .args(args);
+
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let report: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(report["summary"]["total_todos"], 3, "{report}");
+}
+
+#[rstest]
+fn test_scan_mismatched_extension_warns(test_project: TempDir) {
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(test_project.path())
+        .arg("scan")
+        .arg(".")
+        .arg("-N")
+        .arg("-f")
+        .arg("json")
+        .arg("-o")
+        .arg("report.out");
+
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("expected '.json'"));
+    let report = fs::read_to_string(test_project.path().join("report.out")).unwrap();
+    assert!(serde_json::from_str::<serde_json::Value>(&report).is_ok());
+}
+
+#[rstest]
+fn test_scan_format_inferred_from_extension(test_project: TempDir) {
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(test_project.path())
+        .arg("scan")
+        .arg(".")
+        .arg("-N")
+        .arg("-o")
+        .arg("todos.sarif");
+
+    cmd.assert().success();
+    let log = fs::read_to_string(test_project.path().join("todos.sarif")).unwrap();
+    let log: serde_json::Value = serde_json::from_str(&log).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+}
+
+#[rstest]
+#[case("report.xml")]
+#[case("report.out")]
+fn test_scan_uninferable_extension_needs_format(test_project: TempDir, #[case] output: &str) {
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(test_project.path())
+        .arg("scan")
+        .arg(".")
+        .arg("-N")
+        .arg("-o")
+        .arg(output);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("pass --format"));
+    assert!(!test_project.path().join(output).exists());
+}
+
+#[rstest]
+fn test_scan_template_output(test_project: TempDir) {
+    let template = test_project.path().join("report.tmpl");
+    fs::write(
+        &template,
+        "{{ summary.total }} TODOs, files scanned: {{ scan.files_scanned }}\n\
+         {% for group in groups %}{% for todo in group.todos %}\
+         [{{ group.name }}] {{ todo.description }}\n{% endfor %}{% endfor %}",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(test_project.path())
+        .arg("scan")
+        .arg(".")
+        .arg("-N")
+        .arg("-f")
+        .arg("template")
+        .arg("--template")
+        .arg(&template);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("3 TODOs, files scanned: 1"))
+        .stdout(predicate::str::contains("[fixme] handle error case"));
+}
+
+#[rstest]
+fn test_scan_template_error_reports_line(test_project: TempDir) {
+    let template = test_project.path().join("broken.tmpl");
+    fs::write(&template, "fine\n{{ summary.total }\nmore\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(test_project.path())
+        .arg("scan")
+        .arg(".")
+        .arg("-N")
+        .arg("-f")
+        .arg("template")
+        .arg("--template")
+        .arg("broken.tmpl");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("broken.tmpl:2: syntax error"));
+}
+
+#[rstest]
+fn test_scan_template_format_requires_template(test_project: TempDir) {
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.arg("scan")
+        .arg(test_project.path())
+        .arg("-N")
+        .arg("-f")
+        .arg("template");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--template <PATH>"));
+}
+
+#[rstest]
+fn test_scan_html_report_links_to_source(test_project: TempDir) {
+    let output_file = test_project.path().join("report.html");
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(test_project.path())
+        .env("TOWL_GITHUB_OWNER", "acme")
+        .env("TOWL_GITHUB_REPO", "widgets")
+        .arg("scan")
+        .arg(".")
+        .arg("-N")
+        .arg("-f")
+        .arg("html")
+        .arg("-o")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let html = std::fs::read_to_string(&output_file).unwrap();
+    assert!(html.contains("implement this function"));
+    // not a git repository, so links fall back to main
+    assert!(html.contains(r#"href="https://github.com/acme/widgets/blob/main/test.rs#L2""#));
+}
+
+#[rstest]
+fn test_scan_github_actions_annotations_and_summary(test_project: TempDir) {
+    let summary_file = test_project.path().join("step_summary.md");
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(test_project.path())
+        .env("GITHUB_STEP_SUMMARY", &summary_file)
+        .arg("scan")
+        .arg(test_project.path())
+        .arg("-N")
+        .arg("-f")
+        .arg("github-actions");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("::warning file="))
+        .stdout(predicate::str::contains("::handle error case"));
+    let summary = std::fs::read_to_string(&summary_file).unwrap();
+    assert!(summary.contains("# TODO Comments"));
+    assert!(summary.contains("handle error case"));
+}
+
+#[rstest]
+fn test_scan_junit_failures_by_type(test_project: TempDir) {
+    let output_file = test_project.path().join("report.xml");
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(test_project.path())
+        .arg("scan")
+        .arg(test_project.path())
+        .arg("-N")
+        .arg("-f")
+        .arg("junit")
+        .arg("-o")
+        .arg(&output_file)
+        .arg("--junit-failures")
+        .arg("todo,hack");
+
+    cmd.assert().success();
+    let report = std::fs::read_to_string(&output_file).unwrap();
+    assert!(report.contains(r#"<failure message="TODO: implement this function" type="TODO">"#));
+    assert!(report.contains(r#"<skipped message="FIXME: handle error case"/>"#));
+}
+
+#[rstest]
+#[case(&["-N", "-f", "sarif", "--junit-failures", "todo"])]
+#[case(&["-N", "--junit-failures", "bug"])]
+#[case(&["--junit-failures", "bug"])]
+fn test_junit_failures_requires_junit_format(test_project: TempDir, #[case] args: &[&str]) {
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.arg("scan").arg(test_project.path()).args(args);
+
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "--junit-failures only applies to --format junit",
+    ));
+}
+
 #[rstest]
 #[case("todo", "implement this func", "handle error case")]
 #[case("fixme", "handle error case", "implement this func")]
@@ -117,6 +354,74 @@ fn test_todo_type_filtering(
         .stdout(predicate::str::contains(should_not_contain).not());
 }
 
+#[rstest]
+#[case(&["src/**"], "from src", "from tests")]
+#[case(&["tests/**"], "from tests", "from src")]
+#[case(&["src/**", "tests/**"], "from tests", "from root")]
+fn test_include_flag(
+    #[case] include: &[&str],
+    #[case] should_contain: &str,
+    #[case] should_not_contain: &str,
+) {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    for dir in ["src", "tests"] {
+        fs::create_dir(temp_dir.path().join(dir)).expect("Failed to create dir");
+        fs::write(
+            temp_dir.path().join(dir).join("lib.rs"),
+            format!("// TODO: from {dir}"),
+        )
+        .expect("Failed to write test file");
+    }
+    fs::write(temp_dir.path().join("build.rs"), "// TODO: from root")
+        .expect("Failed to write test file");
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.arg("scan")
+        .arg(temp_dir.path())
+        .arg("--non-interactive")
+        .arg("--format")
+        .arg("terminal");
+    for glob in include {
+        cmd.arg("--include").arg(glob);
+    }
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(should_contain))
+        .stdout(predicate::str::contains(should_not_contain).not());
+}
+
+#[cfg(unix)]
+#[rstest]
+#[case(false)]
+#[case(true)]
+fn test_follow_symlinks_flag(#[case] follow: bool) {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let vendored = temp_dir.path().join(".vendored");
+    fs::create_dir(&vendored).expect("Failed to create dir");
+    fs::write(vendored.join("lib.rs"), "// TODO: from vendored")
+        .expect("Failed to write test file");
+    fs::write(temp_dir.path().join(".ignore"), ".vendored/\n").expect("Failed to write ignore");
+    std::os::unix::fs::symlink(&vendored, temp_dir.path().join("src"))
+        .expect("Failed to create symlink");
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.arg("scan")
+        .arg(temp_dir.path())
+        .arg("--non-interactive")
+        .arg("--format")
+        .arg("terminal");
+    if follow {
+        cmd.arg("--follow-symlinks");
+    }
+
+    let output = cmd.assert().success().get_output().stdout.clone();
+    assert_eq!(
+        String::from_utf8_lossy(&output).contains("from vendored"),
+        follow
+    );
+}
+
 #[rstest]
 fn test_verbose_flag(test_project: TempDir) {
     let mut cmd = Command::cargo_bin("towl").unwrap();
@@ -148,6 +453,43 @@ fn test_init_command() {
     assert!(config_path.exists());
 }
 
+#[test]
+fn test_init_yes_proposes_detected_languages() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    git(root, &["init", "-q"]);
+    git(
+        root,
+        &[
+            "remote",
+            "add",
+            "origin",
+            "https://github.com/owner/repo.git",
+        ],
+    );
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::create_dir_all(root.join("node_modules/dep")).unwrap();
+    fs::write(root.join("src/app.ts"), "// TODO: wire up\n").unwrap();
+    fs::write(
+        root.join("node_modules/dep/index.js"),
+        "// TODO: not ours\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(root).args(["init", "--yes"]);
+
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("Detected typescript (1 files)"))
+        .stderr(predicate::str::contains("Excluding node_modules/*"));
+
+    let content = fs::read_to_string(root.join(".towl.toml")).unwrap();
+    assert!(content.contains("[parsing.languages.typescript]"));
+    assert!(content.contains("\"node_modules/*\""));
+    assert!(!content.contains("[parsing.languages.rust]"));
+}
+
 #[rstest]
 fn test_config_command() {
     let mut cmd = Command::cargo_bin("towl").unwrap();
@@ -158,6 +500,228 @@ fn test_config_command() {
         .stderr(predicate::str::contains("Towl Configuration"));
 }
 
+#[rstest]
+fn test_config_show_origin_follows_extends() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    fs::create_dir_all(temp_dir.path().join("shared")).unwrap();
+    fs::create_dir_all(temp_dir.path().join("repo")).unwrap();
+    fs::write(
+        temp_dir.path().join("shared/base.toml"),
+        "[parsing]\ninclude_context_lines = 4\nexclude_patterns = [\"vendor/*\"]\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("repo/.towl.toml"),
+        "extends = [\"../shared/base.toml\"]\n\n[merge]\nappend = [\"parsing.exclude_patterns\"]\n\n[parsing]\nexclude_patterns = [\"gen/*\"]\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(temp_dir.path().join("repo"))
+        .args(["config", "--show-origin"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "base.toml\tparsing.include_context_lines = 4\n",
+        ))
+        .stdout(predicate::str::contains(
+            "base.toml+file:.towl.toml\tparsing.exclude_patterns = [\"vendor/*\", \"gen/*\"]\n",
+        ))
+        .stdout(predicate::str::contains("default\tllm.max_retries = "));
+}
+
+#[rstest]
+fn test_config_profile_shows_merged_result() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    fs::write(
+        temp_dir.path().join(".towl.toml"),
+        "[llm]\nmodel = \"team\"\nmax_retries = 4\n\n[profile.ci.llm]\nmodel = \"cheap\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .args(["config", "--profile", "ci", "--show-origin"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "profile:ci(file:.towl.toml)\tllm.model = \"cheap\"\n",
+        ))
+        .stdout(predicate::str::contains(
+            "file:.towl.toml\tllm.max_retries = 4\n",
+        ));
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .env("TOWL_PROFILE", "ci")
+        .arg("config");
+
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("profile ci"))
+        .stderr(predicate::str::contains("cheap"));
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .args(["config", "--profile", "nightly"]);
+
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "`nightly` is not defined (available: ci)",
+    ));
+}
+
+#[rstest]
+fn test_config_set_and_generic_env_overrides() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    fs::write(
+        temp_dir.path().join(".towl.toml"),
+        "[parsing]\ninclude_context_lines = 4\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .env("TOWL__PARSING__INCLUDE_CONTEXT_LINES", "6")
+        .env("TOWL__LLM__MAX_RETRIES", "1")
+        .args([
+            "config",
+            "--show-origin",
+            "--set",
+            "parsing.include_context_lines=8",
+        ]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "cli:--set\tparsing.include_context_lines = 8\n",
+        ))
+        .stdout(predicate::str::contains(
+            "env:TOWL__LLM__MAX_RETRIES\tllm.max_retries = 1\n",
+        ));
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .args(["config", "--set", "parsing.include_context_lines=99"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("context_lines value 99"));
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .args(["config", "--set", "llm.modle=x"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown config key `llm.modle`"));
+}
+
+#[rstest]
+fn test_global_config_applies_under_project_config() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let xdg = temp_dir.path().join("xdg");
+    fs::create_dir_all(xdg.join("towl")).unwrap();
+    fs::write(
+        xdg.join("towl/config.toml"),
+        "[llm]\nprovider = \"openai\"\nmodel = \"personal\"\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join(".towl.toml"),
+        "[llm]\nmodel = \"team\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .env("XDG_CONFIG_HOME", &xdg)
+        .env_remove("TOWL_LLM_PROVIDER")
+        .env_remove("TOWL_LLM_MODEL")
+        .args(["config", "--show-origin"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "towl/config.toml\tllm.provider = \"openai\"\n",
+        ))
+        .stdout(predicate::str::contains(
+            "file:.towl.toml\tllm.model = \"team\"\n",
+        ));
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .env("XDG_CONFIG_HOME", &xdg)
+        .arg("config");
+
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("1. built-in defaults"))
+        .stderr(predicate::str::contains("towl/config.toml"))
+        .stderr(predicate::str::contains("3. project config .towl.toml"))
+        .stderr(predicate::str::contains("4. environment variables"));
+}
+
+#[rstest]
+fn test_config_validate_accepts_initialized_config() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let config_path = temp_dir.path().join("towl.toml");
+    Command::cargo_bin("towl")
+        .unwrap()
+        .arg("init")
+        .arg("--path")
+        .arg(&config_path)
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.args(["config", "validate"]).arg(&config_path);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::ends_with(": ok\n"));
+}
+
+#[rstest]
+fn test_config_validate_reports_every_problem() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let config_path = temp_dir.path().join("towl.toml");
+    fs::write(
+        &config_path,
+        "[parsing]\nfollow_symlinks = \"maybe\"\ntypo = 1\nfunction_patterns = [\"(\"]\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.arg("config")
+        .arg("--config")
+        .arg(&config_path)
+        .arg("validate");
+
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("towl.toml:2:1:"))
+        .stdout(predicate::str::contains(
+            "towl.toml:3:1: unknown key `parsing.typo`",
+        ))
+        .stdout(predicate::str::contains(
+            "towl.toml:4:22: Pattern ( is not a valid regex",
+        ))
+        .stderr(predicate::str::contains("3 problem(s)"));
+}
+
+#[rstest]
+fn test_config_schema_command() {
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.args(["config", "schema"]);
+
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let schema: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(schema["title"], "towl configuration");
+    assert!(schema["properties"]["parsing"].is_object());
+}
+
 #[rstest]
 fn test_nonexistent_path() {
     let mut cmd = Command::cargo_bin("towl").unwrap();
@@ -183,3 +747,133 @@ fn test_invalid_todo_type(test_project: TempDir) {
 
     cmd.assert().failure();
 }
+
+fn git(root: &std::path::Path, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .args(["-c", "user.name=towl", "-c", "user.email=towl@example.com"])
+        .args(args)
+        .current_dir(root)
+        .status()
+        .expect("Failed to run git");
+    assert!(status.success(), "git {args:?} failed");
+}
+
+#[test]
+fn test_scan_git_ref() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    git(root, &["init", "-q"]);
+    fs::write(root.join("lib.rs"), "// TODO: released code\n").expect("Failed to write test file");
+    git(root, &["add", "-A"]);
+    git(root, &["commit", "-q", "-m", "release"]);
+    git(root, &["tag", "v1.2.0"]);
+    fs::write(root.join("lib.rs"), "// TODO: work in progress\n")
+        .expect("Failed to write test file");
+
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.arg("scan")
+        .arg(root)
+        .arg("--non-interactive")
+        .arg("--ref")
+        .arg("v1.2.0")
+        .arg("--format")
+        .arg("terminal");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("released code"))
+        .stdout(predicate::str::contains("work in progress").not());
+}
+
+#[rstest]
+#[case(&["--ref", "main"])]
+#[case(&["--ref", "main", "-N", "--discovery", "git"])]
+fn test_scan_git_ref_argument_conflicts(#[case] args: &[&str]) {
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.arg("scan").args(args);
+
+    cmd.assert().failure();
+}
+
+#[test]
+fn test_scan_unknown_git_ref() {
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.arg("scan")
+        .arg("--non-interactive")
+        .arg("--ref")
+        .arg("no-such-ref-anywhere");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid git ref"));
+}
+
+#[rstest]
+#[case(
+    "src/foo.rs",
+    "// TODO: from buffer\nlet todo = \"TODO: not a comment\";",
+    "from buffer",
+    "not a comment"
+)]
+#[case(
+    "tools/gen.py",
+    "# TODO: from buffer\n// TODO: not python",
+    "from buffer",
+    "not python"
+)]
+fn test_scan_stdin(
+    #[case] filename: &str,
+    #[case] content: &str,
+    #[case] should_contain: &str,
+    #[case] should_not_contain: &str,
+) {
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.arg("scan")
+        .arg("--non-interactive")
+        .arg("--stdin")
+        .arg("--stdin-filename")
+        .arg(filename)
+        .arg("--format")
+        .arg("terminal")
+        .write_stdin(content);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(should_contain))
+        .stdout(predicate::str::contains(filename))
+        .stdout(predicate::str::contains(should_not_contain).not());
+}
+
+#[rstest]
+#[case(false, "")]
+#[case(true, "wire up the cache")]
+fn test_placeholders_flag(#[case] placeholders: bool, #[case] expected: &str) {
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.arg("scan")
+        .arg("--non-interactive")
+        .arg("--stdin")
+        .arg("--stdin-filename")
+        .arg("lib.rs")
+        .arg("--format")
+        .arg("terminal")
+        .write_stdin("fn cache() {\n    todo!(\"wire up the cache\")\n}\n");
+    if placeholders {
+        cmd.arg("--placeholders");
+    }
+
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let stdout = String::from_utf8_lossy(&output);
+    assert_eq!(stdout.contains("PLACEHOLDER"), placeholders, "{stdout}");
+    assert!(stdout.contains(expected));
+}
+
+#[rstest]
+#[case(&["--stdin"])]
+#[case(&["-N", "--stdin-filename", "a.rs"])]
+#[case(&["-N", "--stdin", "--ref", "main"])]
+fn test_scan_stdin_argument_conflicts(#[case] args: &[&str]) {
+    let mut cmd = Command::cargo_bin("towl").unwrap();
+    cmd.arg("scan").args(args);
+
+    cmd.assert().failure();
+}
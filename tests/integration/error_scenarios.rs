@@ -174,7 +174,7 @@ fn test_output_to_directory_instead_of_file() {
         .arg("--output")
         .arg(&output_dir);
 
-    // Output path is a directory without extension, so Output::new fails
+    // Output path is a directory, so writing the report fails
     cmd.assert().failure().code(1);
 }
 